@@ -0,0 +1,65 @@
+/// Disjoint-set forest (union-find) over elements 0..n, with path compression
+/// and union by size, for grouping connected mesh elements.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+  parent: Vec<usize>,
+  size: Vec<usize>,
+}
+
+impl DisjointSet {
+  /// Creates n singleton sets.
+  pub fn new(n: usize) -> Self {
+    Self {
+      parent: (0..n).collect(),
+      size: vec![1; n],
+    }
+  }
+
+  /// Returns the representative element of the set containing x.
+  pub fn find(&mut self, x: usize) -> usize {
+    let mut root = x;
+    while self.parent[root] != root {
+      root = self.parent[root];
+    }
+    let mut x = x;
+    while self.parent[x] != root {
+      let next = self.parent[x];
+      self.parent[x] = root;
+      x = next;
+    }
+    root
+  }
+
+  /// Merges the sets containing x and y. Returns false if they were already in
+  /// the same set.
+  pub fn union(&mut self, x: usize, y: usize) -> bool {
+    let (mut a, mut b) = (self.find(x), self.find(y));
+    if a == b {
+      return false;
+    }
+    if self.size[a] < self.size[b] {
+      ::std::mem::swap(&mut a, &mut b);
+    }
+    self.parent[b] = a;
+    self.size[a] += self.size[b];
+    true
+  }
+
+  /// Returns the set index of each element, with sets numbered 0..k in the
+  /// order their first elements appear, together with the number of sets k.
+  pub fn labels(&mut self) -> (Vec<usize>, usize) {
+    let n = self.parent.len();
+    let mut label_of_root = vec![usize::MAX; n];
+    let mut labels = Vec::with_capacity(n);
+    let mut count = 0;
+    for x in 0..n {
+      let root = self.find(x);
+      if label_of_root[root] == usize::MAX {
+        label_of_root[root] = count;
+        count += 1;
+      }
+      labels.push(label_of_root[root]);
+    }
+    (labels, count)
+  }
+}
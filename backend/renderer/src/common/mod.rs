@@ -1,2 +1,4 @@
 pub mod constants;
+pub mod disjoint_set;
+pub mod random;
 pub mod utils;
\ No newline at end of file
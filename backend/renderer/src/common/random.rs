@@ -0,0 +1,60 @@
+/// A small deterministic pseudo-random number generator (xorshift64*), for
+/// randomized geometric algorithms that need reproducible results without
+/// pulling in external dependencies. Not suitable for cryptographic purposes.
+///
+/// For details please read:
+///
+/// > An Experimental Exploration of Marsaglia's xorshift Generators,
+/// > Scrambled. S. Vigna. ACM Transactions on Mathematical Software, 2016.
+#[derive(Debug, Clone)]
+pub struct Random {
+  state: u64,
+}
+
+impl Random {
+  /// Creates a generator from a seed. Generators created with the same seed
+  /// produce the same sequence.
+  pub fn new(seed: u64) -> Self {
+    // The state of xorshift must never be zero.
+    Self {
+      state: seed ^ 0x9E37_79B9_7F4A_7C15,
+    }.warmed_up()
+  }
+
+  /// Returns the next uniformly distributed 64-bit integer.
+  pub fn next_u64(&mut self) -> u64 {
+    self.state ^= self.state >> 12;
+    self.state ^= self.state << 25;
+    self.state ^= self.state >> 27;
+    self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+  }
+
+  /// Returns the next uniformly distributed float in [0, 1).
+  pub fn next_f64(&mut self) -> f64 {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// Returns the next uniformly distributed integer in [0, bound).
+  pub fn next_usize(&mut self, bound: usize) -> usize {
+    assert!(bound > 0, "Bound must be positive!");
+    (self.next_u64() % bound as u64) as usize
+  }
+
+  /// Shuffles a slice uniformly (Fisher-Yates).
+  pub fn shuffle<T>(&mut self, v: &mut [T]) {
+    for i in (1..v.len()).rev() {
+      let j = self.next_usize(i + 1);
+      v.swap(i, j);
+    }
+  }
+
+  fn warmed_up(mut self) -> Self {
+    if self.state == 0 {
+      self.state = 1;
+    }
+    for _ in 0..4 {
+      self.next_u64();
+    }
+    self
+  }
+}
@@ -1,4 +1,4 @@
-use geometry::{HasSurfaceArea, HasVolume};
+use geometry::{HasSurfaceArea, HasVolume, HasBoundingSphere, BoundingSphere};
 use math::Vector3;
 use std::fmt;
 use std::cmp::PartialEq;
@@ -38,7 +38,7 @@ impl BoundingBox3 {
   }
 
   pub fn max_y(&self) -> f64 {
-    self.max_corner.x()
+    self.max_corner.y()
   }
 
   pub fn max_z(&self) -> f64 {
@@ -61,6 +61,22 @@ impl BoundingBox3 {
     self.max_corner - self.min_corner
   }
 
+  /// Returns the center point of the box.
+  pub fn center(&self) -> Vector3 {
+    (self.min_corner + self.max_corner) / 2.0
+  }
+
+  /// Returns half of the size of the box along each axis, i.e. the distance
+  /// from the center to the faces of the box.
+  pub fn half_size(&self) -> Vector3 {
+    self.size() / 2.0
+  }
+
+  /// Returns true if the given point lies inside the box or on its boundary.
+  pub fn contains(&self, p: Vector3) -> bool {
+    (0..3).all(|i| p[i] >= self.min_corner[i] && p[i] <= self.max_corner[i])
+  }
+
   // Moves this bounding box along a specified direction.
   pub fn move_self(&mut self, dir: Vector3) {
     self.min_corner += dir;
@@ -89,6 +105,21 @@ impl BoundingBox3 {
     Self::new(Vector3::zero(), Vector3::zero())
   }
 
+  /// Returns the tightest bounding box containing all the given points. The
+  /// point list must not be empty.
+  pub fn new_from_points(points: &[Vector3]) -> Self {
+    assert!(!points.is_empty(), "Cannot bound an empty point set!");
+    let mut min_corner = points[0];
+    let mut max_corner = points[0];
+    for p in points.iter().skip(1) {
+      for i in 0..3 {
+        min_corner[i] = min_corner[i].min(p[i]);
+        max_corner[i] = max_corner[i].max(p[i]);
+      }
+    }
+    Self::new(min_corner, max_corner)
+  }
+
   pub fn new_from_nums(min_x: f64, min_y: f64, min_z: f64,
       max_x: f64, max_y: f64, max_z: f64) -> Self {
     Self::new(
@@ -122,6 +153,12 @@ impl HasVolume for BoundingBox3 {
   }
 }
 
+impl HasBoundingSphere for BoundingBox3 {
+  fn bounding_sphere(&self) -> BoundingSphere {
+    BoundingSphere::new(self.center(), self.half_size().len())
+  }
+}
+
 /// Printing the bounding box as
 /// (min_x, min_y, min_z) - (max_x, max_y, max_z).
 impl fmt::Display for BoundingBox3 {
@@ -0,0 +1,237 @@
+use common::constants;
+use common::random::Random;
+use geometry::{
+    BoundingBox3, HasBoundingBox3, HasBoundingSphere, HasSurfaceArea,
+    HasVolume, Triangle3,
+};
+use math::{Matrix3, Vector3};
+use std::cmp::PartialEq;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Definitions and operations of a bounding sphere, represented by its center
+/// and radius.
+#[derive(Debug, Copy, Clone)]
+pub struct BoundingSphere {
+  center: Vector3,
+  radius: f64,
+}
+
+impl BoundingSphere {
+  pub fn center(&self) -> Vector3 {
+    self.center
+  }
+
+  pub fn radius(&self) -> f64 {
+    self.radius
+  }
+
+  /// Returns true if the point lies inside the sphere or on its surface
+  /// (within EPSILON).
+  pub fn contains(&self, p: Vector3) -> bool {
+    (p - self.center).len() <= self.radius + constants::EPSILON
+  }
+
+  /// Grows the sphere (if necessary) to contain a given point, moving its
+  /// center towards the point so that the grown sphere remains tight on the
+  /// opposite side.
+  pub fn expand_self(&mut self, p: Vector3) {
+    let d = (p - self.center).len();
+    if d <= self.radius {
+      return;
+    }
+    let radius = (self.radius + d) / 2.0;
+    self.center += (p - self.center) * ((radius - self.radius) / d);
+    self.radius = radius;
+  }
+
+  pub fn new(center: Vector3, radius: f64) -> Self {
+    assert!(radius >= 0.0, "Radius must not be negative!");
+    Self {
+      center: center,
+      radius: radius,
+    }
+  }
+
+  /// Computes an approximate bounding sphere of a point set with Ritter's
+  /// algorithm in linear time. The result is usually 5% to 20% larger than
+  /// the minimal one. The point list must not be empty. For details:
+  ///
+  /// > An Efficient Bounding Sphere, J. Ritter, Graphics Gems, 1990.
+  pub fn new_ritter(points: &[Vector3]) -> Self {
+    assert!(!points.is_empty(), "Cannot bound an empty point set!");
+    let farthest = |from: Vector3| *points.iter().max_by(|a, b|
+        (**a - from).len2().partial_cmp(&(**b - from).len2()).unwrap()
+    ).unwrap();
+    let p = farthest(points[0]);
+    let q = farthest(p);
+    let mut sphere = Self::new_from_diameter(p, q);
+    for point in points.iter() {
+      sphere.expand_self(*point);
+    }
+    sphere
+  }
+
+  /// Computes the minimal bounding sphere of a point set with Welzl's
+  /// randomized algorithm, in expected linear time. The point list must not
+  /// be empty. For details:
+  ///
+  /// > Smallest Enclosing Disks (Balls and Ellipsoids), E. Welzl, 1991.
+  pub fn new_welzl(points: &[Vector3]) -> Self {
+    assert!(!points.is_empty(), "Cannot bound an empty point set!");
+    let mut points = points.to_vec();
+    // A fixed seed keeps the result reproducible.
+    Random::new(points.len() as u64).shuffle(&mut points);
+
+    // The incremental form of the algorithm: whenever a point is outside of
+    // the current sphere, it must lie on the boundary of the minimal sphere
+    // of all points processed so far, hence we recompute with that point
+    // fixed on the boundary.
+    let mut sphere = Self::new(points[0], 0.0);
+    for i in 1..points.len() {
+      if sphere.contains(points[i]) {
+        continue;
+      }
+      sphere = Self::new(points[i], 0.0);
+      for j in 0..i {
+        if sphere.contains(points[j]) {
+          continue;
+        }
+        sphere = Self::new_from_diameter(points[i], points[j]);
+        for k in 0..j {
+          if sphere.contains(points[k]) {
+            continue;
+          }
+          sphere = Self::new_from_boundary(&[points[i], points[j], points[k]]);
+          for l in 0..k {
+            if !sphere.contains(points[l]) {
+              sphere = Self::new_from_boundary(
+                  &[points[i], points[j], points[k], points[l]]);
+            }
+          }
+        }
+      }
+    }
+    sphere
+  }
+
+  // Returns the sphere whose diameter is the segment between two points.
+  fn new_from_diameter(p: Vector3, q: Vector3) -> Self {
+    Self::new((p + q) / 2.0, (p - q).len() / 2.0)
+  }
+
+  // Returns the smallest sphere passing through all given points (up to 4).
+  // If the points are degenerate (collinear, or coplanar but not
+  // co-circular) so that no such sphere exists, returns the minimal sphere
+  // containing them instead, found by enumerating spheres defined by subsets
+  // of the points.
+  fn new_from_boundary(points: &[Vector3]) -> Self {
+    assert!(!points.is_empty() && points.len() <= 4);
+    let exact = match points.len() {
+      1 => Some(Self::new(points[0], 0.0)),
+      2 => Some(Self::new_from_diameter(points[0], points[1])),
+      3 => Triangle3::new(points[0], points[1], points[2]).circumcenter()
+          .map(|c| Self::new(c, (points[0] - c).len())),
+      _ => Self::new_circumsphere(points[0], points[1], points[2], points[3]),
+    };
+    if let Some(sphere) = exact {
+      if points.iter().all(|p| sphere.contains(*p)) {
+        return sphere;
+      }
+    }
+
+    let mut candidates = Vec::new();
+    for i in 0..points.len() {
+      for j in (i + 1)..points.len() {
+        candidates.push(Self::new_from_diameter(points[i], points[j]));
+        for k in (j + 1)..points.len() {
+          if let Some(c) =
+              Triangle3::new(points[i], points[j], points[k]).circumcenter() {
+            candidates.push(Self::new(c, (points[i] - c).len()));
+          }
+        }
+      }
+    }
+    candidates.into_iter()
+        .filter(|s| points.iter().all(|p| s.contains(*p)))
+        .min_by(|a, b| a.radius.partial_cmp(&b.radius).unwrap())
+        .unwrap_or_else(|| Self::new(points[0], 0.0))
+  }
+
+  // Returns the sphere passing through four points, or None if they are
+  // coplanar.
+  fn new_circumsphere(a: Vector3, b: Vector3, c: Vector3, d: Vector3)
+      -> Option<Self> {
+    // Solves |x - a|^2 = |x - p|^2 for p in b, c, d, which is linear in x:
+    // 2 * (p - a) * x = |p|^2 - |a|^2.
+    let m = Matrix3::new_from_vectors((b - a) * 2.0, (c - a) * 2.0,
+        (d - a) * 2.0);
+    let det = m.det();
+    let scale = (b - a).len() * (c - a).len() * (d - a).len();
+    if det.abs() <= constants::EPSILON * 8.0 * scale {
+      return None;
+    }
+    let rhs = Vector3::new(b.len2() - a.len2(), c.len2() - a.len2(),
+        d.len2() - a.len2());
+    // Cramer's rule.
+    let mut center = Vector3::zero();
+    for i in 0..3 {
+      let mut mi = m.t();
+      mi[i] = rhs;
+      center[i] = mi.det() / det;
+    }
+    Some(Self::new(center, (a - center).len()))
+  }
+}
+
+/// Overriding == to allow comparison within error bounds.
+impl PartialEq for BoundingSphere {
+  fn eq(&self, rhs: &Self) -> bool {
+    self.center == rhs.center
+        && (self.radius - rhs.radius).abs() < constants::EPSILON
+  }
+}
+
+impl HasSurfaceArea for BoundingSphere {
+  fn surface_area(&self) -> f64 {
+    4.0 * PI * self.radius * self.radius
+  }
+}
+
+impl HasVolume for BoundingSphere {
+  fn volume(&self) -> f64 {
+    4.0 / 3.0 * PI * self.radius * self.radius * self.radius
+  }
+}
+
+impl HasBoundingBox3 for BoundingSphere {
+  fn bounding_box3(&self) -> BoundingBox3 {
+    let r = Vector3::identity(self.radius);
+    BoundingBox3::new(self.center - r, self.center + r)
+  }
+}
+
+/// The minimal bounding sphere of a point set.
+impl HasBoundingSphere for [Vector3] {
+  fn bounding_sphere(&self) -> BoundingSphere {
+    BoundingSphere::new_welzl(self)
+  }
+}
+
+/// The minimal bounding sphere of a set of triangles.
+impl HasBoundingSphere for [Triangle3] {
+  fn bounding_sphere(&self) -> BoundingSphere {
+    let points: Vec<Vector3> = self.iter()
+        .flat_map(|t| vec![t.v(0), t.v(1), t.v(2)])
+        .collect();
+    BoundingSphere::new_welzl(&points)
+  }
+}
+
+/// Printing the sphere as:
+/// BoundingSphere(center, radius)
+impl fmt::Display for BoundingSphere {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "BoundingSphere({}, {})", &self.center, &self.radius)
+  }
+}
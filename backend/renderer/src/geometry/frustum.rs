@@ -0,0 +1,116 @@
+use geometry::{BoundingBox3, Plane};
+use math::{Matrix4, Vector3};
+
+/// Result of classifying a volume against a frustum.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Containment {
+  /// The volume lies completely inside.
+  INSIDE,
+  /// The volume lies completely outside.
+  OUTSIDE,
+  /// The volume (possibly) crosses the boundary.
+  INTERSECTING,
+}
+
+/// Definitions of a view frustum, bounded by six planes whose normals point
+/// to the interior. Planes are stored in the order of left, right, bottom,
+/// top, near and far.
+///
+/// Classification of volumes is conservative: a volume reported as OUTSIDE is
+/// guaranteed to be outside, while volumes near the edges or corners of the
+/// frustum might be reported as INTERSECTING even if they are outside. This
+/// is the desired behavior for culling.
+#[derive(Debug, Copy, Clone)]
+pub struct Frustum {
+  planes: [Plane; 6],
+}
+
+impl Frustum {
+  pub const LEFT: usize = 0;
+  pub const RIGHT: usize = 1;
+  pub const BOTTOM: usize = 2;
+  pub const TOP: usize = 3;
+  pub const NEAR: usize = 4;
+  pub const FAR: usize = 5;
+
+  /// Returns the plane at a given index, see constants for indexing.
+  pub fn plane(&self, ind: usize) -> Plane {
+    assert!(ind < 6, "Index out of bound!");
+    self.planes[ind]
+  }
+
+  /// Returns true if the point lies inside the frustum or on its boundary.
+  pub fn contains_point(&self, p: Vector3) -> bool {
+    self.planes.iter().all(|plane| plane.contains_in_half_space(p))
+  }
+
+  /// Classifies an axis-aligned bounding box against the frustum. For each
+  /// plane, the box corners farthest along and against the plane normal are
+  /// tested.
+  pub fn classify_box(&self, b: &BoundingBox3) -> Containment {
+    let mut result = Containment::INSIDE;
+    for plane in self.planes.iter() {
+      let n = plane.normal();
+      let mut far_corner = b.min_corner();
+      let mut near_corner = b.max_corner();
+      for i in 0..3 {
+        if n[i] >= 0.0 {
+          far_corner[i] = b.max_corner()[i];
+          near_corner[i] = b.min_corner()[i];
+        }
+      }
+      if plane.signed_distance(far_corner) < 0.0 {
+        return Containment::OUTSIDE;
+      }
+      if plane.signed_distance(near_corner) < 0.0 {
+        result = Containment::INTERSECTING;
+      }
+    }
+    result
+  }
+
+  /// Classifies a sphere, given by its center and radius, against the
+  /// frustum.
+  pub fn classify_sphere(&self, center: Vector3, radius: f64) -> Containment {
+    let mut result = Containment::INSIDE;
+    for plane in self.planes.iter() {
+      let d = plane.signed_distance(center);
+      if d < -radius {
+        return Containment::OUTSIDE;
+      }
+      if d < radius {
+        result = Containment::INTERSECTING;
+      }
+    }
+    result
+  }
+
+  /// Constructs a frustum from six planes, in the order of left, right,
+  /// bottom, top, near and far, all with normals pointing inwards.
+  pub fn new(planes: [Plane; 6]) -> Self {
+    Self {
+      planes: planes,
+    }
+  }
+
+  /// Extracts the frustum from a projection (or view-projection) matrix
+  /// mapping the frustum into the cube [-1, 1]^3 (OpenGL convention), e.g.
+  /// one created by Matrix4::perspective(). Planes are in the space before
+  /// the transform. For details:
+  ///
+  /// > Fast Extraction of Viewing Frustum Planes from the World-View-Projection
+  /// > Matrix. G. Gribb, K. Hartmann. 2001.
+  pub fn new_from_matrix(m: &Matrix4) -> Self {
+    let plane = |row: usize, sign: f64| {
+      let r = m[3];
+      let s = m[row];
+      Plane::new(
+          Vector3::new(r[0] + sign * s[0], r[1] + sign * s[1], r[2] + sign * s[2]),
+          -(r[3] + sign * s[3]))
+    };
+    Self::new([
+        plane(0, 1.0), plane(0, -1.0),
+        plane(1, 1.0), plane(1, -1.0),
+        plane(2, 1.0), plane(2, -1.0)])
+  }
+}
@@ -1,11 +1,34 @@
 pub use self::bounding_box3::BoundingBox3;
+pub use self::bounding_sphere::BoundingSphere;
+pub use self::frustum::{Frustum, Containment};
+pub use self::oriented_bounding_box3::OrientedBoundingBox3;
+pub use self::plane::Plane;
+pub use self::quadric::Quadric;
+pub use self::ray3::Ray3;
 pub use self::triangle3::Triangle3;
+pub use self::triangular_mesh::{
+    TriangularMesh, TriangularMeshBuilder, MeshBuildError, MeshEditError,
+    Vertex, Edge, Face, GeodesicSource, MeshBvh,
+    MeshPoint, VertexNormalMode, FaceNormalMode, NormalWeighting,
+    OutgoingEdges, VertexFaces, VertexNeighbors, FaceEdges, FaceNeighbors,
+    BoundaryLoops, ValidationReport, RepairReport, SimplificationOptions,
+    SectionPolyline, SimplificationReport, SmoothingOptions, SmoothingWeighting,
+    SubdivisionOptions, SurfaceSampler, TangentFrame, VertexCurvature,
+};
 pub use self::traits::*;
+pub use self::voxel_grid::VoxelGrid;
 
 mod bounding_box3;
+mod bounding_sphere;
+mod frustum;
+mod oriented_bounding_box3;
+mod plane;
+mod quadric;
+mod ray3;
 mod traits;
 mod triangle3;
 mod triangular_mesh;
+mod voxel_grid;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,218 @@
+use common::constants;
+use geometry::{
+    BoundingBox3, HasBoundingBox3, HasSurfaceArea, HasVolume, Ray3,
+};
+use math::{Matrix3, Vector3};
+use std::cmp::PartialEq;
+use std::fmt;
+
+/// Definitions and operations of a 3-d oriented bounding box (OBB), i.e. a
+/// box with arbitrary orientation, represented by its center, three
+/// orthonormal axes (stored as rows of a matrix, forming a right-handed
+/// frame) and the half extents of the box along each axis.
+#[derive(Debug, Copy, Clone)]
+pub struct OrientedBoundingBox3 {
+  center: Vector3,
+  axes: Matrix3,
+  half_extents: Vector3,
+}
+
+impl OrientedBoundingBox3 {
+  pub fn center(&self) -> Vector3 {
+    self.center
+  }
+
+  /// Returns the axes of the box as rows of a matrix. Multiplying this matrix
+  /// with a vector transforms the vector into the local frame of the box.
+  pub fn axes(&self) -> Matrix3 {
+    self.axes
+  }
+
+  /// Returns the unit axis at a given index.
+  pub fn axis(&self, ind: usize) -> Vector3 {
+    self.axes[ind]
+  }
+
+  pub fn half_extents(&self) -> Vector3 {
+    self.half_extents
+  }
+
+  /// Returns all 8 corners of the box.
+  pub fn corners(&self) -> [Vector3; 8] {
+    let mut corners = [Vector3::zero(); 8];
+    for (i, corner) in corners.iter_mut().enumerate() {
+      let mut p = self.center;
+      for k in 0..3 {
+        let sign = if (i >> k) & 1 == 0 { -1.0 } else { 1.0 };
+        p += self.axes[k] * (sign * self.half_extents[k]);
+      }
+      *corner = p;
+    }
+    corners
+  }
+
+  /// Transforms a point from world space into the local frame of the box,
+  /// whose origin is the box center.
+  pub fn to_local(&self, p: Vector3) -> Vector3 {
+    self.axes * (p - self.center)
+  }
+
+  /// Returns true if the point lies inside the box or on its boundary
+  /// (within EPSILON).
+  pub fn contains(&self, p: Vector3) -> bool {
+    let local = self.to_local(p);
+    (0..3).all(|i| local[i].abs() <= self.half_extents[i] + constants::EPSILON)
+  }
+
+  /// Intersects a ray with the box using the slab method in the local frame
+  /// of the box. Returns the range [t_enter, t_exit] of ray parameters inside
+  /// the box, where t_enter is 0 if the ray starts inside. Returns None if the
+  /// ray misses the box.
+  pub fn intersect_ray(&self, ray: &Ray3) -> Option<(f64, f64)> {
+    let origin = self.to_local(ray.origin());
+    let dir = self.axes * ray.dir();
+    let mut t_enter = 0.0f64;
+    let mut t_exit = constants::INFINITY;
+    for i in 0..3 {
+      let h = self.half_extents[i];
+      if dir[i].abs() < constants::EPSILON_TINY {
+        if origin[i].abs() > h {
+          return None;
+        }
+        continue;
+      }
+      let t1 = (-h - origin[i]) / dir[i];
+      let t2 = (h - origin[i]) / dir[i];
+      t_enter = t_enter.max(t1.min(t2));
+      t_exit = t_exit.min(t1.max(t2));
+      if t_enter > t_exit {
+        return None;
+      }
+    }
+    Some((t_enter, t_exit))
+  }
+
+  /// Tests whether two oriented boxes overlap, using the separating axis
+  /// theorem over the 15 candidate axes: the 3 axes of each box and the 9
+  /// cross products between them. Touching counts as overlapping.
+  pub fn overlaps(&self, other: &Self) -> bool {
+    let d = other.center - self.center;
+    let mut axes = Vec::with_capacity(15);
+    for i in 0..3 {
+      axes.push(self.axes[i]);
+      axes.push(other.axes[i]);
+      for j in 0..3 {
+        axes.push(self.axes[i] ^ other.axes[j]);
+      }
+    }
+    !axes.iter().any(|axis| {
+      // Cross products of (almost) parallel axes are degenerate, and the
+      // separation along them is covered by the face axes.
+      if axis.len2() < constants::EPSILON_TINY {
+        return false;
+      }
+      (d * *axis).abs() > self.projected_radius(*axis)
+          + other.projected_radius(*axis)
+    })
+  }
+
+  // Returns half of the length of the box projected onto an axis.
+  fn projected_radius(&self, axis: Vector3) -> f64 {
+    (0..3).map(|i| self.half_extents[i] * (self.axes[i] * axis).abs()).sum()
+  }
+
+  /// Constructs an oriented box from its center, axes (as rows of a matrix,
+  /// which must be orthonormal) and half extents.
+  pub fn new(center: Vector3, axes: Matrix3, half_extents: Vector3) -> Self {
+    assert!(axes * axes.t() == Matrix3::identity(),
+        "Axes of an oriented box must be orthonormal!");
+    assert!(half_extents.x() >= 0.0 && half_extents.y() >= 0.0
+        && half_extents.z() >= 0.0, "Half extents must not be negative!");
+    Self {
+      center: center,
+      axes: axes,
+      half_extents: half_extents,
+    }
+  }
+
+  /// Constructs an oriented box equivalent to an axis-aligned one.
+  pub fn new_from_box(b: &BoundingBox3) -> Self {
+    Self::new(b.center(), Matrix3::identity(), b.half_size())
+  }
+
+  /// Fits an oriented box to a set of points by principal component
+  /// analysis: the axes are the eigenvectors of the covariance matrix of the
+  /// points, sorted by descending variance, and the extents are the ranges of
+  /// the points projected onto each axis. The point list must not be empty.
+  ///
+  /// The result is usually tight for elongated point sets, but is not
+  /// guaranteed to be the minimal-volume box.
+  pub fn new_from_points(points: &[Vector3]) -> Self {
+    assert!(!points.is_empty(), "Cannot bound an empty point set!");
+    let n = points.len() as f64;
+    let mean = points.iter().fold(Vector3::zero(), |s, p| s + *p) / n;
+    let covariance = points.iter().fold(Matrix3::zero(),
+        |s, p| s + Matrix3::outer(*p - mean, *p - mean)) / n;
+    let (_, axes) = covariance.symmetric_eigen();
+
+    let mut min_corner = Vector3::identity(constants::INFINITY);
+    let mut max_corner = Vector3::identity(-constants::INFINITY);
+    for p in points.iter() {
+      let local = axes * (*p - mean);
+      for i in 0..3 {
+        min_corner[i] = min_corner[i].min(local[i]);
+        max_corner[i] = max_corner[i].max(local[i]);
+      }
+    }
+    let local_center = (min_corner + max_corner) / 2.0;
+    Self::new(
+        mean + axes.t() * local_center,
+        axes,
+        (max_corner - min_corner) / 2.0)
+  }
+}
+
+/// Overriding == to allow comparison within error bounds. Note that the same
+/// box can be represented by different axes (e.g. with negated or permuted
+/// axes), which are not considered equal.
+impl PartialEq for OrientedBoundingBox3 {
+  fn eq(&self, rhs: &Self) -> bool {
+    self.center == rhs.center && self.axes == rhs.axes
+        && self.half_extents == rhs.half_extents
+  }
+}
+
+impl HasSurfaceArea for OrientedBoundingBox3 {
+  fn surface_area(&self) -> f64 {
+    let e = self.half_extents * 2.0;
+    2.0 * (e.x() * e.y() + e.y() * e.z() + e.z() * e.x())
+  }
+}
+
+impl HasVolume for OrientedBoundingBox3 {
+  fn volume(&self) -> f64 {
+    8.0 * self.half_extents.x() * self.half_extents.y() * self.half_extents.z()
+  }
+}
+
+/// The enclosing axis-aligned box of the oriented one.
+impl HasBoundingBox3 for OrientedBoundingBox3 {
+  fn bounding_box3(&self) -> BoundingBox3 {
+    let mut extents = Vector3::zero();
+    for i in 0..3 {
+      extents[i] = (0..3)
+          .map(|k| self.half_extents[k] * self.axes[k][i].abs())
+          .sum();
+    }
+    BoundingBox3::new(self.center - extents, self.center + extents)
+  }
+}
+
+/// Printing the box as:
+/// OrientedBoundingBox3(center, axes, half_extents)
+impl fmt::Display for OrientedBoundingBox3 {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "OrientedBoundingBox3({}, {}, {})",
+        &self.center, &self.axes, &self.half_extents)
+  }
+}
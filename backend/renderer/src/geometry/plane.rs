@@ -0,0 +1,118 @@
+use common::constants;
+use geometry::{Ray3, Triangle3};
+use math::Vector3;
+use std::cmp::PartialEq;
+use std::fmt;
+
+/// Definitions and operations of a plane in 3D space, represented by its unit
+/// normal n and offset d, such that a point p lies on the plane iff
+///
+/// > n * p = d.
+///
+/// The plane splits the space into two half-spaces: the positive one (where n
+/// points to, n * p > d) and the negative one.
+#[derive(Debug, Copy, Clone)]
+pub struct Plane {
+  normal: Vector3,
+  offset: f64,
+}
+
+impl Plane {
+  /// Returns the unit normal vector of the plane.
+  pub fn normal(&self) -> Vector3 {
+    self.normal
+  }
+
+  /// Returns the signed distance from the origin to the plane, along its
+  /// normal.
+  pub fn offset(&self) -> f64 {
+    self.offset
+  }
+
+  /// Returns the signed distance from a point to the plane, which is positive
+  /// if the point lies in the positive half-space.
+  pub fn signed_distance(&self, p: Vector3) -> f64 {
+    self.normal * p - self.offset
+  }
+
+  /// Returns true if the point lies in the positive half-space or on the
+  /// plane (within EPSILON).
+  pub fn contains_in_half_space(&self, p: Vector3) -> bool {
+    self.signed_distance(p) >= -constants::EPSILON
+  }
+
+  /// Returns the orthogonal projection of a point onto the plane.
+  pub fn project(&self, p: Vector3) -> Vector3 {
+    p - self.normal * self.signed_distance(p)
+  }
+
+  /// Returns the same plane with its normal negated, i.e. with the two
+  /// half-spaces swapped.
+  pub fn flip(&self) -> Self {
+    Self {
+      normal: -self.normal,
+      offset: -self.offset,
+    }
+  }
+
+  /// Intersects a ray with the plane, returning the ray parameter t of the
+  /// intersection point. Returns None if the ray is parallel to the plane or
+  /// the plane is behind the ray origin.
+  pub fn intersect_ray(&self, ray: &Ray3) -> Option<f64> {
+    let denom = self.normal * ray.dir();
+    if denom.abs() < constants::EPSILON_TINY {
+      return None;
+    }
+    let t = -self.signed_distance(ray.origin()) / denom;
+    if t < 0.0 {
+      None
+    } else {
+      Some(t)
+    }
+  }
+
+  /// Constructs a plane from a normal (not necessarily unit) and an offset.
+  /// The representation is normalized, i.e. both normal and offset are
+  /// divided by the length of the given normal.
+  pub fn new(normal: Vector3, offset: f64) -> Self {
+    let len = normal.len();
+    assert!(len > constants::EPSILON_TINY, "Plane normal must not be zero!");
+    Self {
+      normal: normal / len,
+      offset: offset / len,
+    }
+  }
+
+  /// Constructs a plane with a given normal passing through a given point.
+  pub fn new_from_point(normal: Vector3, p: Vector3) -> Self {
+    Self::new(normal, normal * p)
+  }
+
+  /// Constructs a plane passing through three points. The normal follows the
+  /// counter-clockwise order of the points, see Triangle3::normal(). Panics if
+  /// the points are collinear.
+  pub fn new_from_points(p0: Vector3, p1: Vector3, p2: Vector3) -> Self {
+    Self::new_from_triangle(&Triangle3::new(p0, p1, p2))
+  }
+
+  /// Constructs the supporting plane of a (non-degenerate) triangle.
+  pub fn new_from_triangle(t: &Triangle3) -> Self {
+    Self::new_from_point(t.normal(), t.v(0))
+  }
+}
+
+/// Overriding == to allow comparison within error bounds.
+impl PartialEq for Plane {
+  fn eq(&self, rhs: &Self) -> bool {
+    self.normal == rhs.normal
+        && (self.offset - rhs.offset).abs() < constants::EPSILON
+  }
+}
+
+/// Printing the plane as:
+/// Plane(n * p = d)
+impl fmt::Display for Plane {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "Plane({} * p = {})", &self.normal, &self.offset)
+  }
+}
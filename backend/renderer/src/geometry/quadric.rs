@@ -0,0 +1,106 @@
+use common::constants;
+use geometry::Plane;
+use math::{Matrix3, Matrix4, Vector3};
+use std::ops::{Add, AddAssign, Mul};
+
+/// Definitions and operations of an error quadric, i.e. a quadratic function
+/// of a point p, represented by a symmetric 4x4 matrix Q such that
+///
+/// > Q(p) = v^T * Q * v, where v = (p, 1).
+///
+/// The quadric of a plane evaluates to the squared distance from the plane,
+/// and sums of such quadrics to the sum of squared distances from a set of
+/// planes. For details please read:
+///
+/// > Surface Simplification Using Quadric Error Metrics.
+/// > M. Garland, P. S. Heckbert. SIGGRAPH 1997.
+#[derive(Debug, Copy, Clone)]
+pub struct Quadric {
+  q: Matrix4,
+}
+
+impl Quadric {
+  /// Returns the matrix representation of the quadric.
+  pub fn matrix(&self) -> Matrix4 {
+    self.q
+  }
+
+  /// Evaluates the quadric at a point.
+  pub fn error(&self, p: Vector3) -> f64 {
+    let v = [p.x(), p.y(), p.z(), 1.0];
+    let qv = self.q.mul_homogeneous(&v);
+    v[0] * qv[0] + v[1] * qv[1] + v[2] * qv[2] + v[3] * qv[3]
+  }
+
+  /// Returns the point minimizing the quadric, by solving
+  ///
+  /// > A * p = -b,
+  ///
+  /// where A is the upper-left 3x3 block of Q and b the upper 3 elements of
+  /// its last column. Returns None if A is (nearly) singular, e.g. when all
+  /// planes are (nearly) parallel, in which case the minimum is not unique.
+  pub fn minimizer(&self) -> Option<Vector3> {
+    let a = Matrix3::new(
+        self.q[0][0], self.q[0][1], self.q[0][2],
+        self.q[1][0], self.q[1][1], self.q[1][2],
+        self.q[2][0], self.q[2][1], self.q[2][2]);
+    // Determinants are relative to the scale of the quadric, so that the
+    // test does not depend on the number and weights of the planes.
+    let scale = a.trace();
+    if scale <= 0.0 || a.det().abs() <= constants::EPSILON * scale.powi(3) {
+      return None;
+    }
+    let inv = a.inverse()?;
+    Some(-(inv * Vector3::new(self.q[0][3], self.q[1][3], self.q[2][3])))
+  }
+
+  /// Creates the quadric of a plane, evaluating to the squared distance from
+  /// the plane, multiplied by a weight.
+  pub fn new_from_plane(plane: &Plane, weight: f64) -> Self {
+    let n = plane.normal();
+    let v = [n.x(), n.y(), n.z(), -plane.offset()];
+    let mut q = Matrix4::zero();
+    for i in 0..4 {
+      for j in 0..4 {
+        q[i][j] = v[i] * v[j] * weight;
+      }
+    }
+    Self {
+      q: q,
+    }
+  }
+
+  /// Creates the zero quadric.
+  pub fn zero() -> Self {
+    Self {
+      q: Matrix4::zero(),
+    }
+  }
+}
+
+/// Overriding += for the sum of quadrics.
+impl AddAssign for Quadric {
+  fn add_assign(&mut self, rhs: Self) {
+    self.q += rhs.q;
+  }
+}
+
+/// Overriding + for the sum of quadrics.
+impl Add for Quadric {
+  type Output = Self;
+  fn add(self, rhs: Self) -> Self {
+    let mut q = self;
+    q += rhs;
+    q
+  }
+}
+
+/// Overriding * for scaling a quadric.
+impl Mul<f64> for Quadric {
+  type Output = Self;
+  fn mul(self, rhs: f64) -> Self {
+    Self {
+      q: self.q * rhs,
+    }
+  }
+}
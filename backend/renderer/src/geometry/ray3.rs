@@ -0,0 +1,45 @@
+use common::constants;
+use math::Vector3;
+use std::fmt;
+
+/// Definitions of a ray in 3D space, starting from its origin and going
+/// infinitely along a direction. The direction is normalized upon creation,
+/// so that the ray parameter t measures the distance from the origin.
+#[derive(Debug, Copy, Clone)]
+pub struct Ray3 {
+  origin: Vector3,
+  dir: Vector3,
+}
+
+impl Ray3 {
+  pub fn origin(&self) -> Vector3 {
+    self.origin
+  }
+
+  /// Returns the (unit) direction of the ray.
+  pub fn dir(&self) -> Vector3 {
+    self.dir
+  }
+
+  /// Returns the point on the ray at a given distance t from its origin.
+  pub fn at(&self, t: f64) -> Vector3 {
+    self.origin + self.dir * t
+  }
+
+  pub fn new(origin: Vector3, dir: Vector3) -> Self {
+    assert!(dir.len2() > constants::EPSILON_TINY,
+        "Ray direction must not be zero!");
+    Self {
+      origin: origin,
+      dir: dir.normalize(),
+    }
+  }
+}
+
+/// Printing the ray as:
+/// Ray3(origin -> dir)
+impl fmt::Display for Ray3 {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "Ray3({} -> {})", &self.origin, &self.dir)
+  }
+}
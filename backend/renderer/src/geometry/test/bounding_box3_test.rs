@@ -32,3 +32,17 @@ fn test_basic_properties() {
       Vector3::new(20.0, 31.0, 52.0));
   assert_eq!(box_shift, box_expected);
 }
+
+#[test]
+fn test_points_and_center() {
+  let b = BoundingBox3::new_from_points(&[
+      Vector3::new(1.0, -2.0, 3.0),
+      Vector3::new(-1.0, 4.0, 0.0),
+      Vector3::new(0.0, 0.0, 5.0)]);
+  assert_eq!(b, BoundingBox3::new_from_nums(-1.0, -2.0, 0.0, 1.0, 4.0, 5.0));
+  assert_eq!(b.max_y(), 4.0);
+  assert_eq!(b.center(), Vector3::new(0.0, 1.0, 2.5));
+  assert_eq!(b.half_size(), Vector3::new(1.0, 3.0, 2.5));
+  assert!(b.contains(Vector3::new(1.0, 4.0, 5.0)));
+  assert!(!b.contains(Vector3::new(0.0, 4.5, 1.0)));
+}
@@ -0,0 +1,95 @@
+use super::super::{
+    BoundingBox3, BoundingSphere, HasBoundingBox3, HasBoundingSphere,
+    HasVolume, Triangle3,
+};
+use common::random::Random;
+use math::Vector3;
+
+#[test]
+fn test_basic_properties() {
+  let mut s = BoundingSphere::new(Vector3::new(1.0, 2.0, 3.0), 2.0);
+  assert!(s.contains(Vector3::new(1.0, 2.0, 5.0)));
+  assert!(!s.contains(Vector3::new(2.0, 3.0, 4.5)));
+  assert_eq!(s.bounding_box3(),
+      BoundingBox3::new_from_nums(-1.0, 0.0, 1.0, 3.0, 4.0, 5.0));
+  assert!((s.volume() - 32.0 / 3.0 * ::std::f64::consts::PI).abs() < 1e-9);
+
+  s.expand_self(Vector3::new(1.0, 2.0, 4.0));
+  assert_eq!(s, BoundingSphere::new(Vector3::new(1.0, 2.0, 3.0), 2.0));
+  s.expand_self(Vector3::new(1.0, 2.0, 7.0));
+  assert_eq!(s, BoundingSphere::new(Vector3::new(1.0, 2.0, 4.0), 3.0));
+
+  let b = BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 2.0, 2.0, 2.0);
+  assert_eq!(b.bounding_sphere(),
+      BoundingSphere::new(Vector3::one(), 3.0f64.sqrt()));
+}
+
+#[test]
+fn test_triangles() {
+  // Acute triangle: the circumsphere.
+  let acute = Triangle3::new(
+      Vector3::x_unit(), Vector3::y_unit(), Vector3::z_unit());
+  let s = acute.bounding_sphere();
+  assert_eq!(s.center(), Vector3::identity(1.0 / 3.0));
+  assert!((s.radius() - (2.0f64 / 3.0).sqrt()).abs() < 1e-9);
+
+  // Obtuse triangle: the longest edge is the diameter.
+  let obtuse = Triangle3::new(
+      Vector3::zero(), Vector3::new(4.0, 0.0, 0.0), Vector3::new(2.0, 1.0, 0.0));
+  assert_eq!(obtuse.bounding_sphere(),
+      BoundingSphere::new(Vector3::new(2.0, 0.0, 0.0), 2.0));
+
+  let s = [acute, obtuse].bounding_sphere();
+  for t in [acute, obtuse].iter() {
+    for i in 0..3 {
+      assert!(s.contains(t.v(i)));
+    }
+  }
+  // (0, 0, 1) is outside of the bounding sphere of the obtuse triangle.
+  assert!(s.radius() > 2.0);
+}
+
+#[test]
+fn test_point_sets() {
+  // Corners of a cube, plus some points inside.
+  let mut points = Vec::new();
+  for i in 0..8 {
+    points.push(Vector3::new(
+        (i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64));
+  }
+  let mut random = Random::new(42);
+  for _ in 0..100 {
+    points.push(Vector3::new(
+        random.next_f64(), random.next_f64(), random.next_f64()));
+  }
+  let exact = BoundingSphere::new(Vector3::identity(0.5), 0.75f64.sqrt());
+  assert_eq!(BoundingSphere::new_welzl(&points), exact);
+  assert_eq!(points.bounding_sphere(), exact);
+
+  let approx = BoundingSphere::new_ritter(&points);
+  assert!(points.iter().all(|p| approx.contains(*p)));
+  assert!(approx.radius() >= exact.radius() - 1e-9);
+  assert!(approx.radius() <= exact.radius() * 1.2);
+
+  // Degenerate inputs: a single point, and points on a line.
+  let single = [Vector3::one(), Vector3::one()];
+  assert_eq!(single.bounding_sphere(), BoundingSphere::new(Vector3::one(), 0.0));
+  let line: Vec<Vector3> =
+      (0..10).map(|i| Vector3::x_unit() * i as f64).collect();
+  assert_eq!(line.bounding_sphere(),
+      BoundingSphere::new(Vector3::new(4.5, 0.0, 0.0), 4.5));
+  assert_eq!(BoundingSphere::new_ritter(&line),
+      BoundingSphere::new(Vector3::new(4.5, 0.0, 0.0), 4.5));
+
+  // Random points on a sphere, where all points are tight.
+  let center = Vector3::new(1.0, -2.0, 3.0);
+  let sphere_points: Vec<Vector3> = (0..200).map(|_| {
+    let v = Vector3::new(random.next_f64() - 0.5, random.next_f64() - 0.5,
+        random.next_f64() - 0.5);
+    center + v.normalize() * 2.0
+  }).collect();
+  let s = sphere_points.bounding_sphere();
+  assert!(sphere_points.iter().all(|p| s.contains(*p)));
+  assert!(s.radius() <= 2.0 + 1e-6);
+  assert!((s.center() - center).len() < 0.05);
+}
@@ -0,0 +1,79 @@
+use super::super::{BoundingBox3, Containment, Frustum, Plane};
+use math::{Matrix4, Vector3};
+
+struct TestBed {
+  // A camera at the origin looking towards -z, with 90 degrees field of
+  // view, near plane at z = -1 and far plane at z = -100.
+  frustum: Frustum,
+}
+
+fn create_test_bed() -> TestBed {
+  let m = Matrix4::perspective(::std::f64::consts::PI / 2.0, 1.0, 1.0, 100.0);
+  TestBed {
+    frustum: Frustum::new_from_matrix(&m),
+  }
+}
+
+fn dispose_test_bed(_tb: &mut TestBed) {}
+
+#[test]
+fn test_planes_from_matrix() {
+  let mut tb = create_test_bed();
+
+  let h = 0.5f64.sqrt();
+  assert_eq!(tb.frustum.plane(Frustum::LEFT),
+      Plane::new(Vector3::new(h, 0.0, -h), 0.0));
+  assert_eq!(tb.frustum.plane(Frustum::TOP),
+      Plane::new(Vector3::new(0.0, -h, -h), 0.0));
+  assert_eq!(tb.frustum.plane(Frustum::NEAR),
+      Plane::new(-Vector3::z_unit(), 1.0));
+  assert_eq!(tb.frustum.plane(Frustum::FAR),
+      Plane::new(Vector3::z_unit(), -100.0));
+
+  assert!(tb.frustum.contains_point(Vector3::new(0.0, 0.0, -10.0)));
+  assert!(tb.frustum.contains_point(Vector3::new(9.0, -9.0, -10.0)));
+  assert!(!tb.frustum.contains_point(Vector3::new(11.0, 0.0, -10.0)));
+  assert!(!tb.frustum.contains_point(Vector3::new(0.0, 0.0, 10.0)));
+  assert!(!tb.frustum.contains_point(Vector3::new(0.0, 0.0, -0.5)));
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_classification() {
+  let mut tb = create_test_bed();
+
+  let f = &tb.frustum;
+  assert_eq!(f.classify_box(
+      &BoundingBox3::new_from_nums(-1.0, -1.0, -11.0, 1.0, 1.0, -9.0)),
+      Containment::INSIDE);
+  assert_eq!(f.classify_box(
+      &BoundingBox3::new_from_nums(-1.0, -1.0, -2.0, 1.0, 1.0, 0.0)),
+      Containment::INTERSECTING);
+  assert_eq!(f.classify_box(
+      &BoundingBox3::new_from_nums(-1.0, -1.0, 1.0, 1.0, 1.0, 3.0)),
+      Containment::OUTSIDE);
+  assert_eq!(f.classify_box(
+      &BoundingBox3::new_from_nums(20.0, -1.0, -11.0, 22.0, 1.0, -9.0)),
+      Containment::OUTSIDE);
+
+  assert_eq!(f.classify_sphere(Vector3::new(0.0, 0.0, -50.0), 10.0),
+      Containment::INSIDE);
+  assert_eq!(f.classify_sphere(Vector3::new(0.0, 0.0, -100.0), 10.0),
+      Containment::INTERSECTING);
+  assert_eq!(f.classify_sphere(Vector3::new(0.0, 50.0, -10.0), 10.0),
+      Containment::OUTSIDE);
+
+  // A frustum built from explicit planes: the unit cube.
+  let cube = Frustum::new([
+      Plane::new(Vector3::x_unit(), 0.0), Plane::new(-Vector3::x_unit(), -1.0),
+      Plane::new(Vector3::y_unit(), 0.0), Plane::new(-Vector3::y_unit(), -1.0),
+      Plane::new(Vector3::z_unit(), 0.0), Plane::new(-Vector3::z_unit(), -1.0)]);
+  assert!(cube.contains_point(Vector3::identity(0.5)));
+  assert_eq!(cube.classify_sphere(Vector3::identity(0.5), 0.2),
+      Containment::INSIDE);
+  assert_eq!(cube.classify_sphere(Vector3::identity(0.5), 0.7),
+      Containment::INTERSECTING);
+
+  dispose_test_bed(&mut tb);
+}
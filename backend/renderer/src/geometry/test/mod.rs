@@ -1,2 +1,8 @@
 mod bounding_box3_test;
 mod triangle3_test;
+mod plane_test;
+mod frustum_test;
+mod oriented_bounding_box3_test;
+mod bounding_sphere_test;
+mod quadric_test;
+mod voxel_grid_test;
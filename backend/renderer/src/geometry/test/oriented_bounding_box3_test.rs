@@ -0,0 +1,142 @@
+use super::super::{
+    BoundingBox3, HasBoundingBox3, HasSurfaceArea, HasVolume,
+    OrientedBoundingBox3, Ray3,
+};
+use math::{Matrix3, Vector3};
+
+struct TestBed {
+  // A box of size 10 x 2 x 1 centered at (1, 2, 3), rotated by 45 degrees
+  // around the z axis.
+  obb: OrientedBoundingBox3,
+  axes: Matrix3,
+}
+
+fn create_test_bed() -> TestBed {
+  let h = 0.5f64.sqrt();
+  let axes = Matrix3::new(
+      h, h, 0.0,
+      -h, h, 0.0,
+      0.0, 0.0, 1.0);
+  TestBed {
+    obb: OrientedBoundingBox3::new(
+        Vector3::new(1.0, 2.0, 3.0), axes, Vector3::new(5.0, 1.0, 0.5)),
+    axes: axes,
+  }
+}
+
+fn dispose_test_bed(_tb: &mut TestBed) {}
+
+#[test]
+fn test_basic_properties() {
+  let mut tb = create_test_bed();
+
+  assert_eq!(tb.obb.volume(), 20.0);
+  assert_eq!(tb.obb.surface_area(), 2.0 * (20.0 + 2.0 + 10.0));
+  assert!(tb.obb.contains(Vector3::new(1.0, 2.0, 3.0) + tb.axes[0] * 4.9));
+  assert!(!tb.obb.contains(Vector3::new(1.0, 2.0, 3.0) + tb.axes[1] * 1.1));
+  for c in tb.obb.corners().iter() {
+    assert!(tb.obb.contains(*c));
+  }
+
+  // Half extent of the enclosing box along x and y: (5 + 1) / sqrt(2).
+  let e = 6.0 * 0.5f64.sqrt();
+  assert_eq!(tb.obb.bounding_box3(), BoundingBox3::new_from_nums(
+      1.0 - e, 2.0 - e, 2.5, 1.0 + e, 2.0 + e, 3.5));
+
+  let aabb = BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 1.0, 2.0, 3.0);
+  let from_aabb = OrientedBoundingBox3::new_from_box(&aabb);
+  assert_eq!(from_aabb.bounding_box3(), aabb);
+  assert_eq!(from_aabb.volume(), aabb.volume());
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_fit_by_pca() {
+  let mut tb = create_test_bed();
+
+  // Samples on a regular grid inside the box.
+  let mut points = Vec::new();
+  for i in 0..11 {
+    for j in 0..3 {
+      for k in 0..3 {
+        let local = Vector3::new(
+            i as f64 - 5.0, j as f64 - 1.0, (k as f64 - 1.0) / 2.0);
+        points.push(tb.obb.center() + tb.axes.t() * local);
+      }
+    }
+  }
+  let fitted = OrientedBoundingBox3::new_from_points(&points);
+  assert_eq!(fitted.center(), tb.obb.center());
+  assert_eq!(fitted.half_extents(), tb.obb.half_extents());
+  assert!((fitted.volume() - tb.obb.volume()).abs() < 1e-6);
+  for i in 0..3 {
+    assert!(((fitted.axis(i) * tb.axes[i]).abs() - 1.0).abs() < 1e-9);
+  }
+  // The fitted box is much tighter than the axis-aligned one.
+  assert!(fitted.volume() * 3.0 < BoundingBox3::new_from_points(&points).volume());
+
+  // A single point gives an empty box.
+  let p = OrientedBoundingBox3::new_from_points(&[Vector3::one()]);
+  assert_eq!(p.center(), Vector3::one());
+  assert_eq!(p.volume(), 0.0);
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_ray_intersection() {
+  let mut tb = create_test_bed();
+
+  // Along the long axis, from outside.
+  let c = tb.obb.center();
+  let ray = Ray3::new(c - tb.axes[0] * 10.0, tb.axes[0]);
+  let (t0, t1) = tb.obb.intersect_ray(&ray).unwrap();
+  assert!((t0 - 5.0).abs() < 1e-9 && (t1 - 15.0).abs() < 1e-9);
+
+  // From inside.
+  let (t0, t1) = tb.obb.intersect_ray(&Ray3::new(c, tb.axes[1])).unwrap();
+  assert!(t0 == 0.0 && (t1 - 1.0).abs() < 1e-9);
+
+  // Missing the box, and pointing away from it.
+  assert!(tb.obb.intersect_ray(
+      &Ray3::new(c + tb.axes[1] * 2.0, tb.axes[0])).is_none());
+  assert!(tb.obb.intersect_ray(
+      &Ray3::new(c - tb.axes[0] * 10.0, -tb.axes[0])).is_none());
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_overlap() {
+  let mut tb = create_test_bed();
+
+  let c = tb.obb.center();
+  let unit = |center: Vector3| OrientedBoundingBox3::new(
+      center, Matrix3::identity(), Vector3::identity(0.5));
+  assert!(tb.obb.overlaps(&tb.obb));
+  assert!(tb.obb.overlaps(&unit(c + tb.axes[0] * 5.0)));
+  assert!(unit(c + tb.axes[0] * 5.0).overlaps(&tb.obb));
+  // Inside the enclosing axis-aligned box but outside the oriented one,
+  // separated only by an axis of the oriented box.
+  assert!(!tb.obb.overlaps(&unit(c + Vector3::new(3.0, -3.0, 0.0))));
+  assert!(!tb.obb.overlaps(&unit(c + Vector3::new(0.0, 0.0, 1.1))));
+
+  // Two boxes crossing each other in an X shape.
+  let h = 0.5f64.sqrt();
+  let rot = Matrix3::new(0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+  let other = OrientedBoundingBox3::new(c, rot * tb.axes,
+      Vector3::new(5.0, 1.0, 0.5));
+  assert!(tb.obb.overlaps(&other));
+  // A box rotated by 45 degrees around the x axis, whose lowest edge lies
+  // just above the top face of the tested box.
+  let tilted_axes = Matrix3::new(
+      0.0, h, h,
+      0.0, -h, h,
+      1.0, 0.0, 0.0);
+  let tilted = OrientedBoundingBox3::new(
+      c + Vector3::new(0.0, 0.0, 2.0), tilted_axes, Vector3::new(1.0, 1.0, 5.0));
+  assert!(!tb.obb.overlaps(&tilted));
+
+  dispose_test_bed(&mut tb);
+}
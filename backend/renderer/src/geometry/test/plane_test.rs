@@ -0,0 +1,57 @@
+use super::super::{Plane, Ray3, Triangle3};
+use math::Vector3;
+
+#[test]
+fn test_init_works() {
+  let p1 = Plane::new(Vector3::new(0.0, 0.0, 2.0), 4.0);
+  let p2 = Plane::new_from_point(Vector3::z_unit(), Vector3::new(5.0, -1.0, 2.0));
+  let p3 = Plane::new_from_points(
+      Vector3::new(0.0, 0.0, 2.0),
+      Vector3::new(1.0, 0.0, 2.0),
+      Vector3::new(0.0, 1.0, 2.0));
+  let p4 = Plane::new_from_triangle(&Triangle3::new(
+      Vector3::new(0.0, 0.0, 2.0),
+      Vector3::new(0.0, 1.0, 2.0),
+      Vector3::new(1.0, 0.0, 2.0)));
+  assert_eq!(p1.normal(), Vector3::z_unit());
+  assert_eq!(p1.offset(), 2.0);
+  assert_eq!(p1, p2);
+  assert_eq!(p1, p3);
+  // Clockwise points give the opposite normal.
+  assert_eq!(p1.flip(), p4);
+  assert!(p1 != p4);
+}
+
+#[test]
+fn test_distance_and_projection() {
+  // The plane x + y = 1.
+  let p = Plane::new(Vector3::new(1.0, 1.0, 0.0), 1.0);
+  let h = 0.5f64.sqrt();
+  assert!(p.signed_distance(Vector3::new(1.0, 0.0, 0.0)).abs() < 1e-9);
+  assert!((p.signed_distance(Vector3::zero()) + h).abs() < 1e-9);
+  assert!((p.signed_distance(Vector3::new(2.0, 2.0, 7.0)) - 3.0 * h).abs()
+      < 1e-9);
+  assert!(p.contains_in_half_space(Vector3::new(1.0, 1.0, 0.0)));
+  assert!(p.contains_in_half_space(Vector3::new(0.5, 0.5, 0.0)));
+  assert!(!p.contains_in_half_space(Vector3::new(0.0, 0.5, 0.0)));
+  assert_eq!(p.project(Vector3::new(0.0, 0.0, 3.0)),
+      Vector3::new(0.5, 0.5, 3.0));
+}
+
+#[test]
+fn test_ray_intersection() {
+  let p = Plane::new(Vector3::z_unit(), 1.0);
+  let r1 = Ray3::new(Vector3::new(1.0, 2.0, 0.0), Vector3::new(0.0, 0.0, 2.0));
+  assert_eq!(r1.dir(), Vector3::z_unit());
+  assert_eq!(p.intersect_ray(&r1), Some(1.0));
+
+  let r2 = Ray3::new(Vector3::zero(), Vector3::new(1.0, 0.0, 1.0));
+  let t = p.intersect_ray(&r2).unwrap();
+  assert_eq!(r2.at(t), Vector3::new(1.0, 0.0, 1.0));
+
+  // Parallel ray, and ray pointing away from the plane.
+  assert_eq!(p.intersect_ray(&Ray3::new(Vector3::zero(), Vector3::x_unit())),
+      None);
+  assert_eq!(p.intersect_ray(&Ray3::new(Vector3::zero(), -Vector3::z_unit())),
+      None);
+}
@@ -0,0 +1,36 @@
+use super::super::{Plane, Quadric};
+use math::Vector3;
+
+#[test]
+fn test_plane_quadric() {
+  let plane = Plane::new(Vector3::new(1.0, 1.0, 0.0), 2.0f64.sqrt());
+  let q = Quadric::new_from_plane(&plane, 1.0);
+  for p in [Vector3::zero(), Vector3::new(3.0, -1.0, 7.0),
+      Vector3::new(1.0, 0.0, 0.0)].iter() {
+    let d = plane.signed_distance(*p);
+    assert!((q.error(*p) - d * d).abs() < 1e-9);
+  }
+  assert!((Quadric::new_from_plane(&plane, 3.0).error(Vector3::zero()) - 3.0)
+      .abs() < 1e-9);
+  assert_eq!(q.matrix(), q.matrix().t());
+  // Points on a plane all minimize its quadric.
+  assert!(q.minimizer().is_none());
+}
+
+#[test]
+fn test_minimizer() {
+  let corner = Vector3::new(1.0, 2.0, 3.0);
+  let mut q = Quadric::zero();
+  for n in [Vector3::x_unit(), Vector3::y_unit(), Vector3::z_unit()].iter() {
+    q += Quadric::new_from_plane(&Plane::new_from_point(*n, corner), 1.0);
+  }
+  assert_eq!(q.minimizer(), Some(corner));
+  assert!(q.error(corner).abs() < 1e-9);
+  assert!((q.error(Vector3::zero()) - corner.len2()).abs() < 1e-9);
+
+  // Two planes meeting along a line do not have a unique minimizer.
+  let q = Quadric::new_from_plane(&Plane::new(Vector3::x_unit(), 1.0), 1.0)
+      + Quadric::new_from_plane(&Plane::new(Vector3::y_unit(), 1.0), 1.0) * 2.0;
+  assert!(q.minimizer().is_none());
+  assert!((q.error(Vector3::new(0.0, 0.0, 5.0)) - 3.0).abs() < 1e-9);
+}
@@ -2,6 +2,8 @@ use super::super::{
     Triangle3, HasBoundingBox3, BoundingBox3, HasSurfaceArea
 };
 use math::Vector3;
+use common::{constants, utils};
+use std::f64::consts::PI;
 
 struct TestBed {
   tri1: Triangle3,
@@ -70,3 +72,217 @@ fn test_properties() {
 
   dispose_test_bed(&mut tb);
 }
+
+#[test]
+fn test_box_overlap() {
+  let mut tb = create_test_bed();
+
+  let unit_box = BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+  assert!(tb.tri1.overlaps_box(&unit_box));
+  assert!(tb.tri2.overlaps_box(&unit_box));
+  // The box touches tri2 at its vertex (1, 0, 0).
+  assert!(tb.tri2.overlaps_box(
+      &BoundingBox3::new_from_nums(1.0, -1.0, -1.0, 2.0, 0.0, 0.0)));
+  // Separated by the plane of tri2 (x + y + z = 1).
+  assert!(!tb.tri2.overlaps_box(
+      &BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 0.3, 0.3, 0.3)));
+  // Separated along the z axis.
+  assert!(!tb.tri1.overlaps_box(
+      &BoundingBox3::new_from_nums(0.0, 0.0, 0.5, 1.0, 1.0, 1.0)));
+  // Separated by the edge v1 -> v2 of tri1 only (box lies beyond the
+  // hypotenuse but within the bounding box of the triangle).
+  assert!(!tb.tri1.overlaps_box(
+      &BoundingBox3::new_from_nums(0.8, 1.2, -1.0, 1.0, 2.0, 1.0)));
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_clip_to_box() {
+  let mut tb = create_test_bed();
+
+  // A box containing the whole triangle clips nothing.
+  let big_box = BoundingBox3::new_from_nums(-1.0, -1.0, -1.0, 3.0, 3.0, 3.0);
+  assert_eq!(tb.tri1.clip_to_box(&big_box), Some(tb.tri1.bounding_box3()));
+
+  // Clipping tri1 with y <= 1 cuts the hypotenuse at (0.5, 1, 0).
+  let lower_box = BoundingBox3::new_from_nums(-1.0, -1.0, -1.0, 3.0, 1.0, 3.0);
+  assert_eq!(tb.tri1.clip_to_box(&lower_box),
+      Some(BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 1.0, 1.0, 0.0)));
+
+  // Clipping tri2 with x <= 0.5 and y <= 0.5 leaves the polygon
+  // (0, 0, 1), (0.5, 0, 0.5), (0.5, 0.5, 0), (0, 0.5, 0.5).
+  let corner_box = BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 0.5, 0.5, 1.0);
+  assert_eq!(tb.tri2.clip_to_box(&corner_box),
+      Some(BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 0.5, 0.5, 1.0)));
+
+  let far_box = BoundingBox3::new_from_nums(2.0, 2.0, 2.0, 3.0, 3.0, 3.0);
+  assert_eq!(tb.tri2.clip_to_box(&far_box), None);
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_quality_metrics() {
+  let mut tb = create_test_bed();
+
+  // tri2 is equilateral with edge length sqrt(2).
+  let pi = ::std::f64::consts::PI;
+  assert!(utils::equal_with_bound(tb.tri2.min_angle(), pi / 3.0));
+  assert!(utils::equal_with_bound(tb.tri2.max_angle(), pi / 3.0));
+  assert!(utils::equal_with_bound(tb.tri2.aspect_ratio(), 1.0));
+  assert!(utils::equal_with_bound(tb.tri2.radius_ratio(), 2.0));
+  assert!(utils::equal_with_bound(tb.tri2.min_edge_len(), 2.0f64.sqrt()));
+  assert_eq!(tb.tri2.centroid(), Vector3::identity(1.0 / 3.0));
+  assert_eq!(tb.tri2.incenter(), Vector3::identity(1.0 / 3.0));
+  assert_eq!(tb.tri2.circumcenter(), Some(Vector3::identity(1.0 / 3.0)));
+
+  // tri1 is a right triangle with legs 1 and 2, hypotenuse sqrt(5).
+  let sqrt5 = 5.0f64.sqrt();
+  assert!(utils::equal_with_bound(tb.tri1.angle(0), pi / 2.0));
+  assert!(utils::equal_with_bound(tb.tri1.max_angle(), pi / 2.0));
+  assert!(utils::equal_with_bound(tb.tri1.min_angle(), 0.5f64.atan()));
+  assert!(utils::equal_with_bound(tb.tri1.max_edge_len(), sqrt5));
+  assert!(utils::equal_with_bound(tb.tri1.perimeter(), 3.0 + sqrt5));
+  assert!(utils::equal_with_bound(tb.tri1.inradius(), (3.0 - sqrt5) / 2.0));
+  assert!(utils::equal_with_bound(tb.tri1.circumradius(), sqrt5 / 2.0));
+  // The circumcenter of a right triangle is the midpoint of its hypotenuse.
+  assert_eq!(tb.tri1.circumcenter(), Some(Vector3::new(0.5, 1.0, 0.0)));
+  let r = tb.tri1.inradius();
+  assert_eq!(tb.tri1.incenter(), Vector3::new(r, r, 0.0));
+  assert!(tb.tri1.aspect_ratio() > 1.0);
+  assert!(tb.tri1.radius_ratio() > 2.0);
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_degenerate_triangles() {
+  let mut tb = create_test_bed();
+
+  assert!(!tb.tri1.is_degenerate());
+  assert!(!tb.tri2.is_degenerate());
+
+  // Degeneracy is relative to scale: a tiny but well-shaped triangle is fine.
+  let tiny = Triangle3::new(
+    Vector3::zero(),
+    Vector3::new(1e-5, 0.0, 0.0),
+    Vector3::new(0.0, 1e-5, 0.0)
+  );
+  assert!(!tiny.is_degenerate());
+  assert_eq!(tiny.try_normal(), Some(Vector3::z_unit()));
+  assert!(utils::equal_with_bound(tiny.angle(0), PI / 2.0));
+  assert!(utils::equal_with_bound(tiny.min_angle(), PI / 4.0));
+  let r = tiny.inradius();
+  assert!(utils::equal_with_bound(r / 1e-5, (2.0 - 2.0f64.sqrt()) / 2.0));
+  assert!((tiny.incenter() - Vector3::new(r, r, 0.0)).len() < 1e-20);
+  let center = tiny.circumcenter().unwrap();
+  assert!((center - Vector3::new(5e-6, 5e-6, 0.0)).len() < 1e-20);
+
+  let sliver = Triangle3::new(
+    Vector3::zero(),
+    Vector3::new(1000.0, 0.0, 0.0),
+    Vector3::new(500.0, 1e-6, 0.0)
+  );
+  let collinear = Triangle3::new(
+    Vector3::zero(),
+    Vector3::new(1.0, 1.0, 1.0),
+    Vector3::new(2.0, 2.0, 2.0)
+  );
+  let point = Triangle3::new(Vector3::one(), Vector3::one(), Vector3::one());
+  for t in [sliver, collinear, point].iter() {
+    assert!(t.is_degenerate());
+    assert!(t.circumcenter().is_none());
+    assert_eq!(t.aspect_ratio(), constants::INFINITY);
+    assert_eq!(t.radius_ratio(), constants::INFINITY);
+  }
+  assert_eq!(point.incenter(), Vector3::one());
+  assert_eq!(point.angle(1), 0.0);
+
+  // Only triangles with collinear vertices have no normal, slivers do.
+  assert!(collinear.try_normal().is_none());
+  assert!(point.try_normal().is_none());
+  assert_eq!(sliver.normal(), Vector3::z_unit());
+  let sliver = Triangle3::new(
+    Vector3::zero(),
+    Vector3::new(1000.0, 0.0, 0.0),
+    Vector3::new(500.0, 1e-3, 0.0)
+  );
+  assert_eq!(sliver.normal(), Vector3::z_unit());
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_barycentric() {
+  let mut tb = create_test_bed();
+
+  assert_eq!(tb.tri1.barycentric(Vector3::new(0.0, 2.0, 0.0)),
+      Some(Vector3::new(0.0, 0.0, 1.0)));
+  assert_eq!(tb.tri1.barycentric(Vector3::new(0.25, 0.5, 3.0)),
+      Some(Vector3::new(0.5, 0.25, 0.25)));
+  // Outside of the triangle.
+  assert_eq!(tb.tri1.barycentric(Vector3::new(1.0, 2.0, 0.0)),
+      Some(Vector3::new(-1.0, 1.0, 1.0)));
+  let c = tb.tri2.barycentric(tb.tri2.centroid()).unwrap();
+  assert_eq!(c, Vector3::identity(1.0 / 3.0));
+  assert!(Triangle3::new(Vector3::zero(), Vector3::one(), Vector3::one())
+      .barycentric(Vector3::zero()).is_none());
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_closest_point() {
+  let mut tb = create_test_bed();
+
+  // Interior, edges and vertices of tri1.
+  assert_eq!(tb.tri1.closest_point(Vector3::new(0.25, 0.5, 3.0)),
+      Vector3::new(0.25, 0.5, 0.0));
+  assert_eq!(tb.tri1.closest_point_barycentric(Vector3::new(0.5, -1.0, 1.0)),
+      Vector3::new(0.5, 0.5, 0.0));
+  assert_eq!(tb.tri1.closest_point(Vector3::new(-1.0, 1.0, 0.0)),
+      Vector3::new(0.0, 1.0, 0.0));
+  assert_eq!(tb.tri1.closest_point(Vector3::new(1.0, 2.0, 0.0)),
+      Vector3::new(0.2, 1.6, 0.0));
+  assert_eq!(tb.tri1.closest_point(Vector3::new(-1.0, -1.0, 1.0)),
+      Vector3::zero());
+  assert_eq!(tb.tri1.closest_point(Vector3::new(2.0, -0.5, 0.0)),
+      Vector3::x_unit());
+  assert_eq!(tb.tri1.closest_point(Vector3::new(0.0, 3.0, -1.0)),
+      Vector3::new(0.0, 2.0, 0.0));
+  assert_eq!(tb.tri2.closest_point(Vector3::zero()),
+      Vector3::identity(1.0 / 3.0));
+  // Degenerate triangles are treated as segments.
+  let segment = Triangle3::new(Vector3::zero(), Vector3::x_unit() * 2.0,
+      Vector3::x_unit());
+  assert_eq!(segment.closest_point(Vector3::new(1.5, 1.0, 0.0)),
+      Vector3::new(1.5, 0.0, 0.0));
+  let params = segment.closest_point_barycentric(Vector3::new(3.0, 0.0, 1.0));
+  assert!(utils::equal_with_bound(params.x() + params.y() + params.z(), 1.0));
+
+  dispose_test_bed(&mut tb);
+}
+
+#[test]
+fn test_solid_angle() {
+  let mut tb = create_test_bed();
+
+  // tri2 covers an octant as seen from the origin, behind it.
+  assert!((tb.tri2.solid_angle(Vector3::zero())
+      - PI / 2.0).abs() < 1e-12);
+  assert!(tb.tri2.solid_angle(Vector3::one()) < 0.0);
+  // Points in the plane of the triangle see no solid angle.
+  assert!(tb.tri1.solid_angle(Vector3::new(3.0, 3.0, 0.0)).abs() < 1e-12);
+  // Faces of a tetrahedron sum up to 4 * pi inside, and 0 outside.
+  let v = [Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::z_unit()];
+  let faces = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+  let total = |p: Vector3| faces.iter()
+      .map(|f| Triangle3::new(v[f[0]], v[f[1]], v[f[2]]).solid_angle(p))
+      .sum::<f64>();
+  assert!((total(Vector3::identity(0.1)) - 4.0 * PI).abs() < 1e-9);
+  assert!(total(Vector3::identity(1.0)).abs() < 1e-9);
+
+  dispose_test_bed(&mut tb);
+}
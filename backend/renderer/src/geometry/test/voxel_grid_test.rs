@@ -0,0 +1,47 @@
+use super::super::{BoundingBox3, HasBoundingBox3, VoxelGrid};
+use super::super::super::math::Vector3;
+
+#[test]
+fn test_layout() {
+  let mut grid = VoxelGrid::new(Vector3::new(1.0, 2.0, 3.0), 0.5, [4, 3, 2],
+      0u8);
+  assert_eq!(grid.num_voxels(), 24);
+  assert_eq!(grid.index(1, 0, 0), 1);
+  assert_eq!(grid.index(0, 1, 0), 4);
+  assert_eq!(grid.index(0, 0, 1), 12);
+  assert_eq!(grid.coords(23), [3, 2, 1]);
+  for index in 0..grid.num_voxels() {
+    let [i, j, k] = grid.coords(index);
+    assert_eq!(grid.index(i, j, k), index);
+  }
+  grid.set(3, 1, 1, 7);
+  assert_eq!(*grid.get(3, 1, 1), 7);
+  assert_eq!(grid.values()[19], 7);
+  let doubled = grid.map(|v| *v as f32 * 2.0);
+  assert_eq!(doubled.values()[grid.index(3, 1, 1)], 14.0);
+  assert_eq!(doubled.resolution(), [4, 3, 2]);
+}
+
+#[test]
+fn test_geometry() {
+  let grid = VoxelGrid::new(Vector3::new(1.0, 2.0, 3.0), 0.5, [4, 3, 2],
+      false);
+  assert_eq!(grid.bounding_box3(),
+      BoundingBox3::new_from_nums(1.0, 2.0, 3.0, 3.0, 3.5, 4.0));
+  assert_eq!(grid.voxel_center(0, 0, 0), Vector3::new(1.25, 2.25, 3.25));
+  assert_eq!(grid.voxel_box(3, 2, 1),
+      BoundingBox3::new_from_nums(2.5, 3.0, 3.5, 3.0, 3.5, 4.0));
+  assert_eq!(grid.voxel_at(Vector3::new(1.25, 2.25, 3.25)), Some([0, 0, 0]));
+  assert_eq!(grid.voxel_at(Vector3::new(1.5, 2.0, 3.0)), Some([1, 0, 0]));
+  assert_eq!(grid.voxel_at(Vector3::new(3.0, 3.5, 4.0)), Some([3, 2, 1]));
+  assert_eq!(grid.voxel_at(Vector3::new(3.1, 3.0, 3.5)), None);
+  assert_eq!(grid.voxel_at(Vector3::new(2.0, 3.0, 2.9)), None);
+
+  // Grids covering a box are centered on it.
+  let b = BoundingBox3::new_from_nums(0.0, 0.0, 0.0, 1.0, 2.0, 0.0);
+  let grid = VoxelGrid::new_covering(&b, 0.25, 0.1, 0.0);
+  assert_eq!(grid.resolution(), [5, 9, 1]);
+  assert!((grid.bounding_box3().center() - b.center()).len() < 1e-12);
+  assert!(grid.bounding_box3().min_x() <= -0.1);
+  assert!(grid.bounding_box3().max_y() >= 2.1);
+}
@@ -1,4 +1,4 @@
-use geometry::BoundingBox3;
+use geometry::{BoundingBox3, BoundingSphere};
 
 /// A trait for entities with surface area property.
 pub trait HasSurfaceArea {
@@ -14,3 +14,8 @@ pub trait HasVolume {
 pub trait HasBoundingBox3 {
   fn bounding_box3(&self) -> BoundingBox3;
 }
+
+/// A trait for entities with a bounding sphere.
+pub trait HasBoundingSphere {
+  fn bounding_sphere(&self) -> BoundingSphere;
+}
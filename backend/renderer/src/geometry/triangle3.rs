@@ -1,5 +1,8 @@
 use common::{constants, utils};
-use geometry::{HasSurfaceArea, HasVolume, HasBoundingBox3, BoundingBox3};
+use geometry::{
+    HasSurfaceArea, HasVolume, HasBoundingBox3, HasBoundingSphere,
+    BoundingBox3, BoundingSphere,
+};
 use math::Vector3;
 use std::cmp::{PartialEq};
 use std::fmt;
@@ -33,10 +36,290 @@ impl Triangle3 {
     self.vertices[(ind + 1) % 3] - self.vertices[ind]
   }
 
+  /// Returns the unit normal vector of the triangle, following the
+  /// counter-clockwise order of its vertices. Panics if the cross product of
+  /// its edges vanishes, see try_normal() for a non-panicking version.
   pub fn normal(&self) -> Vector3 {
-    let n : Vector3 = self.e(0) ^ self.e(1);
-    assert!(n.len2() > constants::EPSILON_TINY);
-    n.normalize()
+    self.try_normal().expect("Triangle with collinear edges has no normal!")
+  }
+
+  /// Returns the unit normal vector of the triangle, or None if the cross
+  /// product of its edges vanishes (up to rounding errors), i.e. its vertices
+  /// are exactly collinear. Thin slivers, which is_degenerate() reports, still
+  /// have normals.
+  pub fn try_normal(&self) -> Option<Vector3> {
+    let (a, b) = (self.e(0), self.e(1));
+    let n = a ^ b;
+    let len = n.len();
+    if len <= f64::EPSILON * a.len() * b.len() {
+      return None;
+    }
+    // Normalized by hand since Vector3::normalize() treats vectors shorter
+    // than an absolute bound as zero, which does not hold for tiny triangles.
+    Some(n * (1.0 / len))
+  }
+
+  /// Returns the length of the edge at a given index, see e() for indexing.
+  pub fn edge_len(&self, ind: usize) -> f64 {
+    self.e(ind).len()
+  }
+
+  /// Returns lengths of all three edges, in the same order as e().
+  pub fn edge_lens(&self) -> [f64; 3] {
+    [self.edge_len(0), self.edge_len(1), self.edge_len(2)]
+  }
+
+  /// Returns the length of the shortest edge.
+  pub fn min_edge_len(&self) -> f64 {
+    utils::min_array(&self.edge_lens())
+  }
+
+  /// Returns the length of the longest edge.
+  pub fn max_edge_len(&self) -> f64 {
+    utils::max_array(&self.edge_lens())
+  }
+
+  /// Returns the sum of lengths of all three edges.
+  pub fn perimeter(&self) -> f64 {
+    self.edge_lens().iter().sum()
+  }
+
+  /// Returns the interior angle (in radians) at the vertex of a given index.
+  /// Returns 0 if any of the two edges adjacent to the vertex has zero length.
+  pub fn angle(&self, ind: usize) -> f64 {
+    let a = self.e(ind);
+    let b = -self.e((ind + 2) % 3);
+    let len = a.len() * b.len();
+    if len == 0.0 {
+      return 0.0;
+    }
+    (a * b / len).clamp(-1.0, 1.0).acos()
+  }
+
+  /// Returns the smallest interior angle (in radians).
+  pub fn min_angle(&self) -> f64 {
+    utils::min(self.angle(0), self.angle(1), self.angle(2))
+  }
+
+  /// Returns the largest interior angle (in radians).
+  pub fn max_angle(&self) -> f64 {
+    utils::max(self.angle(0), self.angle(1), self.angle(2))
+  }
+
+  /// Returns true if the triangle is degenerate, i.e. collapses into a segment
+  /// or a point. The test is relative to the scale of the triangle: it is
+  /// degenerate if its smallest altitude is negligible (within EPSILON)
+  /// compared with its longest edge.
+  pub fn is_degenerate(&self) -> bool {
+    let max_len = self.max_edge_len();
+    2.0 * self.surface_area() <= constants::EPSILON * max_len * max_len
+  }
+
+  /// Returns the radius of the inscribed circle, which is 0 for degenerate
+  /// triangles.
+  pub fn inradius(&self) -> f64 {
+    let perimeter = self.perimeter();
+    if perimeter == 0.0 {
+      return 0.0;
+    }
+    2.0 * self.surface_area() / perimeter
+  }
+
+  /// Returns the radius of the circumscribed circle, or INFINITY for
+  /// degenerate triangles.
+  pub fn circumradius(&self) -> f64 {
+    if self.is_degenerate() {
+      return constants::INFINITY;
+    }
+    let l = self.edge_lens();
+    l[0] * l[1] * l[2] / (4.0 * self.surface_area())
+  }
+
+  /// Returns the aspect ratio of the triangle, defined as the longest edge
+  /// divided by the diameter of the inscribed circle, normalized so that an
+  /// equilateral triangle has aspect ratio 1. Slivers and needles have large
+  /// ratios, and degenerate triangles have a ratio of INFINITY.
+  pub fn aspect_ratio(&self) -> f64 {
+    if self.is_degenerate() {
+      return constants::INFINITY;
+    }
+    self.max_edge_len() / (2.0 * 3.0f64.sqrt() * self.inradius())
+  }
+
+  /// Returns the ratio of circumradius to inradius, which is 2 for an
+  /// equilateral triangle (the minimum) and INFINITY for degenerate ones.
+  pub fn radius_ratio(&self) -> f64 {
+    if self.is_degenerate() {
+      return constants::INFINITY;
+    }
+    self.circumradius() / self.inradius()
+  }
+
+  /// Returns the centroid, i.e. the average of the three vertices.
+  pub fn centroid(&self) -> Vector3 {
+    (self.vertices[0] + self.vertices[1] + self.vertices[2]) / 3.0
+  }
+
+  /// Returns the center of the circumscribed circle, or None if the triangle
+  /// is degenerate.
+  pub fn circumcenter(&self) -> Option<Vector3> {
+    if self.is_degenerate() {
+      return None;
+    }
+    let a = self.e(0);
+    let b = -self.e(2);
+    let n = a ^ b;
+    let offset = ((n ^ a) * b.len2() + (b ^ n) * a.len2())
+        * (1.0 / (2.0 * n.len2()));
+    Some(self.vertices[0] + offset)
+  }
+
+  /// Returns the center of the inscribed circle. For a triangle collapsed
+  /// into a single point, returns that point.
+  pub fn incenter(&self) -> Vector3 {
+    let perimeter = self.perimeter();
+    if perimeter == 0.0 {
+      return self.vertices[0];
+    }
+    // The weight of each vertex is the length of its opposite edge.
+    (self.vertices[0] * self.edge_len(1)
+        + self.vertices[1] * self.edge_len(2)
+        + self.vertices[2] * self.edge_len(0)) * (1.0 / perimeter)
+  }
+
+  /// Returns the barycentric coordinates (a, b, c) of the orthogonal
+  /// projection of a point onto the plane of the triangle, such that the
+  /// projected point is a * v0 + b * v1 + c * v2 and a + b + c = 1. The
+  /// coordinates are all within [0, 1] iff the projection lies in the
+  /// triangle. Returns None if the triangle is degenerate.
+  pub fn barycentric(&self, p: Vector3) -> Option<Vector3> {
+    if self.is_degenerate() {
+      return None;
+    }
+    let e0 = self.e(0);
+    let e1 = -self.e(2);
+    let d = p - self.vertices[0];
+    let d00 = e0 * e0;
+    let d01 = e0 * e1;
+    let d11 = e1 * e1;
+    let d20 = d * e0;
+    let d21 = d * e1;
+    let denom = d00 * d11 - d01 * d01;
+    let b = (d11 * d20 - d01 * d21) / denom;
+    let c = (d00 * d21 - d01 * d20) / denom;
+    Some(Vector3::new(1.0 - b - c, b, c))
+  }
+
+  /// Returns the point of the triangle closest to a given point.
+  pub fn closest_point(&self, p: Vector3) -> Vector3 {
+    let params = self.closest_point_barycentric(p);
+    self.vertices[0] * params.x() + self.vertices[1] * params.y()
+        + self.vertices[2] * params.z()
+  }
+
+  /// Returns the barycentric coordinates (a, b, c) of the point of the
+  /// triangle closest to a given point, which are within [0, 1] and sum up
+  /// to 1, see barycentric(). The Voronoi region of the point (a vertex, an
+  /// edge or the interior) is determined first. For details please read:
+  ///
+  /// > Real-Time Collision Detection, Chapter 5.1.5. C. Ericson.
+  ///
+  /// Degenerate triangles are treated as their three edges.
+  pub fn closest_point_barycentric(&self, p: Vector3) -> Vector3 {
+    if self.is_degenerate() {
+      return self.closest_point_on_edges(p);
+    }
+    let [a, b, c] = self.vertices;
+    let (ab, ac) = (b - a, c - a);
+    let ap = p - a;
+    let (d1, d2) = (ab * ap, ac * ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+      return Vector3::new(1.0, 0.0, 0.0);
+    }
+    let bp = p - b;
+    let (d3, d4) = (ab * bp, ac * bp);
+    if d3 >= 0.0 && d4 <= d3 {
+      return Vector3::new(0.0, 1.0, 0.0);
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+      let t = d1 / (d1 - d3);
+      return Vector3::new(1.0 - t, t, 0.0);
+    }
+    let cp = p - c;
+    let (d5, d6) = (ab * cp, ac * cp);
+    if d6 >= 0.0 && d5 <= d6 {
+      return Vector3::new(0.0, 0.0, 1.0);
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+      let t = d2 / (d2 - d6);
+      return Vector3::new(1.0 - t, 0.0, t);
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+      let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+      return Vector3::new(0.0, 1.0 - t, t);
+    }
+    let sum = va + vb + vc;
+    Vector3::new(va / sum, vb / sum, vc / sum)
+  }
+
+  /// Returns the signed solid angle subtended by the triangle at a point,
+  /// which is positive if the point is on the back side of the triangle,
+  /// i.e. in the negative half-space of its normal, from where it appears
+  /// clockwise. Solid angles of the faces of a closed surface sum up to
+  /// 4 * pi at points inside it. For details please read:
+  ///
+  /// > The Solid Angle of a Plane Triangle. A. Van Oosterom, J. Strackee.
+  /// > IEEE Transactions on Biomedical Engineering, 1983.
+  pub fn solid_angle(&self, p: Vector3) -> f64 {
+    let (a, b, c) = (self.vertices[0] - p, self.vertices[1] - p,
+        self.vertices[2] - p);
+    let (la, lb, lc) = (a.len(), b.len(), c.len());
+    let numerator = a * (b ^ c);
+    let denominator = la * lb * lc + (a * b) * lc + (a * c) * lb
+        + (b * c) * la;
+    2.0 * numerator.atan2(denominator)
+  }
+
+  /// Tests whether this triangle overlaps with an axis-aligned bounding box,
+  /// using the separating axis theorem. Touching counts as overlapping.
+  ///
+  /// The 13 candidate axes are the 3 box face normals, the triangle normal and
+  /// the 9 cross products between box edges and triangle edges. For details:
+  ///
+  /// > Fast 3D Triangle-Box Overlap Testing, T. Akenine-Möller, 2001.
+  pub fn overlaps_box(&self, b: &BoundingBox3) -> bool {
+    let c = b.center();
+    let h = b.half_size();
+    let v = [self.vertices[0] - c, self.vertices[1] - c, self.vertices[2] - c];
+    let units = [Vector3::x_unit(), Vector3::y_unit(), Vector3::z_unit()];
+
+    let mut axes = Vec::with_capacity(13);
+    axes.extend_from_slice(&units);
+    axes.push(self.e(0) ^ self.e(1));
+    for u in units.iter() {
+      for i in 0..3 {
+        axes.push(*u ^ self.e(i));
+      }
+    }
+    !axes.iter().any(|axis| Self::separated_by_axis(&v, h, *axis))
+  }
+
+  /// Clips this triangle against an axis-aligned bounding box with the
+  /// Sutherland-Hodgman algorithm, and returns the tight bounding box of the
+  /// clipped polygon. Returns None if the triangle lies outside of the box.
+  pub fn clip_to_box(&self, b: &BoundingBox3) -> Option<BoundingBox3> {
+    let mut polygon = self.vertices.to_vec();
+    for axis in 0..3 {
+      polygon = Self::clip_polygon(&polygon, axis, b.min_corner()[axis], true);
+      polygon = Self::clip_polygon(&polygon, axis, b.max_corner()[axis], false);
+      if polygon.is_empty() {
+        return None;
+      }
+    }
+    Some(BoundingBox3::new_from_points(&polygon))
   }
 
   pub fn new(v1: Vector3, v2: Vector3, v3: Vector3) -> Self {
@@ -50,6 +333,70 @@ impl Triangle3 {
       vertices: [v[0], v[1], v[2]],
     }
   }
+
+  // Returns the barycentric coordinates of the point closest to a given
+  // point on the edges of the triangle.
+  fn closest_point_on_edges(&self, p: Vector3) -> Vector3 {
+    let mut closest = Vector3::new(1.0, 0.0, 0.0);
+    let mut min_distance = constants::INFINITY;
+    for i in 0..3 {
+      let (a, e) = (self.vertices[i], self.e(i));
+      let t = if e.len2() > 0.0 {
+        ((p - a) * e / e.len2()).clamp(0.0, 1.0)
+      } else {
+        0.0
+      };
+      let d = (a + e * t - p).len2();
+      if d < min_distance {
+        min_distance = d;
+        closest = Vector3::zero();
+        closest[i] = 1.0 - t;
+        closest[(i + 1) % 3] = t;
+      }
+    }
+    closest
+  }
+
+  // Tests whether the projections of the triangle (with vertices relative to
+  // the box center) and of the box onto the axis are disjoint. Axes of
+  // (almost) zero length are degenerate and never separate.
+  fn separated_by_axis(v: &[Vector3; 3], h: Vector3, axis: Vector3) -> bool {
+    if axis.len2() < constants::EPSILON_TINY {
+      return false;
+    }
+    let p = [v[0] * axis, v[1] * axis, v[2] * axis];
+    let r = h.x() * axis.x().abs() + h.y() * axis.y().abs()
+        + h.z() * axis.z().abs();
+    utils::min(p[0], p[1], p[2]) > r || utils::max(p[0], p[1], p[2]) < -r
+  }
+
+  // Clips a convex polygon against an axis-aligned plane, keeping the part
+  // where p[axis] >= bound if keep_above is true, or p[axis] <= bound
+  // otherwise. Vertices created on the plane have their coordinate snapped
+  // exactly onto it.
+  fn clip_polygon(polygon: &[Vector3], axis: usize, bound: f64,
+      keep_above: bool) -> Vec<Vector3> {
+    let inside = |p: &Vector3| if keep_above {
+      p[axis] >= bound
+    } else {
+      p[axis] <= bound
+    };
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+    for i in 0..polygon.len() {
+      let cur = polygon[i];
+      let next = polygon[(i + 1) % polygon.len()];
+      if inside(&cur) {
+        result.push(cur);
+      }
+      if inside(&cur) != inside(&next) {
+        let t = (bound - cur[axis]) / (next[axis] - cur[axis]);
+        let mut p = cur + (next - cur) * t;
+        p[axis] = bound;
+        result.push(p);
+      }
+    }
+    result
+  }
 }
 
 /// Overriding == and != to allow comparison within error bounds.
@@ -90,6 +437,15 @@ impl HasBoundingBox3 for Triangle3 {
   }
 }
 
+/// The minimal bounding sphere of the triangle, which is the circumsphere for
+/// acute triangles, or the sphere with the longest edge as its diameter
+/// otherwise.
+impl HasBoundingSphere for Triangle3 {
+  fn bounding_sphere(&self) -> BoundingSphere {
+    BoundingSphere::new_welzl(&self.vertices)
+  }
+}
+
 /// Print the triangle as:
 /// Triangle3(v1, v2, v3)
 impl fmt::Display for Triangle3 {
@@ -0,0 +1,361 @@
+use geometry::triangular_mesh::{
+    TriangularMesh, Vertex, Edge, Face, VertexNormalMode, FaceNormalMode,
+    NormalWeighting,
+};
+use math::{Vector2, Vector3};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Errors of building a triangular mesh from invalid input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MeshBuildError {
+  /// A face refers to a vertex index that was never added.
+  VertexIndexOutOfRange {
+    face: usize,
+    vertex: usize,
+    num_vertices: usize,
+  },
+  /// A face refers to the same vertex more than once.
+  RepeatedVertexInFace {
+    face: usize,
+    vertex: usize,
+  },
+  /// A spatial edge, given by its two vertices, is shared by more than two
+  /// faces, which cannot be represented by half-edges and twins.
+  NonManifoldEdge {
+    vertices: (usize, usize),
+    faces: Vec<usize>,
+  },
+  /// Vertex normals are USER_SPECIFIED but a vertex was added without normal.
+  MissingVertexNormal {
+    vertex: usize,
+  },
+  /// Face normals are USER_SPECIFIED but a face was added without normal.
+  MissingFaceNormal {
+    face: usize,
+  },
+  /// Texture coordinates were set for a vertex index that was never added.
+  UvVertexOutOfRange {
+    vertex: usize,
+    num_vertices: usize,
+  },
+  /// Corner texture coordinates were set for a face index that was never
+  /// added.
+  UvFaceOutOfRange {
+    face: usize,
+    num_faces: usize,
+  },
+}
+
+impl fmt::Display for MeshBuildError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      MeshBuildError::VertexIndexOutOfRange { face, vertex, num_vertices } =>
+        write!(f, "Face #{} refers to vertex #{}, but there are only {} \
+            vertices", face, vertex, num_vertices),
+      MeshBuildError::RepeatedVertexInFace { face, vertex } =>
+        write!(f, "Face #{} refers to vertex #{} more than once", face, vertex),
+      MeshBuildError::NonManifoldEdge { vertices, ref faces } =>
+        write!(f, "Edge between vertices #{} and #{} is shared by {} faces \
+            {:?}, at most 2 are allowed", vertices.0, vertices.1, faces.len(),
+            faces),
+      MeshBuildError::MissingVertexNormal { vertex } =>
+        write!(f, "Vertex #{} has no normal, which is required by \
+            VertexNormalMode::USER_SPECIFIED", vertex),
+      MeshBuildError::MissingFaceNormal { face } =>
+        write!(f, "Face #{} has no normal, which is required by \
+            FaceNormalMode::USER_SPECIFIED", face),
+      MeshBuildError::UvVertexOutOfRange { vertex, num_vertices } =>
+        write!(f, "Texture coordinates are set for vertex #{}, but there are \
+            only {} vertices", vertex, num_vertices),
+      MeshBuildError::UvFaceOutOfRange { face, num_faces } =>
+        write!(f, "Texture coordinates are set for face #{}, but there are \
+            only {} faces", face, num_faces),
+    }
+  }
+}
+
+impl Error for MeshBuildError {}
+
+/// Builder of TriangularMesh. Vertices and faces are added one by one, each
+/// returning its index in the built mesh, then build() validates the input and
+/// populates the DCEL.
+///
+/// Faces are specified by indices of their vertices in counter-clockwise
+/// order. Adjacent faces should have consistent orientations, i.e. traverse
+/// their shared edge in opposite directions, otherwise their half-edges are
+/// not linked as twins.
+///
+/// By default, vertex normals are AVERAGED with ANGLE weighting and face
+/// normals are FLAT. Normals given by users are ignored unless the
+/// corresponding mode is USER_SPECIFIED.
+///
+/// Edges shared by more than two faces are rejected, unless
+/// set_allow_non_manifold_edges() is enabled, in which case none of their
+/// half-edges get twins. This allows broken assets to be loaded and inspected
+/// with TriangularMesh::validate().
+///
+/// Texture coordinates can be set per vertex, or per corner of a face with
+/// set_face_uvs(), e.g. along texture seams where a vertex is shared by faces
+/// with different texture coordinates. Corner texture coordinates take
+/// precedence over those of vertices. Like vertex indices of faces, indices
+/// of vertices and faces given texture coordinates are validated in build().
+#[derive(Debug, Clone)]
+pub struct TriangularMeshBuilder {
+  vertices: Vec<Vertex>,
+  faces: Vec<[usize; 3]>,
+  face_normals: Vec<Option<Vector3>>,
+  // Texture coordinates by vertex or face index, in the order they were set.
+  vertex_uvs: Vec<(usize, Vector2)>,
+  face_uvs: Vec<(usize, [Vector2; 3])>,
+  vertex_normal_mode: VertexNormalMode,
+  face_normal_mode: FaceNormalMode,
+  normal_weighting: NormalWeighting,
+  allow_non_manifold_edges: bool,
+}
+
+impl Default for TriangularMeshBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl TriangularMeshBuilder {
+  pub fn new() -> Self {
+    Self {
+      vertices: Vec::new(),
+      faces: Vec::new(),
+      face_normals: Vec::new(),
+      vertex_uvs: Vec::new(),
+      face_uvs: Vec::new(),
+      vertex_normal_mode: VertexNormalMode::AVERAGED,
+      face_normal_mode: FaceNormalMode::FLAT,
+      normal_weighting: NormalWeighting::ANGLE,
+      allow_non_manifold_edges: false,
+    }
+  }
+
+  pub fn set_vertex_normal_mode(&mut self, mode: VertexNormalMode) {
+    self.vertex_normal_mode = mode;
+  }
+
+  pub fn set_face_normal_mode(&mut self, mode: FaceNormalMode) {
+    self.face_normal_mode = mode;
+  }
+
+  pub fn set_normal_weighting(&mut self, weighting: NormalWeighting) {
+    self.normal_weighting = weighting;
+  }
+
+  pub fn set_allow_non_manifold_edges(&mut self, allow: bool) {
+    self.allow_non_manifold_edges = allow;
+  }
+
+  /// Adds a vertex without normal and returns its index.
+  pub fn add_vertex(&mut self, pos: Vector3) -> usize {
+    self.vertices.push(Vertex::new(pos));
+    self.vertices.len() - 1
+  }
+
+  /// Adds a vertex with a user-specified normal and returns its index.
+  pub fn add_vertex_with_normal(&mut self, pos: Vector3, normal: Vector3)
+      -> usize {
+    self.vertices.push(Vertex::new_with_normal(pos, normal));
+    self.vertices.len() - 1
+  }
+
+  /// Sets texture coordinates of a vertex. The index is validated in
+  /// build().
+  pub fn set_vertex_uv(&mut self, v: usize, uv: Vector2) {
+    self.vertex_uvs.push((v, uv));
+  }
+
+  /// Adds a list of vertices without normals, and returns the index of the
+  /// first one.
+  pub fn add_vertices(&mut self, positions: &[Vector3]) -> usize {
+    let first = self.vertices.len();
+    for p in positions.iter() {
+      self.add_vertex(*p);
+    }
+    first
+  }
+
+  /// Adds a face formed by three vertex indices in counter-clockwise order
+  /// and returns its index. Indices are validated in build().
+  pub fn add_face(&mut self, v0: usize, v1: usize, v2: usize) -> usize {
+    self.faces.push([v0, v1, v2]);
+    self.face_normals.push(None);
+    self.faces.len() - 1
+  }
+
+  /// Adds a face with a user-specified normal and returns its index.
+  pub fn add_face_with_normal(&mut self, v0: usize, v1: usize, v2: usize,
+      normal: Vector3) -> usize {
+    self.faces.push([v0, v1, v2]);
+    self.face_normals.push(Some(normal.normalize()));
+    self.faces.len() - 1
+  }
+
+  /// Sets texture coordinates of the three corners of a face, in the same
+  /// order as its vertices. The index is validated in build().
+  pub fn set_face_uvs(&mut self, f: usize, uvs: [Vector2; 3]) {
+    self.face_uvs.push((f, uvs));
+  }
+
+  /// Adds a list of faces, and returns the index of the first one.
+  pub fn add_faces(&mut self, faces: &[[usize; 3]]) -> usize {
+    let first = self.faces.len();
+    for f in faces.iter() {
+      self.add_face(f[0], f[1], f[2]);
+    }
+    first
+  }
+
+  /// Validates the input and builds the mesh. The half-edges of face #f are
+  /// #3f, #3f+1 and #3f+2, going from its vertex #0 to #1, #1 to #2 and #2 to
+  /// #0 respectively.
+  pub fn build(self) -> Result<TriangularMesh, MeshBuildError> {
+    self.validate_faces()?;
+    self.validate_normals()?;
+    self.validate_uvs()?;
+
+    let mut vertices = self.vertices;
+    for &(v, uv) in self.vertex_uvs.iter() {
+      vertices[v].uv = Some(uv);
+    }
+    let mut edges = Vec::with_capacity(self.faces.len() * 3);
+    let mut faces = Vec::with_capacity(self.faces.len());
+    for (f, v) in self.faces.iter().enumerate() {
+      let e = [3 * f, 3 * f + 1, 3 * f + 2];
+      for i in 0..3 {
+        edges.push(Edge {
+          start_vertex: v[i],
+          end_vertex: v[(i + 1) % 3],
+          face: f,
+          twin_edge: None,
+          next_edge: e[(i + 1) % 3],
+          prev_edge: e[(i + 2) % 3],
+        });
+        vertices[v[i]].edges.push(e[i]);
+      }
+      let face = match self.face_normals[f] {
+        Some(n) if self.face_normal_mode == FaceNormalMode::USER_SPECIFIED =>
+          Face::new_with_normal(*v, e, n),
+        _ => Face::new(*v, e),
+      };
+      faces.push(face);
+    }
+    for &(f, uvs) in self.face_uvs.iter() {
+      faces[f].uvs = Some(uvs);
+    }
+    Self::link_twins(&mut edges, self.allow_non_manifold_edges)?;
+
+    if self.vertex_normal_mode == VertexNormalMode::USER_SPECIFIED {
+      for v in vertices.iter_mut() {
+        v.normal = v.normal.map(|n| n.normalize());
+      }
+    }
+    let mut mesh = TriangularMesh {
+      vertices: vertices,
+      edges: edges,
+      faces: faces,
+      vertex_normal_mode: self.vertex_normal_mode,
+      face_normal_mode: self.face_normal_mode,
+      normal_weighting: self.normal_weighting,
+    };
+    mesh.recompute_normals();
+    Ok(mesh)
+  }
+
+  // Checks that normals are given wherever they are USER_SPECIFIED.
+  fn validate_normals(&self) -> Result<(), MeshBuildError> {
+    if self.vertex_normal_mode == VertexNormalMode::USER_SPECIFIED {
+      if let Some(v) = self.vertices.iter().position(|v| v.normal.is_none()) {
+        return Err(MeshBuildError::MissingVertexNormal { vertex: v });
+      }
+    }
+    if self.face_normal_mode == FaceNormalMode::USER_SPECIFIED {
+      if let Some(f) = self.face_normals.iter().position(|n| n.is_none()) {
+        return Err(MeshBuildError::MissingFaceNormal { face: f });
+      }
+    }
+    Ok(())
+  }
+
+  // Checks that texture coordinates are set for existing vertices and faces.
+  fn validate_uvs(&self) -> Result<(), MeshBuildError> {
+    let num_vertices = self.vertices.len();
+    if let Some(&(v, _)) = self.vertex_uvs.iter()
+        .find(|&&(v, _)| v >= num_vertices) {
+      return Err(MeshBuildError::UvVertexOutOfRange {
+        vertex: v,
+        num_vertices: num_vertices,
+      });
+    }
+    let num_faces = self.faces.len();
+    if let Some(&(f, _)) = self.face_uvs.iter()
+        .find(|&&(f, _)| f >= num_faces) {
+      return Err(MeshBuildError::UvFaceOutOfRange {
+        face: f,
+        num_faces: num_faces,
+      });
+    }
+    Ok(())
+  }
+
+  // Checks that all faces refer to three distinct, existing vertices.
+  fn validate_faces(&self) -> Result<(), MeshBuildError> {
+    for (f, v) in self.faces.iter().enumerate() {
+      for i in 0..3 {
+        if v[i] >= self.vertices.len() {
+          return Err(MeshBuildError::VertexIndexOutOfRange {
+            face: f,
+            vertex: v[i],
+            num_vertices: self.vertices.len(),
+          });
+        }
+        if v[i] == v[(i + 1) % 3] {
+          return Err(MeshBuildError::RepeatedVertexInFace {
+            face: f,
+            vertex: v[i],
+          });
+        }
+      }
+    }
+    Ok(())
+  }
+
+  // Links half-edges of opposite directions between the same two vertices as
+  // twins. Two half-edges of the same direction (i.e. adjacent faces with
+  // inconsistent orientations) are left without twins, as well as all
+  // half-edges of a non-manifold edge if those are allowed.
+  fn link_twins(edges: &mut [Edge], allow_non_manifold: bool)
+      -> Result<(), MeshBuildError> {
+    let mut spatial_edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (id, e) in edges.iter().enumerate() {
+      let key = (e.start_vertex.min(e.end_vertex),
+          e.start_vertex.max(e.end_vertex));
+      spatial_edges.entry(key).or_default().push(id);
+    }
+    // Edges are visited by id so that errors are reported deterministically.
+    for id in 0..edges.len() {
+      let key = (edges[id].start_vertex.min(edges[id].end_vertex),
+          edges[id].start_vertex.max(edges[id].end_vertex));
+      let shared = &spatial_edges[&key];
+      if shared.len() > 2 && !allow_non_manifold {
+        return Err(MeshBuildError::NonManifoldEdge {
+          vertices: key,
+          faces: shared.iter().map(|e| edges[*e].face).collect(),
+        });
+      }
+      if shared.len() == 2 {
+        let other = if shared[0] == id { shared[1] } else { shared[0] };
+        if edges[other].start_vertex == edges[id].end_vertex {
+          edges[id].twin_edge = Some(other);
+        }
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,319 @@
+use common::constants;
+use geometry::Plane;
+use geometry::triangular_mesh::{TriangularMesh, TriangularMeshBuilder};
+use math::Vector3;
+use std::collections::HashMap;
+
+impl TriangularMesh {
+  /// Builds the convex hull of a point set with the quickhull algorithm, as a
+  /// closed mesh with faces oriented outwards. Starting from a tetrahedron of
+  /// extreme points, the farthest point outside any face is repeatedly added
+  /// by replacing all faces it sees with a fan of new faces connecting it to
+  /// the horizon. For details please read:
+  ///
+  /// > The Quickhull Algorithm for Convex Hulls. C. B. Barber, D. P. Dobkin,
+  /// > H. Huhdanpaa. ACM Transactions on Mathematical Software, 1996.
+  ///
+  /// Points within a tolerance (relative to the extent of the point set) of
+  /// the hull are considered inside, so that duplicate points and points on
+  /// faces or edges of the hull are skipped rather than creating degenerate
+  /// faces. Points added before the hull grew past them, which end up on its
+  /// faces or edges (within the tolerance), are removed by building the hull
+  /// again from the extreme points only. Corners of gently curved surfaces
+  /// are kept, however small the angles between their faces. The vertices of
+  /// the mesh are the extreme points, in their order in the input. Returns
+  /// None if the points do not span a volume, i.e. if there are fewer than
+  /// four points, or all of them are (nearly) coplanar.
+  pub fn new_convex_hull(points: &[Vector3]) -> Option<TriangularMesh> {
+    let mut hull = Quickhull::new(points)?;
+    hull.run();
+    let vertices = hull.vertices();
+    let extreme: Vec<Vector3> = vertices.iter()
+        .filter(|v| hull.is_extreme(**v))
+        .map(|v| points[*v])
+        .collect();
+    if extreme.len() == vertices.len() {
+      return Some(hull.build());
+    }
+    let mut hull = Quickhull::new(&extreme)?;
+    hull.run();
+    Some(hull.build())
+  }
+}
+
+// A face of the hull under construction, with the points outside it (i.e.
+// in its positive half-space) that are not yet assigned to other faces.
+struct HullFace {
+  vertices: [usize; 3],
+  plane: Plane,
+  outside: Vec<usize>,
+  alive: bool,
+}
+
+// The state of the quickhull algorithm.
+struct Quickhull<'a> {
+  points: &'a [Vector3],
+  tolerance: f64,
+  faces: Vec<HullFace>,
+  // The face of each directed edge (start, end) of alive faces.
+  edge_faces: HashMap<(usize, usize), usize>,
+  // Faces that may have points outside them. Removed faces have none.
+  pending: Vec<usize>,
+}
+
+impl<'a> Quickhull<'a> {
+  // Initializes the hull with a tetrahedron of extreme points, and assigns
+  // the other points to its faces. Returns None if the points do not span a
+  // volume.
+  fn new(points: &'a [Vector3]) -> Option<Self> {
+    if points.len() < 4 {
+      return None;
+    }
+    let extent = points.iter()
+        .map(|p| p.x().abs().max(p.y().abs()).max(p.z().abs()))
+        .fold(0.0, f64::max);
+    let tolerance = constants::EPSILON_TINY * extent.max(1.0);
+
+    // The two most distant among the extreme points along the axes.
+    let mut extremes = Vec::with_capacity(6);
+    for axis in 0..3 {
+      extremes.push(Self::farthest(points, |p| -p[axis])?);
+      extremes.push(Self::farthest(points, |p| p[axis])?);
+    }
+    let mut v = [0; 4];
+    let mut max_distance = 0.0;
+    for &a in extremes.iter() {
+      for &b in extremes.iter() {
+        let d = (points[a] - points[b]).len2();
+        if d > max_distance {
+          max_distance = d;
+          v[0] = a;
+          v[1] = b;
+        }
+      }
+    }
+    if max_distance.sqrt() <= tolerance {
+      return None;
+    }
+    // The point farthest from the line, then the one farthest from the plane.
+    let (p0, p1) = (points[v[0]], points[v[1]]);
+    let dir = (p1 - p0).normalize();
+    v[2] = Self::farthest(points, |p| {
+      let d = p - p0;
+      (d - dir * (d * dir)).len()
+    })?;
+    if ((points[v[2]] - p0) ^ dir).len() <= tolerance {
+      return None;
+    }
+    let base = Plane::new_from_points(p0, p1, points[v[2]]);
+    v[3] = Self::farthest(points, |p| base.signed_distance(p).abs())?;
+    if base.signed_distance(points[v[3]]).abs() <= tolerance {
+      return None;
+    }
+    // Faces are oriented outwards, i.e. away from the fourth point.
+    if base.signed_distance(points[v[3]]) > 0.0 {
+      v.swap(0, 1);
+    }
+
+    let mut hull = Self {
+      points: points,
+      tolerance: tolerance,
+      faces: Vec::new(),
+      edge_faces: HashMap::new(),
+      pending: Vec::new(),
+    };
+    let faces = [[v[0], v[1], v[2]], [v[0], v[3], v[1]], [v[1], v[3], v[2]],
+        [v[2], v[3], v[0]]];
+    for face in faces.iter() {
+      hull.add_face(*face);
+    }
+    let candidates: Vec<usize> = (0..points.len())
+        .filter(|p| !v.contains(p))
+        .collect();
+    hull.assign_points(&candidates, &[0, 1, 2, 3]);
+    Some(hull)
+  }
+
+  // Adds points to the hull until no points are outside.
+  fn run(&mut self) {
+    while let Some(f) = self.pending.pop() {
+      if !self.faces[f].outside.is_empty() {
+        self.add_farthest_point(f);
+      }
+    }
+  }
+
+  // Adds the farthest point outside a face to the hull, removing all faces
+  // visible from the point and connecting the point to their horizon.
+  fn add_farthest_point(&mut self, f: usize) {
+    let points = self.points;
+    let outside = &self.faces[f].outside;
+    let plane = self.faces[f].plane;
+    let eye = *outside.iter()
+        .max_by(|a, b| plane.signed_distance(points[**a])
+            .partial_cmp(&plane.signed_distance(points[**b])).unwrap())
+        .unwrap();
+    let p = points[eye];
+
+    // Visible faces are connected, and found by a flood fill from the face.
+    let mut visible = vec![f];
+    let mut is_visible: HashMap<usize, bool> = HashMap::new();
+    is_visible.insert(f, true);
+    let mut horizon = Vec::new();
+    let mut i = 0;
+    while i < visible.len() {
+      let vertices = self.faces[visible[i]].vertices;
+      for k in 0..3 {
+        let (a, b) = (vertices[k], vertices[(k + 1) % 3]);
+        let neighbor = self.edge_faces[&(b, a)];
+        let neighbor_visible = match is_visible.get(&neighbor) {
+          Some(seen) => *seen,
+          None => {
+            let seen =
+                self.faces[neighbor].plane.signed_distance(p) > self.tolerance;
+            is_visible.insert(neighbor, seen);
+            if seen {
+              visible.push(neighbor);
+            }
+            seen
+          },
+        };
+        if !neighbor_visible {
+          horizon.push((a, b));
+        }
+      }
+      i += 1;
+    }
+
+    let mut orphans = Vec::new();
+    for g in visible.iter() {
+      let face = &mut self.faces[*g];
+      face.alive = false;
+      orphans.append(&mut face.outside);
+      let vertices = face.vertices;
+      for k in 0..3 {
+        self.edge_faces.remove(&(vertices[k], vertices[(k + 1) % 3]));
+      }
+    }
+    let new_faces: Vec<usize> = horizon.iter()
+        .map(|&(a, b)| self.add_face([a, b, eye]))
+        .collect();
+    orphans.retain(|q| *q != eye);
+    self.assign_points(&orphans, &new_faces);
+  }
+
+  // Adds a face, returning its index.
+  fn add_face(&mut self, vertices: [usize; 3]) -> usize {
+    let id = self.faces.len();
+    let [a, b, c] = vertices;
+    self.faces.push(HullFace {
+      vertices: vertices,
+      plane: Plane::new_from_points(self.points[a], self.points[b],
+          self.points[c]),
+      outside: Vec::new(),
+      alive: true,
+    });
+    for k in 0..3 {
+      self.edge_faces.insert((vertices[k], vertices[(k + 1) % 3]), id);
+    }
+    self.pending.push(id);
+    id
+  }
+
+  // Assigns each point to the face it is farthest outside of, among given
+  // faces. Points inside all of them (within the tolerance) are dropped.
+  fn assign_points(&mut self, candidates: &[usize], faces: &[usize]) {
+    for &q in candidates.iter() {
+      let p = self.points[q];
+      let mut best = None;
+      let mut max_distance = self.tolerance;
+      for &f in faces.iter() {
+        let d = self.faces[f].plane.signed_distance(p);
+        if d > max_distance {
+          max_distance = d;
+          best = Some(f);
+        }
+      }
+      if let Some(f) = best {
+        self.faces[f].outside.push(q);
+      }
+    }
+  }
+
+  // Returns the indices of points on the hull in ascending order.
+  fn vertices(&self) -> Vec<usize> {
+    let mut vertices: Vec<usize> = self.faces.iter()
+        .filter(|f| f.alive)
+        .flat_map(|f| f.vertices.iter().cloned())
+        .collect();
+    vertices.sort();
+    vertices.dedup();
+    vertices
+  }
+
+  // Returns whether a point on the hull is a corner of it, i.e. it is
+  // neither inside a face, where all its neighbors are within the tolerance
+  // of the plane of one of its faces, nor on an edge, where it is within the
+  // tolerance of the segment between two of its neighbors.
+  fn is_extreme(&self, v: usize) -> bool {
+    let faces: Vec<&HullFace> = self.faces.iter()
+        .filter(|f| f.alive && f.vertices.contains(&v))
+        .collect();
+    let mut neighbors: Vec<usize> = faces.iter()
+        .flat_map(|f| f.vertices.iter().cloned())
+        .filter(|u| *u != v)
+        .collect();
+    neighbors.sort();
+    neighbors.dedup();
+    let plane = faces[0].plane;
+    let tolerance = self.tolerance;
+    let in_plane =
+        |u: &usize| plane.signed_distance(self.points[*u]).abs() <= tolerance;
+    if neighbors.iter().all(in_plane) {
+      return false;
+    }
+    let p = self.points[v];
+    for (i, &a) in neighbors.iter().enumerate() {
+      for &b in neighbors[i + 1..].iter() {
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let ab = pb - pa;
+        let t = (p - pa) * ab / ab.len2();
+        if (0.0..=1.0).contains(&t)
+            && (pa + ab * t - p).len() <= self.tolerance {
+          return false;
+        }
+      }
+    }
+    true
+  }
+
+  // Builds the mesh from alive faces, keeping the input order of vertices.
+  fn build(&self) -> TriangularMesh {
+    let mut ids = vec![None; self.points.len()];
+    for face in self.faces.iter().filter(|f| f.alive) {
+      for v in face.vertices.iter() {
+        ids[*v] = Some(0);
+      }
+    }
+    let mut builder = TriangularMeshBuilder::new();
+    for (v, id) in ids.iter_mut().enumerate() {
+      if id.is_some() {
+        *id = Some(builder.add_vertex(self.points[v]));
+      }
+    }
+    for face in self.faces.iter().filter(|f| f.alive) {
+      let v = face.vertices;
+      builder.add_face(ids[v[0]].unwrap(), ids[v[1]].unwrap(),
+          ids[v[2]].unwrap());
+    }
+    builder.build().expect("Convex hull must be a valid mesh!")
+  }
+
+  // Returns the index of the point with the largest value of a function.
+  fn farthest<F>(points: &[Vector3], distance: F) -> Option<usize>
+      where F: Fn(Vector3) -> f64 {
+    (0..points.len())
+        .max_by(|a, b| distance(points[*a]).partial_cmp(&distance(points[*b]))
+            .unwrap())
+  }
+}
@@ -0,0 +1,227 @@
+use common::constants;
+use geometry::HasSurfaceArea;
+use geometry::triangular_mesh::TriangularMesh;
+use math::{Matrix3, Vector3};
+use std::f64::consts::PI;
+
+/// Discrete curvatures of the surface at a vertex, averaged over the mixed
+/// Voronoi area of the vertex. Curvatures are signed with respect to the
+/// vertex normal: surfaces bending away from the normal (e.g. a sphere with
+/// outward normals) have positive curvatures.
+#[derive(Debug, Copy, Clone)]
+pub struct VertexCurvature {
+  area: f64,
+  normal: Vector3,
+  mean: f64,
+  gaussian: f64,
+  max_curvature: f64,
+  min_curvature: f64,
+  max_direction: Vector3,
+  min_direction: Vector3,
+}
+
+impl VertexCurvature {
+  /// Returns the mixed Voronoi area of the vertex, over which curvatures are
+  /// averaged.
+  pub fn area(&self) -> f64 {
+    self.area
+  }
+
+  /// Returns the unit normal the curvatures are signed against.
+  pub fn normal(&self) -> Vector3 {
+    self.normal
+  }
+
+  /// Returns the mean curvature H = (k1 + k2) / 2.
+  pub fn mean(&self) -> f64 {
+    self.mean
+  }
+
+  /// Returns the Gaussian curvature K = k1 * k2.
+  pub fn gaussian(&self) -> f64 {
+    self.gaussian
+  }
+
+  /// Returns the principal curvatures (k1, k2), where k1 >= k2.
+  pub fn principal_curvatures(&self) -> (f64, f64) {
+    (self.max_curvature, self.min_curvature)
+  }
+
+  /// Returns the unit principal directions (d1, d2) in the tangent plane,
+  /// along which the surface bends by k1 and k2 respectively, where
+  /// d2 = normal ^ d1. Directions are arbitrary at umbilic points (k1 = k2).
+  pub fn principal_directions(&self) -> (Vector3, Vector3) {
+    (self.max_direction, self.min_direction)
+  }
+
+  /// Returns the shape operator (Weingarten map) as a symmetric matrix:
+  ///
+  /// > S = k1 * d1 * d1^T + k2 * d2 * d2^T,
+  ///
+  /// so that t^T * S * t is the normal curvature along a unit tangent t, and
+  /// the normal is in its null space.
+  pub fn shape_operator(&self) -> Matrix3 {
+    Matrix3::outer(self.max_direction, self.max_direction) * self.max_curvature
+        + Matrix3::outer(self.min_direction, self.min_direction)
+            * self.min_curvature
+  }
+}
+
+impl TriangularMesh {
+  /// Estimates the curvatures of the surface at a vertex from its one-ring:
+  ///
+  /// - The mean curvature by the cotangent formula, i.e. the mean curvature
+  ///   normal 2 * H * n is the Laplace-Beltrami operator of positions (see
+  ///   cotan_laplacian()) divided by the area.
+  /// - The Gaussian curvature by the angle deficit, i.e. (2 * pi minus the
+  ///   sum of angles around the vertex) divided by the area.
+  /// - The principal curvatures as H +/- sqrt(H^2 - K), where H^2 < K due to
+  ///   discretization errors is clamped to umbilic points.
+  /// - The principal directions as the eigenvectors of a quadratic form
+  ///   fitted to the normal curvatures along the edges, see shape_operator()
+  ///   of VertexCurvature.
+  ///
+  /// Areas are mixed Voronoi areas, which tile the surface without overlaps
+  /// also with obtuse faces. The normal is the vertex normal if any, or else
+  /// the area-weighted average of adjacent face normals. For details please
+  /// read:
+  ///
+  /// > Discrete Differential-Geometry Operators for Triangulated
+  /// > 2-Manifolds. M. Meyer, M. Desbrun, P. Schröder, A. H. Barr. 2003.
+  ///
+  /// Returns None for boundary and isolated vertices, where the one-ring is
+  /// incomplete, or if the one-ring has no area.
+  pub fn vertex_curvature(&self, v: usize) -> Option<VertexCurvature> {
+    let vertex = &self.vertices[v];
+    let mut outgoing = self.outgoing_edges(v).peekable();
+    if outgoing.peek().is_none() || outgoing.any(|e| {
+      let edge = &self.edges[e];
+      edge.twin_edge.is_none()
+          || self.edges[edge.prev_edge].twin_edge.is_none()
+    }) {
+      return None;
+    }
+
+    let mut area = 0.0;
+    let mut max_len2: f64 = 0.0;
+    let mut angle_sum = 0.0;
+    let mut curvature_normal = Vector3::zero();
+    let mut normal_sum = Vector3::zero();
+    // Neighbors with the total area of faces adjacent to their edges.
+    let mut neighbors: Vec<(usize, f64)> = Vec::new();
+    for f in self.vertex_faces(v) {
+      let face = &self.faces[f];
+      let t = self.triangle(f);
+      let i = face.vertices.iter().position(|u| *u == v).unwrap();
+      let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+      let face_area = t.surface_area();
+      max_len2 = max_len2.max(t.max_edge_len().powi(2));
+      angle_sum += t.angle(i);
+      normal_sum += t.e(0) ^ t.e(1);
+      for &c in [j, k].iter() {
+        match neighbors.iter().position(|&(u, _)| u == face.vertices[c]) {
+          Some(n) => neighbors[n].1 += face_area,
+          None => neighbors.push((face.vertices[c], face_area)),
+        }
+      }
+      if t.is_degenerate() {
+        continue;
+      }
+
+      let cot = self.face_cotangents(f);
+      let (eij, eik) = (t.v(j) - t.v(i), t.v(k) - t.v(i));
+      // The edge from corner i to j is opposite to the angle at k.
+      curvature_normal -= (eij * cot[k] + eik * cot[j]) / 2.0;
+      area += if cot.iter().all(|c| *c >= 0.0) {
+        (eij.len2() * cot[k] + eik.len2() * cot[j]) / 8.0
+      } else if cot[i] < 0.0 {
+        face_area / 2.0
+      } else {
+        face_area / 4.0
+      };
+    }
+    // Relative to the one-ring, as in Triangle3::is_degenerate().
+    if 2.0 * area <= constants::EPSILON * max_len2 {
+      return None;
+    }
+    let normal = match vertex.normal {
+      Some(n) => n,
+      None => {
+        let len = normal_sum.len();
+        if len == 0.0 {
+          return None;
+        }
+        normal_sum * (1.0 / len)
+      },
+    };
+
+    let mean = curvature_normal * normal / (2.0 * area);
+    let gaussian = (2.0 * PI - angle_sum) / area;
+    let delta = (mean * mean - gaussian).max(0.0).sqrt();
+    let max_direction = self.max_curvature_direction(v, normal, &neighbors);
+    Some(VertexCurvature {
+      area: area,
+      normal: normal,
+      mean: mean,
+      gaussian: gaussian,
+      max_curvature: mean + delta,
+      min_curvature: mean - delta,
+      max_direction: max_direction,
+      min_direction: normal ^ max_direction,
+    })
+  }
+
+  /// Estimates curvatures at all vertices, see vertex_curvature().
+  pub fn vertex_curvatures(&self) -> Vec<Option<VertexCurvature>> {
+    (0..self.vertices.len()).map(|v| self.vertex_curvature(v)).collect()
+  }
+
+  // Returns the direction of the maximum principal curvature at a vertex,
+  // by fitting a quadratic form in the tangent plane to the normal curvatures
+  // along the edges to its neighbors, in the least squares sense weighted by
+  // the areas of faces adjacent to the edges:
+  //
+  // > k_j = 2 * n * (v - v_j) / |v - v_j|^2 ~ a * x_j^2 + 2 * b * x_j * y_j
+  // > + c * y_j^2,
+  //
+  // where (x_j, y_j) is the unit projection of the edge onto the tangent
+  // plane. Weights are normalized to sum to 1, so that the normal equations
+  // do not depend on the scale of the mesh. Falls back to any tangent
+  // direction if the fit is not determined.
+  fn max_curvature_direction(&self, v: usize, normal: Vector3,
+      neighbors: &[(usize, f64)]) -> Vector3 {
+    let e1 = Self::orthonormalize(Vector3::x_unit(), normal)
+        .or_else(|| Self::orthonormalize(Vector3::y_unit(), normal))
+        .unwrap();
+    let e2 = normal ^ e1;
+    let p = self.vertices[v].pos;
+    let weight_sum: f64 = neighbors.iter().map(|&(_, w)| w).sum();
+    if weight_sum <= 0.0 {
+      return e1;
+    }
+    // Normal equations of the fit.
+    let mut lhs = Matrix3::zero();
+    let mut rhs = Vector3::zero();
+    for &(u, w) in neighbors.iter() {
+      let w = w / weight_sum;
+      let e = p - self.vertices[u].pos;
+      let len2 = e.len2();
+      if len2 == 0.0 {
+        continue;
+      }
+      if let Some(t) = Self::orthonormalize(e, normal) {
+        let (x, y) = (t * e1, t * e2);
+        let row = Vector3::new(x * x, 2.0 * x * y, y * y);
+        lhs += Matrix3::outer(row, row) * w;
+        rhs += row * (w * 2.0 * (normal * e) / len2);
+      }
+    }
+    if lhs.det().abs() < constants::EPSILON_TINY {
+      return e1;
+    }
+    let form = lhs.inverse().unwrap() * rhs;
+    // The eigenvector of [[a, b], [b, c]] with the larger eigenvalue.
+    let theta = 0.5 * (2.0 * form.y()).atan2(form.x() - form.z());
+    e1 * theta.cos() + e2 * theta.sin()
+  }
+}
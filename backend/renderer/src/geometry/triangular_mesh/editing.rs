@@ -0,0 +1,558 @@
+use common::constants;
+use geometry::triangular_mesh::{
+    TriangularMesh, Vertex, Edge, Face, MeshPoint, VertexNormalMode,
+    FaceNormalMode,
+};
+use math::{Vector2, Vector3};
+use std::error::Error;
+use std::fmt;
+
+/// Errors of local edit operations that cannot be applied without breaking
+/// the topology of the mesh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MeshEditError {
+  /// The edge to flip is on the boundary, i.e. has no twin.
+  BoundaryEdge {
+    edge: usize,
+  },
+  /// Flipping an edge would duplicate an existing edge between two vertices.
+  EdgeExists {
+    vertices: (usize, usize),
+  },
+  /// Collapsing an edge would make the mesh non-manifold, see
+  /// TriangularMesh::collapse_edge().
+  LinkConditionViolated {
+    edge: usize,
+  },
+}
+
+impl fmt::Display for MeshEditError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      MeshEditError::BoundaryEdge { edge } =>
+        write!(f, "Edge #{} is on the boundary and cannot be flipped", edge),
+      MeshEditError::EdgeExists { vertices } =>
+        write!(f, "An edge between vertices #{} and #{} already exists",
+            vertices.0, vertices.1),
+      MeshEditError::LinkConditionViolated { edge } =>
+        write!(f, "Collapsing edge #{} would make the mesh non-manifold", edge),
+    }
+  }
+}
+
+impl Error for MeshEditError {}
+
+impl TriangularMesh {
+  /// Flips an edge shared by two faces, i.e. replaces it with the other
+  /// diagonal of the quad formed by the two faces. Returns the new half-edge
+  /// (going between the two vertices opposite to the old edge), whose twin is
+  /// in the other face. The flip is purely topological, and may fold the two
+  /// faces over if the quad is not convex.
+  pub fn flip_edge(&mut self, e: usize) -> Result<usize, MeshEditError> {
+    let t = self.edges[e].twin_edge
+        .ok_or(MeshEditError::BoundaryEdge { edge: e })?;
+    let (a, b) = (self.edges[e].start_vertex, self.edges[e].end_vertex);
+    let (f1, f2) = (self.edges[e].face, self.edges[t].face);
+    let c = self.edges[self.edges[e].next_edge].end_vertex;
+    let d = self.edges[self.edges[t].next_edge].end_vertex;
+    if c == d || self.find_edge(c, d).is_some()
+        || self.find_edge(d, c).is_some() {
+      return Err(MeshEditError::EdgeExists { vertices: (c, d) });
+    }
+    let normal = match (self.faces[f1].normal, self.faces[f2].normal) {
+      (Some(n1), Some(n2)) => Some((n1 + n2).normalize()),
+      _ => None,
+    };
+    // Corner texture coordinates follow their vertices, from the old faces.
+    let (uvs1, uvs2) =
+        if self.faces[f1].uvs.is_some() || self.faces[f2].uvs.is_some() {
+          let uv = |f: usize, v: usize| self.corner_uv(f, &[(v, 1.0)]);
+          (Self::corners([uv(f1, c), uv(f2, d), uv(f1, b)]),
+              Self::corners([uv(f2, d), uv(f1, c), uv(f2, a)]))
+        } else {
+          (None, None)
+        };
+
+    // The quad is a -> d -> b -> c in counter-clockwise order.
+    self.set_face(f1, [c, d, b], uvs1);
+    self.set_face(f2, [d, c, a], uvs2);
+    if self.face_normal_mode == FaceNormalMode::USER_SPECIFIED {
+      self.faces[f1].normal = normal;
+      self.faces[f2].normal = normal;
+    }
+    self.finish_edit(&[f1, f2], &[]);
+    Ok(3 * f1)
+  }
+
+  /// Splits an edge at a parameter t in [0, 1] (from its start to its end
+  /// vertex) by inserting a new vertex, and splits both faces adjacent to the
+  /// edge into two. Texture coordinates (including corner ones) and
+  /// user-specified normals of the new vertex are interpolated. Returns the
+  /// index of the new vertex.
+  pub fn split_edge(&mut self, e: usize, t: f64) -> usize {
+    assert!((0.0..=1.0).contains(&t), "Parameter must be in [0, 1]!");
+    let (a, b) = (self.edges[e].start_vertex, self.edges[e].end_vertex);
+    let m = self.vertices.len();
+    let vertex = self.interpolated_vertex(&[(a, 1.0 - t), (b, t)]);
+    self.vertices.push(vertex);
+
+    let twin = self.edges[e].twin_edge;
+    let mut changed = Vec::new();
+    for &(edge, start, end) in [(Some(e), a, b), (twin, b, a)].iter() {
+      if let Some(edge) = edge {
+        let f = self.edges[edge].face;
+        let c = self.edges[self.edges[edge].next_edge].end_vertex;
+        let uvs = self.faces[f].uvs.map(|_| {
+          let uv = |v: usize| self.corner_uv(f, &[(v, 1.0)]).unwrap();
+          let um = self.corner_uv(f, &[(a, 1.0 - t), (b, t)]).unwrap();
+          ([uv(start), um, uv(c)], [um, uv(end), uv(c)])
+        });
+        self.set_face(f, [start, m, c], uvs.map(|u| u.0));
+        // The new face is coplanar with the face it is split from.
+        let normal = self.faces[f].normal;
+        changed.push(f);
+        changed.push(self.push_face([m, end, c], normal, uvs.map(|u| u.1)));
+      }
+    }
+    self.finish_edit(&changed, &[]);
+    m
+  }
+
+  /// Splits a face into three at a point, by inserting a new vertex connected
+  /// to the three vertices of the face. Texture coordinates (including corner
+  /// ones) and user-specified normals of the new vertex are interpolated.
+  /// Returns the index of the new vertex. Faces become degenerate if the
+  /// point lies on an edge of the face, see split_edge() instead.
+  pub fn split_face(&mut self, p: &MeshPoint) -> usize {
+    let f = p.face_id;
+    let v = self.faces[f].vertices;
+    let m = self.vertices.len();
+    let vertex = self.interpolated_vertex(&[(v[0], p.params[0]),
+        (v[1], p.params[1]), (v[2], p.params[2])]);
+    self.vertices.push(vertex);
+
+    let normal = self.faces[f].normal;
+    let uvs = self.faces[f].uvs.map(|u| {
+      let um = u[0] * p.params[0] + u[1] * p.params[1] + u[2] * p.params[2];
+      [[u[0], u[1], um], [u[1], u[2], um], [u[2], u[0], um]]
+    });
+    self.set_face(f, [v[0], v[1], m], uvs.map(|u| u[0]));
+    let g1 = self.push_face([v[1], v[2], m], normal, uvs.map(|u| u[1]));
+    let g2 = self.push_face([v[2], v[0], m], normal, uvs.map(|u| u[2]));
+    self.finish_edit(&[f, g1, g2], &[]);
+    m
+  }
+
+  /// Collapses an edge by merging its end vertex into its start vertex, which
+  /// is moved to a given position, and removes the faces adjacent to the edge.
+  /// Texture coordinates (including corner ones) and user-specified normals
+  /// are interpolated at the projection of the position onto the edge.
+  /// Returns the index of the merged vertex.
+  ///
+  /// The collapse is rejected if it would make the mesh non-manifold, which
+  /// is checked by the link condition: vertices adjacent to both ends of the
+  /// edge must be exactly those opposite to the edge in its faces, and no
+  /// edge may be opposite to both ends in other faces. For details please
+  /// read:
+  ///
+  /// > Topology Preserving Edge Contraction.
+  /// > T. K. Dey, H. Edelsbrunner, S. Guha, D. V. Nekhayev. 1999.
+  ///
+  /// Since vertices and faces are removed by moving the last ones into their
+  /// places, indices of the last vertex and of the last faces may change.
+  pub fn collapse_edge(&mut self, e: usize, pos: Vector3)
+      -> Result<usize, MeshEditError> {
+    if !self.satisfies_link_condition(e) {
+      return Err(MeshEditError::LinkConditionViolated { edge: e });
+    }
+    let (a, b) = (self.edges[e].start_vertex, self.edges[e].end_vertex);
+    let (pa, pb) = (self.vertices[a].pos, self.vertices[b].pos);
+    let d = pb - pa;
+    let t = if d.len2() < constants::EPSILON_TINY {
+      0.0
+    } else {
+      ((pos - pa) * d / d.len2()).clamp(0.0, 1.0)
+    };
+    let mut merged = self.interpolated_vertex(&[(a, 1.0 - t), (b, t)]);
+    let merged_uv = self.corner_uv(self.edges[e].face, &[(a, 1.0 - t), (b, t)]);
+    merged.pos = pos;
+    merged.edges = self.vertices[a].edges.clone();
+    self.vertices[a] = merged;
+
+    let mut removed = vec![self.edges[e].face];
+    let mut opposite = vec![self.edges[self.edges[e].next_edge].end_vertex];
+    if let Some(twin) = self.edges[e].twin_edge {
+      removed.push(self.edges[twin].face);
+      opposite.push(self.edges[self.edges[twin].next_edge].end_vertex);
+    }
+    let mut changed: Vec<usize> = self.vertex_faces(b)
+        .filter(|f| !removed.contains(f))
+        .collect();
+    for f in changed.iter() {
+      let mut v = self.faces[*f].vertices;
+      for u in v.iter_mut() {
+        if *u == b {
+          *u = a;
+        }
+      }
+      let uvs = self.faces[*f].uvs;
+      self.set_face(*f, v, uvs);
+    }
+    // All corners of the merged vertex share the interpolated texture
+    // coordinates, which removes texture seams through it.
+    if let Some(uv) = merged_uv {
+      for x in self.vertices[a].edges.clone() {
+        let f = self.edges[x].face;
+        let i = self.faces[f].vertices.iter().position(|u| *u == a).unwrap();
+        if let Some(ref mut uvs) = self.faces[f].uvs {
+          uvs[i] = uv;
+        }
+      }
+    }
+    removed.sort();
+    for f in removed.iter().rev() {
+      if let Some(moved) = self.remove_face(*f) {
+        for g in changed.iter_mut() {
+          if *g == moved {
+            *g = *f;
+          }
+        }
+      }
+    }
+    let kept = if self.vertices.len() - 1 == a { b } else { a };
+    self.remove_vertex(b);
+    let mut affected: Vec<usize> = opposite.into_iter()
+        .map(|c| if c == self.vertices.len() { b } else { c })
+        .collect();
+    affected.push(kept);
+    self.finish_edit(&changed, &affected);
+    Ok(kept)
+  }
+
+  /// Checks the consistency of the DCEL, returning a description of the first
+  /// violation found. Meshes built by TriangularMeshBuilder and modified by
+  /// the edit operations always pass the check; it is meant for testing.
+  ///
+  /// - The half-edges of face #f are #3f, #3f+1 and #3f+2, going from its
+  ///   vertex #0 to #1, #1 to #2 and #2 to #0, linked by next and prev edges.
+  /// - Each vertex lists exactly its outgoing half-edges.
+  /// - Twins are mutual and of opposite directions, and half-edges are twins
+  ///   iff they are the only two half-edges of a spatial edge, of opposite
+  ///   directions.
+  pub fn check_invariants(&self) -> Result<(), String> {
+    if self.edges.len() != 3 * self.faces.len() {
+      return Err(format!("{} half-edges for {} faces", self.edges.len(),
+          self.faces.len()));
+    }
+    for (f, face) in self.faces.iter().enumerate() {
+      let v = face.vertices;
+      if v.iter().any(|u| *u >= self.vertices.len()) {
+        return Err(format!("Face #{} refers to a missing vertex", f));
+      }
+      if v[0] == v[1] || v[1] == v[2] || v[2] == v[0] {
+        return Err(format!("Face #{} has repeated vertices", f));
+      }
+      for i in 0..3 {
+        let id = 3 * f + i;
+        let e = &self.edges[id];
+        if face.edges[i] != id || e.face != f || e.start_vertex != v[i]
+            || e.end_vertex != v[(i + 1) % 3]
+            || e.next_edge != 3 * f + (i + 1) % 3
+            || e.prev_edge != 3 * f + (i + 2) % 3 {
+          return Err(format!("Half-edge #{} is inconsistent with face #{}",
+              id, f));
+        }
+      }
+    }
+    let mut listed = vec![0; self.edges.len()];
+    for (v, vertex) in self.vertices.iter().enumerate() {
+      for e in vertex.edges.iter() {
+        if *e >= self.edges.len() || self.edges[*e].start_vertex != v {
+          return Err(format!("Vertex #{} lists half-edge #{}, which does not \
+              start from it", v, e));
+        }
+        listed[*e] += 1;
+      }
+    }
+    if let Some(e) = listed.iter().position(|n| *n != 1) {
+      return Err(format!("Half-edge #{} is listed {} times by its start \
+          vertex", e, listed[e]));
+    }
+    let spatial_edges = self.spatial_edges();
+    for (id, e) in self.edges.iter().enumerate() {
+      let key = (e.start_vertex.min(e.end_vertex),
+          e.start_vertex.max(e.end_vertex));
+      let shared = &spatial_edges[&key];
+      let expected = if shared.len() == 2 {
+        let other = if shared[0] == id { shared[1] } else { shared[0] };
+        if self.edges[other].start_vertex == e.end_vertex {
+          Some(other)
+        } else {
+          None
+        }
+      } else {
+        None
+      };
+      if e.twin_edge != expected {
+        return Err(format!("Half-edge #{} has twin {:?}, expected {:?}", id,
+            e.twin_edge, expected));
+      }
+    }
+    Ok(())
+  }
+
+  // Returns the half-edge from one vertex to another, if any.
+  fn find_edge(&self, from: usize, to: usize) -> Option<usize> {
+    self.outgoing_edges(from).find(|e| self.edges[*e].end_vertex == to)
+  }
+
+  // Returns the vertices adjacent to a vertex, sorted.
+  fn adjacent_vertices(&self, v: usize) -> Vec<usize> {
+    let mut adjacent: Vec<usize> = self.vertex_neighbors(v).collect();
+    adjacent.sort();
+    adjacent
+  }
+
+  // Checks the link condition of collapsing an edge, see collapse_edge().
+  fn satisfies_link_condition(&self, e: usize) -> bool {
+    let (a, b) = (self.edges[e].start_vertex, self.edges[e].end_vertex);
+    let twin = self.edges[e].twin_edge;
+    // Other half-edges between the two vertices make the edge non-manifold.
+    let num_shared = self.outgoing_edges(a)
+        .chain(self.outgoing_edges(b))
+        .filter(|x| {
+          let x = &self.edges[*x];
+          (x.start_vertex == a && x.end_vertex == b)
+              || (x.start_vertex == b && x.end_vertex == a)
+        })
+        .count();
+    if num_shared != if twin.is_some() { 2 } else { 1 } {
+      return false;
+    }
+    // An interior edge between two boundary vertices would pinch the surface.
+    let on_boundary = |v: usize| self.outgoing_edges(v).any(|x| {
+      self.edges[x].twin_edge.is_none()
+          || self.edges[self.edges[x].prev_edge].twin_edge.is_none()
+    });
+    if twin.is_some() && on_boundary(a) && on_boundary(b) {
+      return false;
+    }
+
+    let mut opposite = vec![self.edges[self.edges[e].next_edge].end_vertex];
+    if let Some(t) = twin {
+      opposite.push(self.edges[self.edges[t].next_edge].end_vertex);
+    }
+    opposite.sort();
+    let adjacent_b = self.adjacent_vertices(b);
+    let common: Vec<usize> = self.adjacent_vertices(a).into_iter()
+        .filter(|u| adjacent_b.binary_search(u).is_ok())
+        .collect();
+    if common != opposite {
+      return false;
+    }
+    let link_edges = |v: usize, other: usize| -> Vec<(usize, usize)> {
+      self.outgoing_edges(v)
+          .map(|x| &self.edges[self.edges[x].next_edge])
+          .filter(|x| x.start_vertex != other && x.end_vertex != other)
+          .map(|x| (x.start_vertex.min(x.end_vertex),
+              x.start_vertex.max(x.end_vertex)))
+          .collect()
+    };
+    let link_b = link_edges(b, a);
+    link_edges(a, b).iter().all(|x| !link_b.contains(x))
+  }
+
+  // Returns a new vertex (without edges) whose position, texture coordinates
+  // and user-specified normal are weighted sums over given vertices.
+  fn interpolated_vertex(&self, weights: &[(usize, f64)]) -> Vertex {
+    let pos = weights.iter()
+        .fold(Vector3::zero(), |s, &(v, w)| s + self.vertices[v].pos * w);
+    let mut vertex = Vertex::new(pos);
+    let uvs: Option<Vec<Vector2>> = weights.iter()
+        .map(|&(v, _)| self.vertices[v].uv)
+        .collect();
+    if let Some(uvs) = uvs {
+      vertex.uv = Some(uvs.iter().zip(weights.iter())
+          .fold(Vector2::zero(), |s, (uv, &(_, w))| s + *uv * w));
+    }
+    if self.vertex_normal_mode == VertexNormalMode::USER_SPECIFIED {
+      let n = weights.iter().fold(Vector3::zero(), |s, &(v, w)| {
+        s + self.vertices[v].normal.unwrap_or(Vector3::zero()) * w
+      });
+      // Normals may cancel out, e.g. at the middle of a fold.
+      vertex.normal = if n.len2() > constants::EPSILON_TINY {
+        Some(n.normalize())
+      } else {
+        self.vertices[weights[0].0].normal
+      };
+    }
+    vertex
+  }
+
+  // Returns a weighted sum of texture coordinates at the corners of given
+  // vertices of a face, see corner_uvs(), or None if any is missing.
+  fn corner_uv(&self, f: usize, weights: &[(usize, f64)]) -> Option<Vector2> {
+    let uvs = self.corner_uvs(f)?;
+    let v = self.faces[f].vertices;
+    Some(weights.iter().fold(Vector2::zero(), |s, &(u, w)| {
+      s + uvs[v.iter().position(|x| *x == u).unwrap()] * w
+    }))
+  }
+
+  // Combines texture coordinates of three corners, or returns None if any is
+  // missing.
+  fn corners(uvs: [Option<Vector2>; 3]) -> Option<[Vector2; 3]> {
+    Some([uvs[0]?, uvs[1]?, uvs[2]?])
+  }
+
+  // Rewrites a face as a new triangle with optional corner texture
+  // coordinates, with its half-edges laid out as in
+  // TriangularMeshBuilder::build(). Twins of the old half-edges are cleared,
+  // see finish_edit().
+  fn set_face(&mut self, f: usize, v: [usize; 3],
+      uvs: Option<[Vector2; 3]>) {
+    for i in 0..3 {
+      self.unlink_edge(3 * f + i);
+    }
+    for i in 0..3 {
+      let e = 3 * f + i;
+      self.edges[e] = Edge {
+        start_vertex: v[i],
+        end_vertex: v[(i + 1) % 3],
+        face: f,
+        twin_edge: None,
+        next_edge: 3 * f + (i + 1) % 3,
+        prev_edge: 3 * f + (i + 2) % 3,
+      };
+      self.vertices[v[i]].edges.push(e);
+    }
+    self.faces[f].vertices = v;
+    self.faces[f].uvs = uvs;
+  }
+
+  // Appends a new face with an optional normal and corner texture
+  // coordinates, and returns its index.
+  fn push_face(&mut self, v: [usize; 3], normal: Option<Vector3>,
+      uvs: Option<[Vector2; 3]>) -> usize {
+    let f = self.faces.len();
+    let e = [3 * f, 3 * f + 1, 3 * f + 2];
+    let mut face = Face::new(v, e);
+    face.normal = normal;
+    face.uvs = uvs;
+    self.faces.push(face);
+    for i in 0..3 {
+      self.edges.push(Edge {
+        start_vertex: v[i],
+        end_vertex: v[(i + 1) % 3],
+        face: f,
+        twin_edge: None,
+        next_edge: e[(i + 1) % 3],
+        prev_edge: e[(i + 2) % 3],
+      });
+      self.vertices[v[i]].edges.push(e[i]);
+    }
+    f
+  }
+
+  // Removes a face by moving the last face into its place. Returns the old
+  // index of the moved face, if any.
+  fn remove_face(&mut self, f: usize) -> Option<usize> {
+    for i in 0..3 {
+      self.unlink_edge(3 * f + i);
+    }
+    let last = self.faces.len() - 1;
+    let moved = if f != last {
+      for i in 0..3 {
+        let (from, to) = (3 * last + i, 3 * f + i);
+        let mut edge = self.edges[from].clone();
+        edge.face = f;
+        edge.next_edge = 3 * f + (i + 1) % 3;
+        edge.prev_edge = 3 * f + (i + 2) % 3;
+        if let Some(t) = edge.twin_edge {
+          self.edges[t].twin_edge = Some(to);
+        }
+        for x in self.vertices[edge.start_vertex].edges.iter_mut() {
+          if *x == from {
+            *x = to;
+          }
+        }
+        self.edges[to] = edge;
+      }
+      self.faces[f] = self.faces[last].clone();
+      self.faces[f].edges = [3 * f, 3 * f + 1, 3 * f + 2];
+      Some(last)
+    } else {
+      None
+    };
+    self.faces.pop();
+    self.edges.truncate(3 * last);
+    moved
+  }
+
+  // Removes a vertex without edges by moving the last vertex into its place.
+  fn remove_vertex(&mut self, v: usize) {
+    assert!(self.vertices[v].edges.is_empty(), "Vertex must be unreferenced!");
+    let last = self.vertices.len() - 1;
+    if v != last {
+      for e in self.vertices[last].edges.clone() {
+        let prev = self.edges[e].prev_edge;
+        self.edges[e].start_vertex = v;
+        self.edges[prev].end_vertex = v;
+        let f = self.edges[e].face;
+        for u in self.faces[f].vertices.iter_mut() {
+          if *u == last {
+            *u = v;
+          }
+        }
+      }
+      self.vertices.swap(v, last);
+    }
+    self.vertices.pop();
+  }
+
+  // Detaches a half-edge from its start vertex and its twin.
+  fn unlink_edge(&mut self, e: usize) {
+    let start = self.edges[e].start_vertex;
+    self.vertices[start].edges.retain(|x| *x != e);
+    if let Some(t) = self.edges[e].twin_edge {
+      self.edges[t].twin_edge = None;
+    }
+    self.edges[e].twin_edge = None;
+  }
+
+  // Links a half-edge to its twin, if it is the only half-edge of its
+  // direction and there is exactly one of the opposite direction.
+  fn link_twin(&mut self, e: usize) {
+    let (a, b) = (self.edges[e].start_vertex, self.edges[e].end_vertex);
+    let same = self.vertices[a].edges.iter()
+        .filter(|x| self.edges[**x].end_vertex == b)
+        .count();
+    let opposite: Vec<usize> = self.vertices[b].edges.iter().cloned()
+        .filter(|x| self.edges[*x].end_vertex == a)
+        .collect();
+    if same == 1 && opposite.len() == 1
+        && self.edges[opposite[0]].twin_edge.is_none() {
+      self.edges[e].twin_edge = Some(opposite[0]);
+      self.edges[opposite[0]].twin_edge = Some(e);
+    }
+  }
+
+  // Relinks twins of the half-edges of changed faces, and refreshes normals
+  // of the faces, their vertices and other affected vertices.
+  fn finish_edit(&mut self, faces: &[usize], vertices: &[usize]) {
+    for f in faces.iter() {
+      for i in 0..3 {
+        if self.edges[3 * f + i].twin_edge.is_none() {
+          self.link_twin(3 * f + i);
+        }
+      }
+    }
+    let mut affected: Vec<usize> = faces.iter()
+        .flat_map(|f| self.faces[*f].vertices.to_vec())
+        .chain(vertices.iter().cloned())
+        .collect();
+    affected.sort();
+    affected.dedup();
+    self.refresh_normals(faces, &affected);
+  }
+}
@@ -0,0 +1,115 @@
+use common::constants;
+use geometry::triangular_mesh::{
+    TriangularMesh, FaceNormalMode, MeshPoint, TangentFrame,
+};
+use math::{Vector2, Vector3};
+
+impl TriangularMesh {
+  /// Returns the position of a point on the mesh.
+  pub fn position(&self, p: &MeshPoint) -> Vector3 {
+    let v = self.faces[p.face_id].vertices;
+    self.interpolate(p, |i| self.vertices[v[i]].pos)
+  }
+
+  /// Returns the geometric normal at a point on the mesh, i.e. the unit
+  /// normal of its face triangle, regardless of the normal modes. Returns
+  /// None if the vertices of the face are collinear.
+  pub fn geometric_normal(&self, p: &MeshPoint) -> Option<Vector3> {
+    self.triangle(p.face_id).try_normal()
+  }
+
+  /// Returns the shading normal at a point on the mesh, according to the
+  /// face normal mode of the mesh. In PHONG mode, the normals of the three
+  /// vertices are interpolated, see FaceNormalMode::PHONG, falling back to the
+  /// face normal if any of the vertex normals is missing. Returns None if no
+  /// normal is available, e.g. on a face with collinear vertices.
+  pub fn shading_normal(&self, p: &MeshPoint) -> Option<Vector3> {
+    let face = &self.faces[p.face_id];
+    if self.face_normal_mode != FaceNormalMode::PHONG {
+      return face.normal;
+    }
+    let v = face.vertices;
+    let normals: Option<Vec<Vector3>> =
+        v.iter().map(|u| self.vertices[*u].normal).collect();
+    match normals {
+      Some(n) => {
+        let interpolated = self.interpolate(p, |i| n[i]);
+        if interpolated.len2() < constants::EPSILON_TINY {
+          face.normal
+        } else {
+          Some(interpolated.normalize())
+        }
+      },
+      None => face.normal,
+    }
+  }
+
+  /// Returns the interpolated texture coordinates at a point on the mesh, or
+  /// None if its face has no texture coordinates, see corner_uvs().
+  pub fn uv(&self, p: &MeshPoint) -> Option<Vector2> {
+    let uvs = self.corner_uvs(p.face_id)?;
+    Some(uvs[0] * p.params[0] + uvs[1] * p.params[1] + uvs[2] * p.params[2])
+  }
+
+  /// Returns the unit tangent at a point on the mesh, i.e. the direction in
+  /// which the u texture coordinate increases, made orthogonal to the shading
+  /// normal. Tangents of the corner tangent frames are interpolated if
+  /// available (see recompute_tangent_frames()), otherwise dp/du of the face
+  /// is used. Returns None if the face has no (or degenerate) texture
+  /// coordinates.
+  pub fn tangent(&self, p: &MeshPoint) -> Option<Vector3> {
+    let t = match self.corner_tangent_frames(p.face_id) {
+      Some(frames) => self.interpolate(p, |i| frames[i].tangent()),
+      None => self.uv_derivatives(p.face_id)?.0,
+    };
+    match self.shading_normal(p) {
+      Some(n) => Self::orthonormalize(t, n),
+      None if t.len2() > constants::EPSILON_TINY => Some(t.normalize()),
+      None => None,
+    }
+  }
+
+  /// Returns the unit bitangent at a point on the mesh, i.e.
+  ///
+  /// > bitangent = sign * (normal ^ tangent),
+  ///
+  /// where the normal is the shading normal and the sign is the handedness of
+  /// the texture mapping, see TangentFrame. Returns None if the tangent or the
+  /// shading normal is not available.
+  pub fn bitangent(&self, p: &MeshPoint) -> Option<Vector3> {
+    let sign = match self.corner_tangent_frames(p.face_id) {
+      Some(frames) => frames[0].sign(),
+      None => self.uv_derivatives(p.face_id)?.2,
+    };
+    let n = self.shading_normal(p)?;
+    Some((n ^ self.tangent(p)?) * sign)
+  }
+
+  /// Converts a point on a face (e.g. a ray hit on the triangle of the face)
+  /// back to a MeshPoint. The point is projected onto the plane of the face,
+  /// and clamped into the face if it lies slightly outside due to numerical
+  /// errors. Returns None if the face is degenerate.
+  pub fn mesh_point_at(&self, face_id: usize, p: Vector3) -> Option<MeshPoint> {
+    let params = self.triangle(face_id).barycentric(p)?;
+    let mut clamped = Vector3::zero();
+    for i in 0..3 {
+      clamped[i] = params[i].max(0.0);
+    }
+    let sum = clamped[0] + clamped[1] + clamped[2];
+    Some(MeshPoint::new(face_id, clamped / sum))
+  }
+
+  // Interpolates a per-vertex value at a point on the mesh, given the value
+  // of the i-th vertex of the face.
+  fn interpolate<F>(&self, p: &MeshPoint, value: F) -> Vector3
+      where F: Fn(usize) -> Vector3 {
+    value(0) * p.params[0] + value(1) * p.params[1] + value(2) * p.params[2]
+  }
+
+  // Returns tangent frames of the three corners of a face, or None if any is
+  // missing.
+  fn corner_tangent_frames(&self, f: usize) -> Option<[TangentFrame; 3]> {
+    let frames = self.faces[f].tangent_frames;
+    Some([frames[0]?, frames[1]?, frames[2]?])
+  }
+}
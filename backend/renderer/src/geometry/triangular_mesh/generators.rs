@@ -0,0 +1,386 @@
+use common::constants;
+use geometry::triangular_mesh::{
+    FaceNormalMode, TriangularMesh, TriangularMeshBuilder, VertexNormalMode};
+use math::{Vector2, Vector3};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+// Vertices of an icosahedron inscribed in a sphere of radius sqrt(1 + t^2),
+// where t is the golden ratio, and its faces oriented outwards.
+const ICOSAHEDRON_VERTICES: [[f64; 3]; 12] = [
+    [-1.0, 1.618033988749895, 0.0], [1.0, 1.618033988749895, 0.0],
+    [-1.0, -1.618033988749895, 0.0], [1.0, -1.618033988749895, 0.0],
+    [0.0, -1.0, 1.618033988749895], [0.0, 1.0, 1.618033988749895],
+    [0.0, -1.0, -1.618033988749895], [0.0, 1.0, -1.618033988749895],
+    [1.618033988749895, 0.0, -1.0], [1.618033988749895, 0.0, 1.0],
+    [-1.618033988749895, 0.0, -1.0], [-1.618033988749895, 0.0, 1.0]];
+const ICOSAHEDRON_FACES: [[usize; 3]; 20] = [
+    [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+    [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+    [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+    [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1]];
+
+// Procedural meshes of common shapes, e.g. for quickly assembling scenes and
+// as fixtures of tests. Shapes are placed at a given center with the z axis
+// up, and faces are oriented outwards. Smooth shapes have exact vertex
+// normals (user-specified) interpolated across faces (Phong shading), while
+// flat parts have flat faces. Texture coordinates are in [0, 1]^2, and kept
+// continuous across seams by texture coordinates of faces, so that seams do
+// not split vertices.
+impl TriangularMesh {
+  /// Builds a UV sphere, i.e. a sphere tessellated along meridians (given
+  /// number of segments) and parallels (given number of rings between the
+  /// poles). Texture coordinates are u = longitude / (2 * pi) and v running
+  /// from 0 at the south pole to 1 at the north pole. The mesh is closed.
+  pub fn new_uv_sphere(center: Vector3, radius: f64, rings: usize,
+      segments: usize) -> TriangularMesh {
+    assert!(radius > 0.0, "Radius must be positive!");
+    assert!(rings >= 2 && segments >= 3,
+        "Sphere must have at least 2 rings and 3 segments!");
+    let mut builder = Self::smooth_builder();
+    let point = |theta: f64, phi: f64| Vector3::new(theta.sin() * phi.cos(),
+        theta.sin() * phi.sin(), theta.cos());
+    let uv = |i: usize, j: f64| Vector2::new(j / segments as f64,
+        1.0 - i as f64 / rings as f64);
+
+    let north = builder.add_vertex_with_normal(
+        center + Vector3::z_unit() * radius, Vector3::z_unit());
+    builder.set_vertex_uv(north, uv(0, 0.0));
+    for i in 1..rings {
+      let theta = PI * i as f64 / rings as f64;
+      for j in 0..segments {
+        let n = point(theta, 2.0 * PI * j as f64 / segments as f64);
+        let v = builder.add_vertex_with_normal(center + n * radius, n);
+        builder.set_vertex_uv(v, uv(i, j as f64));
+      }
+    }
+    let south = builder.add_vertex_with_normal(
+        center - Vector3::z_unit() * radius, -Vector3::z_unit());
+    builder.set_vertex_uv(south, uv(rings, 0.0));
+
+    let ring = |i: usize, j: usize| 1 + (i - 1) * segments + j % segments;
+    for j in 0..segments {
+      let (j0, j1, jc) = (j as f64, j as f64 + 1.0, j as f64 + 0.5);
+      let f = builder.add_face(north, ring(1, j), ring(1, j + 1));
+      builder.set_face_uvs(f, [uv(0, jc), uv(1, j0), uv(1, j1)]);
+      let f = builder.add_face(south, ring(rings - 1, j + 1),
+          ring(rings - 1, j));
+      builder.set_face_uvs(f,
+          [uv(rings, jc), uv(rings - 1, j1), uv(rings - 1, j0)]);
+      for i in 1..rings - 1 {
+        let f = builder.add_face(ring(i, j), ring(i + 1, j),
+            ring(i + 1, j + 1));
+        builder.set_face_uvs(f, [uv(i, j0), uv(i + 1, j0), uv(i + 1, j1)]);
+        let f = builder.add_face(ring(i, j), ring(i + 1, j + 1),
+            ring(i, j + 1));
+        builder.set_face_uvs(f, [uv(i, j0), uv(i + 1, j1), uv(i, j1)]);
+      }
+    }
+    builder.build().expect("Sphere must be a valid mesh!")
+  }
+
+  /// Builds an icosphere, i.e. an icosahedron whose faces are subdivided a
+  /// given number of times into four, with new vertices projected onto the
+  /// sphere. Faces are more uniform than those of a UV sphere, there are
+  /// 20 * 4^subdivisions of them. Texture coordinates are the same spherical
+  /// coordinates as those of new_uv_sphere(), except that faces across the
+  /// seam continue beyond u = 1, assuming a repeating texture. The mesh is
+  /// closed.
+  pub fn new_icosphere(center: Vector3, radius: f64, subdivisions: usize)
+      -> TriangularMesh {
+    assert!(radius > 0.0, "Radius must be positive!");
+    let mut normals: Vec<Vector3> = ICOSAHEDRON_VERTICES.iter()
+        .map(|p| Vector3::new(p[0], p[1], p[2]).normalize())
+        .collect();
+    let mut faces = ICOSAHEDRON_FACES.to_vec();
+    for _ in 0..subdivisions {
+      let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+      let mut midpoint = |a: usize, b: usize| {
+        let key = (a.min(b), a.max(b));
+        *midpoints.entry(key).or_insert_with(|| {
+          normals.push((normals[a] + normals[b]).normalize());
+          normals.len() - 1
+        })
+      };
+      faces = faces.iter()
+          .flat_map(|&[a, b, c]| {
+            let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+            vec![[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]
+          })
+          .collect();
+    }
+
+    let mut builder = Self::smooth_builder();
+    for n in normals.iter() {
+      let v = builder.add_vertex_with_normal(center + *n * radius, *n);
+      builder.set_vertex_uv(v, Self::spherical_uv(*n));
+    }
+    for vertices in faces.iter() {
+      let f = builder.add_face(vertices[0], vertices[1], vertices[2]);
+      let mut uvs = [Vector2::zero(); 3];
+      for (uv, v) in uvs.iter_mut().zip(vertices.iter()) {
+        *uv = Self::spherical_uv(normals[*v]);
+      }
+      // Longitudes are arbitrary at the poles, where the one of the face is
+      // taken instead.
+      let is_pole = |k: usize| {
+        let n = normals[vertices[k]];
+        n.x().abs() < constants::EPSILON_TINY
+            && n.y().abs() < constants::EPSILON_TINY
+      };
+      // Faces across the seam at u = 0 continue beyond u = 1.
+      let us: Vec<f64> = (0..3).filter(|k| !is_pole(*k))
+          .map(|k| uvs[k].u())
+          .collect();
+      let wraps = us.iter().cloned().fold(0.0, f64::max)
+          - us.iter().cloned().fold(1.0, f64::min) > 0.5;
+      let others: Vec<usize> = (0..3).filter(|k| !is_pole(*k)).collect();
+      for k in 0..3 {
+        let u = if is_pole(k) {
+          others.iter().map(|o| uvs[*o].u()).sum::<f64>() / others.len() as f64
+        } else {
+          uvs[k].u()
+        };
+        let u = if wraps && u < 0.5 { u + 1.0 } else { u };
+        uvs[k] = Vector2::new(u, uvs[k].v());
+      }
+      builder.set_face_uvs(f, uvs);
+    }
+    builder.build().expect("Icosphere must be a valid mesh!")
+  }
+
+  /// Builds an axis-aligned box of given size, with 8 vertices shared by its
+  /// flat faces. Each side is textured by the whole [0, 1]^2. The mesh is
+  /// closed.
+  pub fn new_box(center: Vector3, size: Vector3) -> TriangularMesh {
+    assert!(size.x() > 0.0 && size.y() > 0.0 && size.z() > 0.0,
+        "Size must be positive!");
+    let mut builder = TriangularMeshBuilder::new();
+    builder.set_face_normal_mode(FaceNormalMode::FLAT);
+    // Vertex k is at the corner with coordinates selected by its bits.
+    for k in 0..8 {
+      let corner = Vector3::new((k & 1) as f64, ((k >> 1) & 1) as f64,
+          ((k >> 2) & 1) as f64) - Vector3::new(0.5, 0.5, 0.5);
+      builder.add_vertex(center + Vector3::new(corner.x() * size.x(),
+          corner.y() * size.y(), corner.z() * size.z()));
+    }
+    // Sides, counter-clockwise when seen from outside.
+    let sides = [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3],
+        [0, 4, 6, 2], [1, 3, 7, 5]];
+    let uvs = [Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0),
+        Vector2::new(1.0, 1.0), Vector2::new(0.0, 1.0)];
+    for side in sides.iter() {
+      let f = builder.add_face(side[0], side[1], side[2]);
+      builder.set_face_uvs(f, [uvs[0], uvs[1], uvs[2]]);
+      let f = builder.add_face(side[0], side[2], side[3]);
+      builder.set_face_uvs(f, [uvs[0], uvs[2], uvs[3]]);
+    }
+    builder.build().expect("Box must be a valid mesh!")
+  }
+
+  /// Builds a rectangle in the xy plane facing +z, of given size along x and
+  /// y, subdivided into a grid of given numbers of segments. Texture
+  /// coordinates run from 0 to 1 along x and y.
+  pub fn new_plane(center: Vector3, size_x: f64, size_y: f64,
+      segments_x: usize, segments_y: usize) -> TriangularMesh {
+    assert!(size_x > 0.0 && size_y > 0.0, "Size must be positive!");
+    assert!(segments_x >= 1 && segments_y >= 1,
+        "Plane must have at least 1 segment along each axis!");
+    let mut builder = Self::smooth_builder();
+    Self::add_grid(&mut builder, segments_x, segments_y, false, false,
+        |u, v| {
+          (center + Vector3::new((u - 0.5) * size_x, (v - 0.5) * size_y, 0.0),
+              Some(Vector3::z_unit()))
+        });
+    builder.build().expect("Plane must be a valid mesh!")
+  }
+
+  /// Builds a cylinder around the z axis of given radius and height,
+  /// tessellated into a given number of segments around the axis and rings
+  /// along it, and closed by flat caps. The side is textured by u around the
+  /// axis and v along it, the caps by a disk inscribed in [0, 1]^2. The caps
+  /// have their own vertices on the rims, so that normals are sharp there,
+  /// and the mesh is not closed topologically.
+  pub fn new_cylinder(center: Vector3, radius: f64, height: f64,
+      segments: usize, rings: usize) -> TriangularMesh {
+    assert!(radius > 0.0 && height > 0.0, "Size must be positive!");
+    assert!(segments >= 3 && rings >= 1,
+        "Cylinder must have at least 3 segments and 1 ring!");
+    let mut builder = Self::smooth_builder();
+    Self::add_grid(&mut builder, segments, rings, true, false, |u, v| {
+      let phi = 2.0 * PI * u;
+      let n = Vector3::new(phi.cos(), phi.sin(), 0.0);
+      (center + n * radius + Vector3::z_unit() * ((v - 0.5) * height), Some(n))
+    });
+    Self::add_disk(&mut builder, center + Vector3::z_unit() * (height / 2.0),
+        radius, segments, true);
+    Self::add_disk(&mut builder, center - Vector3::z_unit() * (height / 2.0),
+        radius, segments, false);
+    builder.build().expect("Cylinder must be a valid mesh!")
+  }
+
+  /// Builds a cone around the z axis of given base radius and height, with a
+  /// given number of segments around the axis, and closed by a flat base.
+  /// The center is halfway between the base and the apex. The side is
+  /// textured by u around the axis and v along it, the base by a disk
+  /// inscribed in [0, 1]^2. The side faces share the apex vertex, whose
+  /// normal is the average of the side normals (i.e. the axis), while its
+  /// texture coordinates are given per face. The base has its own vertices
+  /// on the rim, so that normals are sharp there, and the mesh is not closed
+  /// topologically.
+  pub fn new_cone(center: Vector3, radius: f64, height: f64, segments: usize)
+      -> TriangularMesh {
+    assert!(radius > 0.0 && height > 0.0, "Size must be positive!");
+    assert!(segments >= 3, "Cone must have at least 3 segments!");
+    let mut builder = Self::smooth_builder();
+    let slant = (height * height + radius * radius).sqrt();
+    let normal = |u: f64| {
+      let phi = 2.0 * PI * u;
+      Vector3::new(phi.cos() * height, phi.sin() * height, radius)
+          * (1.0 / slant)
+    };
+    let apex = center + Vector3::z_unit() * (height / 2.0);
+    let base = center - Vector3::z_unit() * (height / 2.0);
+    for j in 0..segments {
+      let u = j as f64 / segments as f64;
+      let rim = Vector3::new((2.0 * PI * u).cos(), (2.0 * PI * u).sin(), 0.0);
+      let v = builder.add_vertex_with_normal(base + rim * radius, normal(u));
+      builder.set_vertex_uv(v, Vector2::new(u, 0.0));
+    }
+    let top = builder.add_vertex_with_normal(apex, Vector3::z_unit());
+    builder.set_vertex_uv(top, Vector2::new(0.5, 1.0));
+    for j in 0..segments {
+      let (u0, u1) = (j as f64 / segments as f64,
+          (j + 1) as f64 / segments as f64);
+      let um = (u0 + u1) / 2.0;
+      let f = builder.add_face(j, (j + 1) % segments, top);
+      builder.set_face_uvs(f, [Vector2::new(u0, 0.0), Vector2::new(u1, 0.0),
+          Vector2::new(um, 1.0)]);
+    }
+    Self::add_disk(&mut builder, base, radius, segments, false);
+    builder.build().expect("Cone must be a valid mesh!")
+  }
+
+  /// Builds a torus around the z axis, i.e. a tube of a given minor radius
+  /// around a circle of a given major radius, tessellated into given numbers
+  /// of segments around the axis (u) and around the tube (v). Texture
+  /// coordinates start at the outer equator. The mesh is closed.
+  pub fn new_torus(center: Vector3, major_radius: f64, minor_radius: f64,
+      major_segments: usize, minor_segments: usize) -> TriangularMesh {
+    assert!(minor_radius > 0.0 && major_radius > minor_radius,
+        "Major radius must be greater than minor radius!");
+    assert!(major_segments >= 3 && minor_segments >= 3,
+        "Torus must have at least 3 segments in each direction!");
+    let mut builder = Self::smooth_builder();
+    Self::add_grid(&mut builder, major_segments, minor_segments, true, true,
+        |u, v| {
+          let (phi, theta) = (2.0 * PI * u, 2.0 * PI * v);
+          let axis = Vector3::new(phi.cos(), phi.sin(), 0.0);
+          let n = axis * theta.cos() + Vector3::z_unit() * theta.sin();
+          (center + axis * major_radius + n * minor_radius, Some(n))
+        });
+    builder.build().expect("Torus must be a valid mesh!")
+  }
+
+  /// Builds a parametric surface p(u, v) with (u, v) in [0, 1]^2 given by a
+  /// function, sampled on a grid of given numbers of segments, and textured
+  /// by (u, v). Faces are oriented along dp/du ^ dp/dv. A surface closed in
+  /// u, i.e. p(0, v) = p(1, v), shares the vertices at u = 0 and u = 1, and
+  /// likewise in v. Vertex normals are averaged from faces, see
+  /// VertexNormalMode::AVERAGED. Parametrizations collapsing a whole side to
+  /// a point (e.g. spherical coordinates) create degenerate faces there.
+  pub fn new_parametric<F>(surface: F, segments_u: usize, segments_v: usize,
+      closed_u: bool, closed_v: bool) -> TriangularMesh
+      where F: Fn(f64, f64) -> Vector3 {
+    assert!(segments_u >= 1 && segments_v >= 1,
+        "Surface must have at least 1 segment in each direction!");
+    assert!((!closed_u || segments_u >= 3) && (!closed_v || segments_v >= 3),
+        "Closed surface must have at least 3 segments around!");
+    let mut builder = TriangularMeshBuilder::new();
+    builder.set_vertex_normal_mode(VertexNormalMode::AVERAGED);
+    builder.set_face_normal_mode(FaceNormalMode::PHONG);
+    Self::add_grid(&mut builder, segments_u, segments_v, closed_u, closed_v,
+        |u, v| (surface(u, v), None));
+    builder.build().expect("Parametric surface must be a valid mesh!")
+  }
+
+  // Returns a builder of a smooth shape with exact normals.
+  fn smooth_builder() -> TriangularMeshBuilder {
+    let mut builder = TriangularMeshBuilder::new();
+    builder.set_vertex_normal_mode(VertexNormalMode::USER_SPECIFIED);
+    builder.set_face_normal_mode(FaceNormalMode::PHONG);
+    builder
+  }
+
+  // Adds a grid of vertices sampled at (u, v) = (i / nu, j / nv), with
+  // positions and optional normals given by a function, and two faces per
+  // cell oriented along dp/du ^ dp/dv. In a closed direction the last samples
+  // are the first ones, while texture coordinates of faces run up to 1.
+  fn add_grid<F>(builder: &mut TriangularMeshBuilder, nu: usize, nv: usize,
+      closed_u: bool, closed_v: bool, sample: F)
+      where F: Fn(f64, f64) -> (Vector3, Option<Vector3>) {
+    let cols = if closed_u { nu } else { nu + 1 };
+    let rows = if closed_v { nv } else { nv + 1 };
+    let uv = |i: usize, j: usize| Vector2::new(i as f64 / nu as f64,
+        j as f64 / nv as f64);
+    let mut ids = Vec::with_capacity(cols * rows);
+    for j in 0..rows {
+      for i in 0..cols {
+        let uv = uv(i, j);
+        let v = match sample(uv.u(), uv.v()) {
+          (p, Some(n)) => builder.add_vertex_with_normal(p, n),
+          (p, None) => builder.add_vertex(p),
+        };
+        builder.set_vertex_uv(v, uv);
+        ids.push(v);
+      }
+    }
+    let id = |i: usize, j: usize| ids[(j % rows) * cols + i % cols];
+    for j in 0..nv {
+      for i in 0..nu {
+        let f = builder.add_face(id(i, j), id(i + 1, j), id(i + 1, j + 1));
+        builder.set_face_uvs(f, [uv(i, j), uv(i + 1, j), uv(i + 1, j + 1)]);
+        let f = builder.add_face(id(i, j), id(i + 1, j + 1), id(i, j + 1));
+        builder.set_face_uvs(f, [uv(i, j), uv(i + 1, j + 1), uv(i, j + 1)]);
+      }
+    }
+  }
+
+  // Adds a flat disk in the xy plane, facing +z (or -z), as a fan of faces
+  // around its center, textured by the disk inscribed in [0, 1]^2 as seen
+  // from the front.
+  fn add_disk(builder: &mut TriangularMeshBuilder, center: Vector3,
+      radius: f64, segments: usize, up: bool) {
+    let (normal, sign) = if up {
+      (Vector3::z_unit(), 1.0)
+    } else {
+      (-Vector3::z_unit(), -1.0)
+    };
+    let middle = builder.add_vertex_with_normal(center, normal);
+    builder.set_vertex_uv(middle, Vector2::new(0.5, 0.5));
+    for j in 0..segments {
+      let phi = 2.0 * PI * j as f64 / segments as f64;
+      let v = builder.add_vertex_with_normal(
+          center + Vector3::new(phi.cos(), phi.sin(), 0.0) * radius, normal);
+      builder.set_vertex_uv(v, Vector2::new(0.5 + 0.5 * phi.cos(),
+          0.5 + 0.5 * sign * phi.sin()));
+    }
+    let rim = |j: usize| middle + 1 + j % segments;
+    for j in 0..segments {
+      if up {
+        builder.add_face(middle, rim(j), rim(j + 1));
+      } else {
+        builder.add_face(middle, rim(j + 1), rim(j));
+      }
+    }
+  }
+
+  // Returns spherical texture coordinates of a unit vector: the longitude
+  // over 2 * pi in [0, 1), and the latitude mapped from [-pi/2, pi/2] to
+  // [0, 1].
+  fn spherical_uv(n: Vector3) -> Vector2 {
+    let u = n.y().atan2(n.x()) / (2.0 * PI);
+    Vector2::new(if u < 0.0 { u + 1.0 } else { u },
+        0.5 + n.z().clamp(-1.0, 1.0).asin() / PI)
+  }
+}
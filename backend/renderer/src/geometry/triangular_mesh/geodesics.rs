@@ -0,0 +1,241 @@
+use common::constants;
+use common::disjoint_set::DisjointSet;
+use geometry::triangular_mesh::{TriangularMesh, MeshPoint};
+use math::{SparseMatrix, Vector3};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+// The relative residual of linear solves in the heat method.
+const SOLVER_TOLERANCE: f64 = 1e-10;
+
+/// A source of geodesic distances, either a vertex or any point on the mesh.
+#[derive(Debug, Copy, Clone)]
+pub enum GeodesicSource {
+  VERTEX(usize),
+  POINT(MeshPoint),
+}
+
+impl TriangularMesh {
+  /// Approximates geodesic distances from the nearest of given sources to
+  /// each vertex, by shortest paths along edges (Dijkstra's algorithm). A
+  /// point source is connected to the vertices of its face by straight
+  /// lines. Paths are restricted to edges, thus distances are overestimated,
+  /// depending on the tessellation (up to 8% on regular grids). Vertices not
+  /// connected to any source get constants::INFINITY.
+  pub fn geodesic_distances_dijkstra(&self, sources: &[GeodesicSource])
+      -> Vec<f64> {
+    let mut distances = vec![constants::INFINITY; self.vertices.len()];
+    let mut heap = BinaryHeap::new();
+    for (v, d) in self.source_distances(sources) {
+      if d < distances[v] {
+        distances[v] = d;
+        heap.push(Visit { distance: d, vertex: v });
+      }
+    }
+    while let Some(Visit { distance, vertex }) = heap.pop() {
+      if distance > distances[vertex] {
+        continue;
+      }
+      let p = self.vertices[vertex].pos;
+      for u in self.vertex_neighbors(vertex) {
+        let d = distance + (self.vertices[u].pos - p).len();
+        if d < distances[u] {
+          distances[u] = d;
+          heap.push(Visit { distance: d, vertex: u });
+        }
+      }
+    }
+    distances
+  }
+
+  /// Computes geodesic distances from the nearest of given sources to each
+  /// vertex with the heat method, which is much closer to the exact (smooth)
+  /// distances than shortest paths along edges:
+  ///
+  /// 1. Heat is diffused from the sources for a short time t = h^2, where h
+  ///    is the mean edge length, by solving (M + t * L) u = u0 (one backward
+  ///    Euler step), see cotan_laplacian() and vertex_areas(). Heat vanishes
+  ///    (underflows) hundreds of edge lengths from the sources, beyond which
+  ///    distances are unreliable.
+  /// 2. The normalized negated gradient X = -grad(u) / |grad(u)| on each
+  ///    face is the direction in which distances increase.
+  /// 3. Distances are recovered by solving the Poisson equation L * phi =
+  ///    -div(X), then shifted so that the nearest source is at 0.
+  ///
+  /// For details please read:
+  ///
+  /// > Geodesics in Heat: A New Approach to Computing Distance Based on Heat
+  /// > Flow. K. Crane, C. Weischedel, M. Wardetzky. 2013.
+  ///
+  /// Vertices not connected to any source get constants::INFINITY. Returns
+  /// None if a linear solve fails, e.g. on badly degenerate meshes.
+  pub fn geodesic_distances_heat(&self, sources: &[GeodesicSource])
+      -> Option<Vec<f64>> {
+    let n = self.vertices.len();
+    let (labels, num_components) = self.vertex_components();
+    let mut has_source = vec![false; num_components];
+    let mut u0 = vec![0.0; n];
+    for s in sources.iter() {
+      for &(v, w) in self.source_weights(s).iter() {
+        u0[v] += w;
+        has_source[labels[v]] = true;
+      }
+    }
+    let max_iterations = 10 * n + 100;
+
+    // 1. Heat diffusion.
+    let laplacian = self.cotan_laplacian();
+    let num_lengths = self.edges.len().max(1) as f64;
+    let h = self.edges.iter()
+        .map(|e| (self.vertices[e.end_vertex].pos
+            - self.vertices[e.start_vertex].pos).len())
+        .sum::<f64>() / num_lengths;
+    // Vertices without area (e.g. isolated ones) keep their initial heat.
+    let masses: Vec<f64> = self.vertex_areas().into_iter()
+        .map(|a| if a > 0.0 { a } else { 1.0 })
+        .collect();
+    let heat = SparseMatrix::diag(&masses)
+        .weighted_sum(1.0, &laplacian, h * h);
+    // Heat decays exponentially with the distance, so it is solved directly
+    // to keep the directions of its gradient far away from the sources.
+    let u = heat.solve_cholesky(&u0)?;
+
+    // 2. and 3. Divergence of the normalized gradient.
+    let mut divergence = vec![0.0; n];
+    for (f, face) in self.faces.iter().enumerate() {
+      let t = self.triangle(f);
+      let normal = match t.try_normal() {
+        Some(n) => n,
+        None => continue,
+      };
+      // The gradient of a linear function on the face, where the edge
+      // opposite to corner i is e(i + 1), times twice the area of the face.
+      // Heat is rescaled to the face, as only the direction matters.
+      let scale = face.vertices.iter().map(|v| u[*v]).fold(0.0, f64::max);
+      if scale <= 0.0 {
+        continue;
+      }
+      let grad = (0..3).fold(Vector3::zero(), |g, i| {
+        g + (normal ^ t.e((i + 1) % 3)) * (u[face.vertices[i]] / scale)
+      });
+      // Normalized by hand, see Triangle3::try_normal(). Gradients within
+      // rounding errors of the edges have no direction.
+      let len = grad.len();
+      if len <= f64::EPSILON * t.max_edge_len() {
+        continue;
+      }
+      let x = grad * (-1.0 / len);
+      let cot = self.face_cotangents(f);
+      for i in 0..3 {
+        let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+        // Edges from corner i to corners j and k, opposite to angles at k
+        // and j respectively.
+        divergence[face.vertices[i]] += 0.5 * (((t.v(j) - t.v(i)) * x) * cot[k]
+            + ((t.v(k) - t.v(i)) * x) * cot[j]);
+      }
+    }
+
+    // Each component is solved up to a constant, thus the right-hand side is
+    // projected to zero mean (which it is, up to rounding errors).
+    let mut sums = vec![(0.0, 0); num_components];
+    for v in 0..n {
+      sums[labels[v]].0 += divergence[v];
+      sums[labels[v]].1 += 1;
+    }
+    let b: Vec<f64> = (0..n)
+        .map(|v| {
+          let (sum, count) = sums[labels[v]];
+          sum / count as f64 - divergence[v]
+        })
+        .collect();
+    let mut phi = laplacian.solve_conjugate_gradient(&b, SOLVER_TOLERANCE,
+        max_iterations)?;
+
+    let mut shifts = vec![constants::INFINITY; num_components];
+    for s in sources.iter() {
+      let weights = self.source_weights(s);
+      let value = weights.iter().map(|&(v, w)| phi[v] * w).sum::<f64>();
+      let c = labels[weights[0].0];
+      shifts[c] = shifts[c].min(value);
+    }
+    for v in 0..n {
+      phi[v] = if has_source[labels[v]] {
+        (phi[v] - shifts[labels[v]]).max(0.0)
+      } else {
+        constants::INFINITY
+      };
+    }
+    Some(phi)
+  }
+
+  // Returns the vertices of a source with their interpolation weights.
+  fn source_weights(&self, source: &GeodesicSource) -> Vec<(usize, f64)> {
+    match *source {
+      GeodesicSource::VERTEX(v) => vec![(v, 1.0)],
+      GeodesicSource::POINT(ref p) => {
+        let v = self.faces[p.face_id].vertices;
+        (0..3).map(|i| (v[i], p.params[i])).collect()
+      },
+    }
+  }
+
+  // Returns initial distances from sources to vertices, i.e. 0 for vertex
+  // sources, and straight-line distances to the vertices of the face of a
+  // point source.
+  fn source_distances(&self, sources: &[GeodesicSource]) -> Vec<(usize, f64)> {
+    let mut distances = Vec::new();
+    for s in sources.iter() {
+      match *s {
+        GeodesicSource::VERTEX(v) => distances.push((v, 0.0)),
+        GeodesicSource::POINT(ref p) => {
+          let pos = self.position(p);
+          for v in self.faces[p.face_id].vertices.iter() {
+            distances.push((*v, (self.vertices[*v].pos - pos).len()));
+          }
+        },
+      }
+    }
+    distances
+  }
+
+  // Labels vertices by connected components through faces, with isolated
+  // vertices in their own components. Returns the labels and the number of
+  // components.
+  fn vertex_components(&self) -> (Vec<usize>, usize) {
+    let mut components = DisjointSet::new(self.vertices.len());
+    for face in self.faces.iter() {
+      components.union(face.vertices[0], face.vertices[1]);
+      components.union(face.vertices[1], face.vertices[2]);
+    }
+    components.labels()
+  }
+}
+
+// A vertex reached by Dijkstra's algorithm, ordered by its distance, the
+// smallest first.
+#[derive(Debug, Copy, Clone)]
+struct Visit {
+  distance: f64,
+  vertex: usize,
+}
+
+impl PartialEq for Visit {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Visit {
+  fn cmp(&self, other: &Self) -> Ordering {
+    // Reversed so that BinaryHeap pops the smallest distance first.
+    other.distance.partial_cmp(&self.distance).unwrap_or(Ordering::Equal)
+  }
+}
@@ -0,0 +1,123 @@
+use common::constants;
+use geometry::{
+    BoundingBox3, BoundingSphere, HasBoundingBox3, HasBoundingSphere,
+    HasSurfaceArea, HasVolume,
+};
+use geometry::triangular_mesh::TriangularMesh;
+use math::{Matrix3, Vector3};
+
+impl TriangularMesh {
+  /// Returns the centroid of the surface, i.e. the average of centroids of
+  /// faces weighted by their areas, or None if the total area is zero.
+  pub fn surface_centroid(&self) -> Option<Vector3> {
+    let mut area = 0.0;
+    let mut sum = Vector3::zero();
+    for f in 0..self.faces.len() {
+      let t = self.triangle(f);
+      let a = t.surface_area();
+      area += a;
+      sum += t.centroid() * a;
+    }
+    if area == 0.0 {
+      return None;
+    }
+    Some(sum * (1.0 / area))
+  }
+
+  /// Returns the centroid of the solid enclosed by the mesh, assuming uniform
+  /// density, or None if the volume is zero up to rounding errors, i.e.
+  /// negligible compared with the volumes of the tetrahedra summed up. See
+  /// volume() for requirements on the mesh.
+  pub fn volume_centroid(&self) -> Option<Vector3> {
+    let mut volume = 0.0;
+    let mut magnitude = 0.0;
+    let mut sum = Vector3::zero();
+    for f in 0..self.faces.len() {
+      let t = self.triangle(f);
+      // The tetrahedron formed by the origin and the face, whose centroid is
+      // a quarter of the sum of the face vertices.
+      let v = Self::signed_tetrahedron_volume(t.v(0), t.v(1), t.v(2));
+      volume += v;
+      magnitude += v.abs();
+      sum += (t.v(0) + t.v(1) + t.v(2)) * (v / 4.0);
+    }
+    if volume.abs() <= constants::EPSILON * magnitude {
+      return None;
+    }
+    Some(sum * (1.0 / volume))
+  }
+
+  /// Returns the inertia tensor of the solid enclosed by the mesh about its
+  /// volume centroid, assuming unit density (multiply by the density for
+  /// other materials), or None if the volume is zero. See volume() for
+  /// requirements on the mesh; an inward oriented mesh negates the tensor.
+  ///
+  /// The covariance matrix of the solid is summed over the tetrahedra formed
+  /// by the origin and each face, then translated to the centroid, and the
+  /// inertia tensor is I = trace(C) * E - C. For details please read:
+  ///
+  /// > Fast and Accurate Computation of Polyhedral Mass Properties.
+  /// > B. Mirtich. Journal of Graphics Tools, 1996.
+  pub fn inertia_tensor(&self) -> Option<Matrix3> {
+    let mut volume = 0.0;
+    let mut covariance = Matrix3::zero();
+    for f in 0..self.faces.len() {
+      let t = self.triangle(f);
+      let (a, b, c) = (t.v(0), t.v(1), t.v(2));
+      let v = Self::signed_tetrahedron_volume(a, b, c);
+      // Integral of p * p^T over the tetrahedron (0, a, b, c).
+      let s = a + b + c;
+      covariance += (Matrix3::outer(s, s) + Matrix3::outer(a, a)
+          + Matrix3::outer(b, b) + Matrix3::outer(c, c)) * (v / 20.0);
+      volume += v;
+    }
+    let centroid = self.volume_centroid()?;
+    let covariance = covariance - Matrix3::outer(centroid, centroid) * volume;
+    Some(Matrix3::identity() * covariance.trace() - covariance)
+  }
+
+  // Returns the signed volume of the tetrahedron formed by the origin and
+  // three points, positive if the points are counter-clockwise seen from
+  // outside, i.e. from the side opposite to the origin.
+  fn signed_tetrahedron_volume(a: Vector3, b: Vector3, c: Vector3) -> f64 {
+    a * (b ^ c) / 6.0
+  }
+}
+
+impl HasSurfaceArea for TriangularMesh {
+  fn surface_area(&self) -> f64 {
+    (0..self.faces.len()).map(|f| self.triangle(f).surface_area()).sum()
+  }
+}
+
+/// The signed volume enclosed by the mesh, computed by the divergence theorem
+/// as the sum of signed volumes of tetrahedra formed by the origin and each
+/// face. It is only meaningful for closed meshes with consistent orientations
+/// (see TriangularMesh::validate() and TriangularMesh::orient_faces()), and is
+/// negative if faces are oriented inwards.
+impl HasVolume for TriangularMesh {
+  fn volume(&self) -> f64 {
+    (0..self.faces.len())
+        .map(|f| {
+          let t = self.triangle(f);
+          Self::signed_tetrahedron_volume(t.v(0), t.v(1), t.v(2))
+        })
+        .sum()
+  }
+}
+
+/// The bounding box of all vertices. The mesh must not be empty.
+impl HasBoundingBox3 for TriangularMesh {
+  fn bounding_box3(&self) -> BoundingBox3 {
+    let points: Vec<Vector3> = self.vertices.iter().map(|v| v.pos).collect();
+    BoundingBox3::new_from_points(&points)
+  }
+}
+
+/// The minimal bounding sphere of all vertices. The mesh must not be empty.
+impl HasBoundingSphere for TriangularMesh {
+  fn bounding_sphere(&self) -> BoundingSphere {
+    let points: Vec<Vector3> = self.vertices.iter().map(|v| v.pos).collect();
+    BoundingSphere::new_welzl(&points)
+  }
+}
@@ -0,0 +1,67 @@
+use geometry::HasSurfaceArea;
+use geometry::triangular_mesh::TriangularMesh;
+use math::SparseMatrix;
+
+impl TriangularMesh {
+  /// Returns the cotangent Laplacian of the mesh, as a symmetric positive
+  /// semi-definite matrix L (i.e. the negated Laplace-Beltrami operator
+  /// integrated over vertex areas), where for vertices i != j sharing an edge:
+  ///
+  /// > L_ij = -(cot a_ij + cot b_ij) / 2,
+  ///
+  /// a_ij and b_ij are the angles opposite to the edge in its (one or two)
+  /// faces, and L_ii = -sum_j L_ij. Constant functions are in its null space.
+  /// Degenerate faces contribute nothing. For details please read:
+  ///
+  /// > Discrete Differential-Geometry Operators for Triangulated
+  /// > 2-Manifolds. M. Meyer, M. Desbrun, P. Schröder, A. H. Barr. 2003.
+  pub fn cotan_laplacian(&self) -> SparseMatrix {
+    let mut triplets = Vec::with_capacity(self.faces.len() * 12);
+    for (f, face) in self.faces.iter().enumerate() {
+      let cot = self.face_cotangents(f);
+      for (k, c) in cot.iter().enumerate() {
+        // The edge opposite to corner k.
+        let (i, j) = (face.vertices[(k + 1) % 3], face.vertices[(k + 2) % 3]);
+        let w = c / 2.0;
+        triplets.push((i, j, -w));
+        triplets.push((j, i, -w));
+        triplets.push((i, i, w));
+        triplets.push((j, j, w));
+      }
+    }
+    let n = self.vertices.len();
+    SparseMatrix::new_from_triplets(n, n, &triplets)
+  }
+
+  /// Returns the area of each vertex, i.e. one third of the total area of its
+  /// adjacent faces, which is the diagonal of the lumped mass matrix and sums
+  /// up to the surface area.
+  pub fn vertex_areas(&self) -> Vec<f64> {
+    let mut areas = vec![0.0; self.vertices.len()];
+    for (f, face) in self.faces.iter().enumerate() {
+      let a = self.triangle(f).surface_area() / 3.0;
+      for v in face.vertices.iter() {
+        areas[*v] += a;
+      }
+    }
+    areas
+  }
+
+  // Returns cotangents of the three angles of a face, or zeros if its
+  // vertices are collinear, see Triangle3::try_normal().
+  pub(super) fn face_cotangents(&self, f: usize) -> [f64; 3] {
+    let t = self.triangle(f);
+    let double_area = (t.e(0) ^ t.e(1)).len();
+    if double_area <= f64::EPSILON * t.edge_len(0) * t.edge_len(1) {
+      return [0.0; 3];
+    }
+    let mut cot = [0.0; 3];
+    for (k, c) in cot.iter_mut().enumerate() {
+      // The edges leaving corner k.
+      let a = t.e(k);
+      let b = -t.e((k + 2) % 3);
+      *c = a * b / double_area;
+    }
+    cot
+  }
+}
@@ -0,0 +1,497 @@
+pub use self::builder::{TriangularMeshBuilder, MeshBuildError};
+pub use self::bvh::MeshBvh;
+pub use self::curvature::VertexCurvature;
+pub use self::editing::MeshEditError;
+pub use self::geodesics::GeodesicSource;
+pub use self::traversal::{
+    OutgoingEdges, VertexFaces, VertexNeighbors, FaceEdges, FaceNeighbors,
+    BoundaryLoops,
+};
+pub use self::repair::RepairReport;
+pub use self::sampling::SurfaceSampler;
+pub use self::simplification::{SimplificationOptions, SimplificationReport};
+pub use self::slicing::SectionPolyline;
+pub use self::smoothing::{SmoothingOptions, SmoothingWeighting};
+pub use self::subdivision::SubdivisionOptions;
+pub use self::tangents::TangentFrame;
+pub use self::validation::ValidationReport;
+
+use math::{Vector2, Vector3};
+use geometry::Triangle3;
+use common::constants;
+use common::disjoint_set::DisjointSet;
+use common::utils;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+mod builder;
+mod bvh;
+mod convex_hull;
+mod curvature;
+mod editing;
+mod evaluation;
+mod generators;
+mod geodesics;
+mod integrals;
+mod laplacian;
+mod normals;
+mod repair;
+mod sampling;
+mod simplification;
+mod slicing;
+mod smoothing;
+mod subdivision;
+mod tangents;
+mod traversal;
+mod validation;
+mod voxelization;
+
+#[cfg(test)]
+mod test;
+
+/// Definitions and operations for a triangular mesh. The mesh is maintained as
+/// a **Doubly-Connected Edge List (DCEL)**:
+///
+/// - Each vertex has its own position and (optionaly) a normal, with a list of
+/// outgoing half-edges. 
+/// - A face (triangle) contains pointers to its three vertices and three
+/// belonging half-edges (going counter-clockwise), optionally a normal vector.
+/// - A half-edge and its twin edge (coincide spatially but of opposite
+/// directions) define a spatial edge. Each half-edge points from one vertex to
+/// another (one-diretional), and belongs to exactly one face (while its twin
+/// edge belongs to the adjacent face).
+///
+/// For more details please read:
+/// 
+/// > Computational Geometry: Algorithms and Applications (second edition),
+/// Chapter 2.2.
+/// > M. de Berg, M. van Kreveld, M. Overmars, O. Schwarzkopf.
+///
+/// DCELs require population and calculation of derived data upon creation.
+/// The constructors are private and therefore, you must and you can only build
+/// a triangular mesh via TriangularMeshBuilder.
+#[derive(Debug, Clone)]
+pub struct TriangularMesh {
+  vertices: Vec<Vertex>,
+  edges: Vec<Edge>,
+  faces: Vec<Face>,
+  vertex_normal_mode: VertexNormalMode,
+  face_normal_mode: FaceNormalMode,
+  normal_weighting: NormalWeighting,
+}
+
+impl TriangularMesh {
+  pub fn num_vertices(&self) -> usize {
+    self.vertices.len()
+  }
+
+  /// Returns the number of half-edges, which is always 3 times the number of
+  /// faces.
+  pub fn num_edges(&self) -> usize {
+    self.edges.len()
+  }
+
+  pub fn num_faces(&self) -> usize {
+    self.faces.len()
+  }
+
+  /// Returns a vertex with its attributes. Its adjacency is queried with the
+  /// traversal iterators, e.g. outgoing_edges() or vertex_neighbors().
+  pub fn vertex(&self, id: usize) -> &Vertex {
+    &self.vertices[id]
+  }
+
+  pub fn edge(&self, id: usize) -> &Edge {
+    &self.edges[id]
+  }
+
+  /// Returns a face with its attributes. Its adjacency is queried with the
+  /// traversal iterators, e.g. face_edges() or face_neighbors().
+  pub fn face(&self, id: usize) -> &Face {
+    &self.faces[id]
+  }
+
+  pub fn vertex_normal_mode(&self) -> VertexNormalMode {
+    self.vertex_normal_mode
+  }
+
+  pub fn face_normal_mode(&self) -> FaceNormalMode {
+    self.face_normal_mode
+  }
+
+  pub fn normal_weighting(&self) -> NormalWeighting {
+    self.normal_weighting
+  }
+
+  /// Returns the geometry of a face as a triangle, with vertices in the same
+  /// (counter-clockwise) order as the face.
+  pub fn triangle(&self, face_id: usize) -> Triangle3 {
+    let v = self.faces[face_id].vertices;
+    Triangle3::new(self.vertices[v[0]].pos, self.vertices[v[1]].pos,
+        self.vertices[v[2]].pos)
+  }
+
+  // Groups half-edges by the spatial edges they lie on, keyed by the indices
+  // of the two vertices, the smaller one first.
+  fn spatial_edges(&self) -> HashMap<(usize, usize), Vec<usize>> {
+    let mut spatial_edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (id, e) in self.edges.iter().enumerate() {
+      let key = (e.start_vertex.min(e.end_vertex),
+          e.start_vertex.max(e.end_vertex));
+      spatial_edges.entry(key).or_default().push(id);
+    }
+    spatial_edges
+  }
+
+  // Groups vertices closer than a tolerance, transitively. Returns the
+  // cluster index of each vertex, with clusters numbered in the order of their
+  // first vertices, and the number of clusters.
+  fn cluster_vertices(&self, tolerance: f64) -> (Vec<usize>, usize) {
+    assert!(tolerance >= 0.0, "Tolerance must be non-negative!");
+    // Vertices within the tolerance are in the same or adjacent cells.
+    let cell_size = tolerance.max(constants::EPSILON);
+    let cell_of = |p: Vector3| [(p.x() / cell_size).floor() as i64,
+        (p.y() / cell_size).floor() as i64, (p.z() / cell_size).floor() as i64];
+    let mut cells: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    let mut clusters = DisjointSet::new(self.vertices.len());
+    for v in 0..self.vertices.len() {
+      let p = self.vertices[v].pos;
+      let c = cell_of(p);
+      for dx in -1..2 {
+        for dy in -1..2 {
+          for dz in -1..2 {
+            if let Some(others) = cells.get(&[c[0] + dx, c[1] + dy, c[2] + dz]) {
+              for u in others.iter() {
+                if (self.vertices[*u].pos - p).len() <= tolerance {
+                  clusters.union(*u, v);
+                }
+              }
+            }
+          }
+        }
+      }
+      cells.entry(c).or_default().push(v);
+    }
+    clusters.labels()
+  }
+
+  // Rebuilds the DCEL from vertices and faces, whose edges are ignored,
+  // keeping the normal modes of the mesh and texture coordinates. Normals are
+  // only kept where they are USER_SPECIFIED, and non-manifold edges are
+  // allowed, so that rebuilding never fails.
+  fn rebuild(&mut self, vertices: Vec<Vertex>, faces: Vec<Face>) {
+    let mut builder = TriangularMeshBuilder::new();
+    builder.set_vertex_normal_mode(self.vertex_normal_mode);
+    builder.set_face_normal_mode(self.face_normal_mode);
+    builder.set_normal_weighting(self.normal_weighting);
+    builder.set_allow_non_manifold_edges(true);
+    let user_vertex_normals =
+        self.vertex_normal_mode == VertexNormalMode::USER_SPECIFIED;
+    for v in vertices.iter() {
+      let id = match v.normal {
+        Some(n) if user_vertex_normals => builder.add_vertex_with_normal(v.pos, n),
+        _ => builder.add_vertex(v.pos),
+      };
+      if let Some(uv) = v.uv {
+        builder.set_vertex_uv(id, uv);
+      }
+    }
+    for face in faces.iter() {
+      let v = face.vertices;
+      let id = match face.normal {
+        Some(n) if self.face_normal_mode == FaceNormalMode::USER_SPECIFIED =>
+          builder.add_face_with_normal(v[0], v[1], v[2], n),
+        _ => builder.add_face(v[0], v[1], v[2]),
+      };
+      if let Some(uvs) = face.uvs {
+        builder.set_face_uvs(id, uvs);
+      }
+    }
+    *self = builder.build().expect("Rebuilt mesh must be valid!");
+  }
+}
+
+/// Definitions of vertices of DCEL.
+#[derive(Debug, Clone)]
+pub struct Vertex {
+  // Position of the vertex.
+  pos: Vector3,
+  // Normal vector of the vertex. Might be None if the parent mesh does not
+  // support normal interpolation.
+  normal: Option<Vector3>,
+  // Texture coordinates of the vertex. Might be None if the parent mesh is
+  // not textured.
+  uv: Option<Vector2>,
+  // Tangent frame of the vertex, shared by all its corners. Might be None if
+  // the vertex is on a texture seam or not textured.
+  tangent_frame: Option<TangentFrame>,
+  // A list of indices of half-edges started from this vertex (outgoing).
+  edges: Vec<usize>,
+}
+
+impl Vertex {
+  pub fn pos(&self) -> Vector3 {
+    self.pos
+  }
+
+  pub fn normal(&self) -> Option<Vector3> {
+    self.normal
+  }
+
+  pub fn uv(&self) -> Option<Vector2> {
+    self.uv
+  }
+
+  /// Returns the tangent frame of the vertex, or None if its corners have
+  /// different frames (e.g. on texture seams) or no frame at all. See
+  /// TriangularMesh::recompute_tangent_frames().
+  pub fn tangent_frame(&self) -> Option<TangentFrame> {
+    self.tangent_frame
+  }
+
+  fn new(pos: Vector3) -> Self {
+    Self {
+      pos: pos,
+      normal: None,
+      uv: None,
+      tangent_frame: None,
+      edges: Vec::new()
+    }
+  }
+
+  fn new_with_normal(pos: Vector3, normal: Vector3) -> Self {
+    Self {
+      pos: pos,
+      normal: Some(normal),
+      uv: None,
+      tangent_frame: None,
+      edges: Vec::new()
+    }
+  }
+}
+
+/// Definitions of half-edges of DCEL.
+#[derive(Debug, Clone)]
+pub struct Edge {
+  // Indices of start and end vertices of this edge.
+  start_vertex: usize,
+  end_vertex: usize,
+  // Index of triangle face this edge belongs to.
+  face: usize,
+  // Index of its twin edge. If its value is None, this edge has no twin edge
+  // and thus on the boundary of a manifold.
+  twin_edge: Option<usize>,
+  // Index of the next edge, which belongs to the same face as this one, and
+  // starts from the end vertex of this edge.
+  next_edge: usize,
+  // Index of the prev edge, which belongs to the same face as this one, and
+  // ends at the start vertex of this edge.
+  prev_edge: usize,
+}
+
+impl Edge {
+  pub fn start_vertex(&self) -> usize {
+    self.start_vertex
+  }
+
+  pub fn end_vertex(&self) -> usize {
+    self.end_vertex
+  }
+
+  pub fn face(&self) -> usize {
+    self.face
+  }
+
+  pub fn twin_edge(&self) -> Option<usize> {
+    self.twin_edge
+  }
+
+  pub fn next_edge(&self) -> usize {
+    self.next_edge
+  }
+
+  pub fn prev_edge(&self) -> usize {
+    self.prev_edge
+  }
+
+  /// Returns true if the edge has no twin, i.e. lies on the boundary.
+  pub fn is_boundary(&self) -> bool {
+    self.twin_edge.is_none()
+  }
+}
+
+/// Definitions of triangular faces of DCEL.
+#[derive(Debug, Clone)]
+pub struct Face {
+  // List of all vertices' indices of this triangle.
+  vertices: [usize; 3],
+  // List of all half-edges belonging to this triangle.
+  // Note: edges in this array might not be sorted in counterclock-wise order.
+  // Please rely on edge.nextEdge to traverse the face.
+  edges: [usize; 3],
+  // Normal vector of this face.
+  normal: Option<Vector3>,
+  // Texture coordinates of the three corners of this face, overriding those
+  // of its vertices.
+  uvs: Option<[Vector2; 3]>,
+  // Tangent frames of the three corners of this face.
+  tangent_frames: [Option<TangentFrame>; 3],
+}
+
+impl Face {
+  pub fn vertices(&self) -> [usize; 3] {
+    self.vertices
+  }
+
+  pub fn normal(&self) -> Option<Vector3> {
+    self.normal
+  }
+
+  /// Returns texture coordinates of the three corners of this face if they
+  /// are specified per corner, see TriangularMesh::corner_uvs() for the
+  /// texture coordinates actually used.
+  pub fn uvs(&self) -> Option<[Vector2; 3]> {
+    self.uvs
+  }
+
+  /// Returns the tangent frame at the i-th corner of this face.
+  pub fn tangent_frame(&self, i: usize) -> Option<TangentFrame> {
+    self.tangent_frames[i]
+  }
+
+  // Returns a copy of this face formed by other vertices, keeping its
+  // attributes, e.g. when vertices are renumbered.
+  fn with_vertices(&self, vertices: [usize; 3]) -> Self {
+    let mut face = self.clone();
+    face.vertices = vertices;
+    face
+  }
+
+  fn new(vertices: [usize; 3], edges: [usize; 3]) -> Self {
+    Self {
+      vertices: vertices,
+      edges: edges,
+      normal: None,
+      uvs: None,
+      tangent_frames: [None; 3],
+    }
+  }
+
+  fn new_with_normal(vertices: [usize; 3], edges: [usize; 3], normal: Vector3)
+      -> Self {
+    Self {
+      vertices: vertices,
+      edges: edges,
+      normal: Some(normal),
+      uvs: None,
+      tangent_frames: [None; 3],
+    }
+  }
+}
+
+/// Definition of different modes of normal vector calculation on vertices.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VertexNormalMode {
+  /// User specifies normal vector for each vertex.
+  USER_SPECIFIED,
+  /// Normal of a vertex is calculated by averaging normals of all its adjacent
+  /// faces. If face normals are provided by users, we use the provided value.
+  /// Otherwise We use the flat normal (the unit normal vector orthoganal to
+  /// the face).
+  ///
+  /// There is actually a huge problem with averaged vertex normal, that the
+  /// averaged normal n might be below some triangle, in other words: for some
+  /// n_i that is a flat surface adjacent to this vertex, n * n_i < 0. Using
+  /// such averaged normal might cause Phong-interpolated normal on such face
+  /// be completely on the wrong direction. Forcing the normal to be negated
+  /// will then cause discontinuity of the normal field across the mesh.
+  ///
+  /// Therefore, after averaging we detect such vertices and correct their
+  /// normals, by iteratively tilting the normal towards the faces it is below
+  /// until it is above all adjacent faces. The correction fails only if the
+  /// adjacent face normals do not fit in any open hemisphere (i.e. the
+  /// surface folds onto itself around the vertex), in which case the averaged
+  /// normal is kept. See TriangularMesh::inverted_vertex_normals() to find
+  /// such vertices.
+  AVERAGED
+}
+
+/// Definition of different weights of face normals when averaging them into
+/// vertex normals, see VertexNormalMode::AVERAGED.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NormalWeighting {
+  /// All adjacent faces contribute equally.
+  UNIFORM,
+  /// Each face is weighted by its interior angle at the vertex. The result
+  /// does not depend on how the surface around the vertex is tessellated.
+  ANGLE,
+  /// Each face is weighted by its area, so that small faces (which are often
+  /// less accurate) contribute less.
+  AREA
+}
+
+/// Definition of different modes of normal vector calculation on faces.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FaceNormalMode {
+  /// User specifies normal vector for each face.
+  USER_SPECIFIED,
+  /// Use the unit normal vector orthoganal to the face.
+  FLAT,
+  /// Linearly interpolate normal vector from normals of all its vertices.
+  /// More specifically, if a point p on a triangle can be represented as:
+  ///
+  /// > p = a * v1 + b * v2 + c * v3,
+  ///
+  /// where vi's are the coordinates of the three vertices of the face and
+  /// a, b, c are positive real numbers satisfying a + b + c = 1, then the
+  /// normal of p, denoted as N(p), is calculated as:
+  ///
+  /// > N(p) = a * N(v1) + b * N(v2) + c * N(v3).
+  ///
+  /// **Note**: if vertex mode is not USER_SPECIFIED, then we first need to
+  /// calculate vertex normal by averaging flat normals of faces, then use the
+  /// averaged vertex normal to do Phong interpolation.
+  PHONG
+}
+
+/// A descriptor for any point on the triangular mesh, represented by a face
+/// index and parametric representation of the point. More specifically, if the
+/// face f is formed vertex v0, v1 and v2 (in this order), the representation of
+/// a point p = (f, a, b, c) on this face represents the point:
+///
+/// > p = a * v0 + b * v1 + c * v2
+///
+/// Note that 0 <= a, b, c <= 1 and a + b + c = 1 must be satisfied.
+///
+/// Users of this class should never care about the order and value of the
+/// parameters as it's closely related to the representation of its belonging
+/// triangular mesh, whose order of vertices in a face is also an internal data.
+#[derive(Debug, Copy, Clone)]
+pub struct MeshPoint {
+  face_id: usize,
+  params: Vector3,
+}
+
+impl MeshPoint {
+  pub fn new(face_id: usize, params: Vector3) -> Self {
+    assert!(params[0] >= 0.0 && params[0] <= 1.0
+        &&  params[1] >= 0.0 && params[1] <= 1.0
+        &&  params[2] >= 0.0 && params[2] <= 1.0
+        &&  utils::equal_with_bound(params[0] + params[1] + params[2], 1.0),
+        "Params must be positive real numbers summing up exactly to 1!");
+    Self {
+      face_id: face_id,
+      params: params,
+    }
+  }
+
+  pub fn face_id(&self) -> usize {
+    self.face_id
+  }
+
+  pub fn params(&self) -> Vector3 {
+    self.params
+  }
+}
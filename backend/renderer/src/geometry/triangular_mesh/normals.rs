@@ -0,0 +1,129 @@
+use geometry::HasSurfaceArea;
+use geometry::triangular_mesh::{
+    TriangularMesh, VertexNormalMode, FaceNormalMode, NormalWeighting,
+};
+use math::Vector3;
+
+// The minimum cosine between a corrected vertex normal and normals of its
+// adjacent faces, keeping the corrected normal strictly above all of them.
+const MIN_CORRECTED_COS: f64 = 0.05;
+// The maximum number of iterations when correcting an inverted vertex normal.
+const MAX_CORRECTION_ITERATIONS: usize = 100;
+
+impl TriangularMesh {
+  /// Recomputes face and vertex normals according to the normal modes of the
+  /// mesh. User-specified normals are kept. This is called when the mesh is
+  /// built, and should be called again after vertices are moved. Tangent
+  /// frames are recomputed as well, see recompute_tangent_frames().
+  ///
+  /// Face normals (in FLAT and PHONG modes) are the unit normals of the
+  /// triangles, or None for triangles without normals (see
+  /// Triangle3::try_normal()). Vertex normals (in AVERAGED mode) are the
+  /// weighted average of adjacent face normals, see NormalWeighting, or None
+  /// if no adjacent face has a normal.
+  pub fn recompute_normals(&mut self) {
+    if self.face_normal_mode != FaceNormalMode::USER_SPECIFIED {
+      for f in 0..self.faces.len() {
+        self.faces[f].normal = self.triangle(f).try_normal();
+      }
+    }
+    if self.vertex_normal_mode == VertexNormalMode::AVERAGED {
+      for v in 0..self.vertices.len() {
+        self.vertices[v].normal = self.averaged_vertex_normal(v);
+      }
+    }
+    self.recompute_tangent_frames();
+  }
+
+  // Recomputes normals of given faces and vertices only, after a local edit
+  // of the mesh changed them or their adjacency. Faces must be recomputed
+  // before vertices, since vertex normals are averaged from face normals.
+  // Tangent frames at the corners of the vertices are refreshed as well, so
+  // the vertices must include those of the faces.
+  pub(super) fn refresh_normals(&mut self, faces: &[usize], vertices: &[usize]) {
+    if self.face_normal_mode != FaceNormalMode::USER_SPECIFIED {
+      for f in faces.iter() {
+        self.faces[*f].normal = self.triangle(*f).try_normal();
+      }
+    }
+    if self.vertex_normal_mode == VertexNormalMode::AVERAGED {
+      for v in vertices.iter() {
+        self.vertices[*v].normal = self.averaged_vertex_normal(*v);
+      }
+    }
+    self.refresh_tangent_frames(vertices);
+  }
+
+  /// Returns indices of vertices whose normals are below (or orthogonal to)
+  /// the normal of any adjacent face, i.e. n * n_i <= 0. Phong interpolation
+  /// with such normals may point to the wrong side of the surface. See
+  /// VertexNormalMode::AVERAGED.
+  pub fn inverted_vertex_normals(&self) -> Vec<usize> {
+    (0..self.vertices.len())
+        .filter(|v| match self.vertices[*v].normal {
+          Some(n) => self.adjacent_face_normals(*v).iter()
+              .any(|&(n_i, _)| n * n_i <= 0.0),
+          None => false,
+        })
+        .collect()
+  }
+
+  // Returns the normals of all faces adjacent to a vertex, together with
+  // their weights. Faces without normals are skipped.
+  fn adjacent_face_normals(&self, v: usize) -> Vec<(Vector3, f64)> {
+    self.vertex_faces(v)
+        .filter_map(|f| {
+          self.faces[f].normal.map(|n| (n, self.normal_weight(f, v)))
+        })
+        .collect()
+  }
+
+  // Returns the weight of a face when averaging normals for one of its
+  // vertices.
+  fn normal_weight(&self, f: usize, v: usize) -> f64 {
+    match self.normal_weighting {
+      NormalWeighting::UNIFORM => 1.0,
+      NormalWeighting::AREA => self.triangle(f).surface_area(),
+      NormalWeighting::ANGLE => {
+        let i = self.faces[f].vertices.iter().position(|u| *u == v).unwrap();
+        self.triangle(f).angle(i)
+      }
+    }
+  }
+
+  // Calculates the averaged normal of a vertex, and corrects it if it is
+  // below any adjacent face.
+  fn averaged_vertex_normal(&self, v: usize) -> Option<Vector3> {
+    let normals = self.adjacent_face_normals(v);
+    let sum = normals.iter().fold(Vector3::zero(), |s, &(n, w)| s + n * w);
+    let mut n = sum.normalize();
+    if n.len2() == 0.0 {
+      // Weights may all vanish (e.g. zero angles) or cancel out, fall back to
+      // the unweighted sum.
+      n = normals.iter().fold(Vector3::zero(), |s, &(n, _)| s + n).normalize();
+      if n.len2() == 0.0 {
+        return None;
+      }
+    }
+
+    if normals.iter().all(|&(n_i, _)| n * n_i > 0.0) {
+      return Some(n);
+    }
+    // Tilts the normal towards the adjacent face it is the most below, until
+    // it is above all of them (similar to the perceptron algorithm).
+    let mut corrected = n;
+    for _ in 0..MAX_CORRECTION_ITERATIONS {
+      let &(worst, _) = normals.iter()
+          .min_by(|a, b| (corrected * a.0).partial_cmp(&(corrected * b.0))
+              .unwrap())
+          .unwrap();
+      let cos = corrected * worst;
+      if cos >= MIN_CORRECTED_COS {
+        return Some(corrected);
+      }
+      corrected = (corrected + worst * (2.0 * MIN_CORRECTED_COS - cos))
+          .normalize();
+    }
+    Some(n)
+  }
+}
@@ -0,0 +1,392 @@
+use geometry::Triangle3;
+use geometry::triangular_mesh::{
+    TriangularMesh, Vertex, Face, FaceNormalMode,
+};
+use math::{Vector2, Vector3};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// Report of changes made by a mesh repair pass. Since repairs rebuild the
+/// mesh, indices of vertices and faces may change; the maps relate indices
+/// before the repair to those after it, so that external per-vertex or
+/// per-face data can be updated accordingly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepairReport {
+  // New index of each old vertex, or None if it was removed.
+  vertex_map: Vec<Option<usize>>,
+  // New index of each old face, or None if it was removed.
+  face_map: Vec<Option<usize>>,
+  // New indices of faces whose orientations were reversed.
+  flipped_faces: Vec<usize>,
+  // New indices of vertices and faces that were created.
+  added_vertices: Vec<usize>,
+  added_faces: Vec<usize>,
+}
+
+impl RepairReport {
+  /// Returns the new index of each vertex before the repair, or None if it was
+  /// removed. Welded vertices map to the same index.
+  pub fn vertex_map(&self) -> &[Option<usize>] {
+    &self.vertex_map
+  }
+
+  /// Returns the new index of each face before the repair, or None if it was
+  /// removed.
+  pub fn face_map(&self) -> &[Option<usize>] {
+    &self.face_map
+  }
+
+  /// Returns (new) indices of faces whose orientations were reversed.
+  pub fn flipped_faces(&self) -> &[usize] {
+    &self.flipped_faces
+  }
+
+  /// Returns (new) indices of vertices created by the repair.
+  pub fn added_vertices(&self) -> &[usize] {
+    &self.added_vertices
+  }
+
+  /// Returns (new) indices of faces created by the repair.
+  pub fn added_faces(&self) -> &[usize] {
+    &self.added_faces
+  }
+
+  /// Returns (old) indices of vertices that were removed.
+  pub fn removed_vertices(&self) -> Vec<usize> {
+    (0..self.vertex_map.len()).filter(|v| self.vertex_map[*v].is_none())
+        .collect()
+  }
+
+  /// Returns (old) indices of faces that were removed.
+  pub fn removed_faces(&self) -> Vec<usize> {
+    (0..self.face_map.len()).filter(|f| self.face_map[*f].is_none()).collect()
+  }
+
+  /// Returns (old) indices of vertices that were welded into a vertex of a
+  /// smaller (old) index.
+  pub fn welded_vertices(&self) -> Vec<usize> {
+    let mut seen = vec![false; self.vertex_map.len()];
+    let mut welded = Vec::new();
+    for (v, u) in self.vertex_map.iter().enumerate() {
+      if let Some(u) = *u {
+        if seen[u] {
+          welded.push(v);
+        }
+        seen[u] = true;
+      }
+    }
+    welded
+  }
+
+  /// Returns true if the repair did not change the mesh.
+  pub fn is_empty(&self) -> bool {
+    self.flipped_faces.is_empty() && self.added_vertices.is_empty()
+        && self.added_faces.is_empty()
+        && self.vertex_map.iter().enumerate().all(|(v, u)| *u == Some(v))
+        && self.face_map.iter().enumerate().all(|(f, g)| *g == Some(f))
+  }
+
+  fn new(vertex_map: Vec<Option<usize>>, face_map: Vec<Option<usize>>)
+      -> Self {
+    Self {
+      vertex_map: vertex_map,
+      face_map: face_map,
+      flipped_faces: Vec::new(),
+      added_vertices: Vec::new(),
+      added_faces: Vec::new(),
+    }
+  }
+
+  fn identity(num_vertices: usize, num_faces: usize) -> Self {
+    Self::new((0..num_vertices).map(Some).collect(),
+        (0..num_faces).map(Some).collect())
+  }
+}
+
+impl fmt::Display for RepairReport {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} vertex(es) welded, {} vertex(es) removed, {} face(s) \
+        removed, {} face(s) flipped, {} vertex(es) and {} face(s) added",
+        self.welded_vertices().len(), self.removed_vertices().len(),
+        self.removed_faces().len(), self.flipped_faces.len(),
+        self.added_vertices.len(), self.added_faces.len())
+  }
+}
+
+impl TriangularMesh {
+  /// Welds vertices closer than a tolerance into one, keeping the position
+  /// and attributes of the vertex of the smallest index. Welding is
+  /// transitive, i.e. a chain of close vertices is welded together even if
+  /// its ends are farther apart. Faces collapsed by welding are removed.
+  ///
+  /// Welded vertices with different texture coordinates (e.g. along texture
+  /// seams) keep them as corner texture coordinates of their faces.
+  pub fn weld_vertices(&mut self, tolerance: f64) -> RepairReport {
+    // Clusters are labeled by their first vertices, thus in the same order.
+    let (labels, num_clusters) = self.cluster_vertices(tolerance);
+    let mut vertices: Vec<Vertex> = Vec::with_capacity(num_clusters);
+    let mut uv_seams = vec![false; num_clusters];
+    for v in 0..self.vertices.len() {
+      if labels[v] == vertices.len() {
+        vertices.push(self.vertices[v].clone());
+      } else if vertices[labels[v]].uv != self.vertices[v].uv {
+        uv_seams[labels[v]] = true;
+      }
+    }
+    let mut faces = Vec::with_capacity(self.faces.len());
+    let mut face_map = Vec::with_capacity(self.faces.len());
+    for (f, face) in self.faces.iter().enumerate() {
+      let v = face.vertices;
+      let welded = [labels[v[0]], labels[v[1]], labels[v[2]]];
+      if welded[0] == welded[1] || welded[1] == welded[2]
+          || welded[2] == welded[0] {
+        face_map.push(None);
+      } else {
+        face_map.push(Some(faces.len()));
+        let mut welded_face = face.with_vertices(welded);
+        if welded.iter().any(|l| uv_seams[*l]) {
+          welded_face.uvs = self.corner_uvs(f);
+        }
+        faces.push(welded_face);
+      }
+    }
+    self.rebuild(vertices, faces);
+    RepairReport::new(labels.into_iter().map(Some).collect(), face_map)
+  }
+
+  /// Removes faces whose triangles are degenerate, see
+  /// Triangle3::is_degenerate(). Their vertices are kept even if no longer
+  /// referenced, see remove_unreferenced_vertices().
+  pub fn remove_degenerate_faces(&mut self) -> RepairReport {
+    let keep: Vec<bool> = (0..self.faces.len())
+        .map(|f| !self.triangle(f).is_degenerate())
+        .collect();
+    self.retain_faces(&keep)
+  }
+
+  /// Removes faces formed by the same three vertices as a face of a smaller
+  /// index, regardless of their orders (and thus orientations).
+  pub fn remove_duplicate_faces(&mut self) -> RepairReport {
+    let mut first_faces: HashMap<[usize; 3], usize> = HashMap::new();
+    let keep: Vec<bool> = self.faces.iter().enumerate()
+        .map(|(f, face)| {
+          let mut key = face.vertices;
+          key.sort();
+          *first_faces.entry(key).or_insert(f) == f
+        })
+        .collect();
+    self.retain_faces(&keep)
+  }
+
+  /// Removes vertices not referenced by any face.
+  pub fn remove_unreferenced_vertices(&mut self) -> RepairReport {
+    let mut vertex_map = vec![None; self.vertices.len()];
+    let mut vertices = Vec::new();
+    for (v, vertex) in self.vertices.iter().enumerate() {
+      if !vertex.edges.is_empty() {
+        vertex_map[v] = Some(vertices.len());
+        vertices.push(vertex.clone());
+      }
+    }
+    let faces = self.faces.iter()
+        .map(|face| {
+          let v = face.vertices;
+          face.with_vertices([vertex_map[v[0]].unwrap(),
+              vertex_map[v[1]].unwrap(), vertex_map[v[2]].unwrap()])
+        })
+        .collect();
+    let num_faces = self.faces.len();
+    self.rebuild(vertices, faces);
+    RepairReport::new(vertex_map, (0..num_faces).map(Some).collect())
+  }
+
+  /// Makes orientations of faces consistent within each connected component,
+  /// by flipping faces so that every manifold edge is traversed in opposite
+  /// directions by its two faces. The face of the smallest index in each
+  /// component keeps its orientation, unless the component is closed, in
+  /// which case all its faces are oriented outwards, i.e. the signed volume
+  /// it encloses is positive.
+  ///
+  /// Components that are not orientable (e.g. a Möbius strip) cannot be made
+  /// consistent, and some of their edges remain inconsistent. Non-manifold
+  /// edges do not propagate orientations.
+  pub fn orient_faces(&mut self) -> RepairReport {
+    let spatial_edges = self.spatial_edges();
+    let key_of = |e: usize| {
+      let (a, b) = (self.edges[e].start_vertex, self.edges[e].end_vertex);
+      (a.min(b), a.max(b))
+    };
+    let mut flipped: Vec<Option<bool>> = vec![None; self.faces.len()];
+    for seed in 0..self.faces.len() {
+      if flipped[seed].is_some() {
+        continue;
+      }
+      flipped[seed] = Some(false);
+      let mut component = vec![seed];
+      let mut closed = true;
+      let mut queue = VecDeque::new();
+      queue.push_back(seed);
+      while let Some(f) = queue.pop_front() {
+        for e in self.face_edges(f) {
+          let shared = &spatial_edges[&key_of(e)];
+          if shared.len() != 2 {
+            closed = false;
+            continue;
+          }
+          let other = if shared[0] == e { shared[1] } else { shared[0] };
+          let g = self.edges[other].face;
+          if flipped[g].is_none() {
+            // Neighbors traversing the edge in the same direction must have
+            // opposite flips.
+            let same = self.edges[other].start_vertex
+                == self.edges[e].start_vertex;
+            flipped[g] = Some(flipped[f].unwrap() != same);
+            component.push(g);
+            queue.push_back(g);
+          }
+        }
+      }
+      if closed {
+        let volume: f64 = component.iter()
+            .map(|f| {
+              let t = self.triangle(*f);
+              let v = t.v(0) * (t.v(1) ^ t.v(2));
+              if flipped[*f].unwrap() { -v } else { v }
+            })
+            .sum();
+        if volume < 0.0 {
+          for f in component.iter() {
+            flipped[*f] = Some(!flipped[*f].unwrap());
+          }
+        }
+      }
+    }
+
+    let mut report = RepairReport::identity(self.vertices.len(),
+        self.faces.len());
+    report.flipped_faces = (0..self.faces.len())
+        .filter(|f| flipped[*f].unwrap())
+        .collect();
+    if report.flipped_faces.is_empty() {
+      return report;
+    }
+    let faces = self.faces.iter().zip(flipped.iter())
+        .map(|(face, flip)| {
+          if !flip.unwrap() {
+            return face.clone();
+          }
+          let v = face.vertices;
+          let mut flipped = face.with_vertices([v[0], v[2], v[1]]);
+          flipped.normal = face.normal.map(|n| -n);
+          flipped.uvs = face.uvs.map(|uv| [uv[0], uv[2], uv[1]]);
+          flipped
+        })
+        .collect();
+    let vertices = self.vertices.clone();
+    self.rebuild(vertices, faces);
+    report
+  }
+
+  /// Fills holes bounded by boundary loops of at most max_edges edges. A hole
+  /// of three edges is filled by one triangle, larger ones by a fan of
+  /// triangles around a new vertex at the centroid of the loop, whose normal
+  /// and texture coordinates are averaged over the loop. New faces are
+  /// oriented consistently with their neighbors.
+  pub fn fill_holes(&mut self, max_edges: usize) -> RepairReport {
+    let loops: Vec<Vec<usize>> = self.boundary_loops()
+        .filter(|l| l.len() >= 3 && l.len() <= max_edges
+            && self.edges[l[l.len() - 1]].end_vertex
+                == self.edges[l[0]].start_vertex)
+        .collect();
+    let mut report = RepairReport::identity(self.vertices.len(),
+        self.faces.len());
+    if loops.is_empty() {
+      return report;
+    }
+
+    let mut vertices = self.vertices.clone();
+    let mut faces = self.faces.clone();
+    for l in loops.iter() {
+      // Each new face contains the twin of a boundary half-edge a -> b.
+      let ends: Vec<(usize, usize)> = l.iter()
+          .map(|e| (self.edges[*e].start_vertex, self.edges[*e].end_vertex))
+          .collect();
+      if l.len() == 3 {
+        report.added_faces.push(faces.len());
+        faces.push(self.filling_face(l[0], [ends[0].1, ends[0].0, ends[2].0],
+            &vertices));
+        continue;
+      }
+      let center = vertices.len();
+      vertices.push(self.loop_centroid(&ends));
+      report.added_vertices.push(center);
+      for (i, &(a, b)) in ends.iter().enumerate() {
+        report.added_faces.push(faces.len());
+        faces.push(self.filling_face(l[i], [b, a, center], &vertices));
+      }
+    }
+    self.rebuild(vertices, faces);
+    report
+  }
+
+  // Returns a new vertex at the centroid of a boundary loop, given as the
+  // start and end vertices of its edges, with averaged attributes.
+  fn loop_centroid(&self, ends: &[(usize, usize)]) -> Vertex {
+    let n = ends.len() as f64;
+    let pos = ends.iter()
+        .fold(Vector3::zero(), |s, &(a, _)| s + self.vertices[a].pos) / n;
+    let mut vertex = Vertex::new(pos);
+    let normals: Option<Vec<Vector3>> = ends.iter()
+        .map(|&(a, _)| self.vertices[a].normal)
+        .collect();
+    if let Some(normals) = normals {
+      let sum = normals.iter().fold(Vector3::zero(), |s, n| s + *n);
+      vertex.normal = Some(sum.normalize());
+    }
+    let uvs: Option<Vec<Vector2>> = ends.iter()
+        .map(|&(a, _)| self.vertices[a].uv)
+        .collect();
+    if let Some(uvs) = uvs {
+      let sum = uvs.iter().fold(Vector2::zero(), |s, uv| s + *uv);
+      vertex.uv = Some(sum * (1.0 / n));
+    }
+    vertex
+  }
+
+  // Returns a new face filling a hole. Its normal is only kept if face
+  // normals are USER_SPECIFIED, and is the normal of the new triangle, or
+  // that of the face across the boundary edge if the triangle is degenerate.
+  // Texture coordinates are those of its vertices.
+  fn filling_face(&self, boundary_edge: usize, v: [usize; 3],
+      vertices: &[Vertex]) -> Face {
+    let mut face = Face::new(v, [0; 3]);
+    if self.face_normal_mode == FaceNormalMode::USER_SPECIFIED {
+      let t = Triangle3::new(vertices[v[0]].pos, vertices[v[1]].pos,
+          vertices[v[2]].pos);
+      face.normal = t.try_normal()
+          .or(self.faces[self.edges[boundary_edge].face].normal);
+    }
+    face
+  }
+
+  // Removes faces that are not marked to be kept, without changing vertices.
+  fn retain_faces(&mut self, keep: &[bool]) -> RepairReport {
+    let mut report = RepairReport::identity(self.vertices.len(),
+        self.faces.len());
+    if keep.iter().all(|k| *k) {
+      return report;
+    }
+    let mut faces = Vec::new();
+    for (f, face) in self.faces.iter().enumerate() {
+      if keep[f] {
+        report.face_map[f] = Some(faces.len());
+        faces.push(face.clone());
+      } else {
+        report.face_map[f] = None;
+      }
+    }
+    let vertices = self.vertices.clone();
+    self.rebuild(vertices, faces);
+    report
+  }
+}
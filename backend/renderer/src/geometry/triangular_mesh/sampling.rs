@@ -0,0 +1,145 @@
+use common::random::Random;
+use geometry::HasSurfaceArea;
+use geometry::triangular_mesh::{TriangularMesh, MeshPoint};
+use math::Vector3;
+use std::collections::HashMap;
+
+/// A sampler of points uniformly distributed over the surface of a mesh, e.g.
+/// for area lights or point cloud generation. A face is chosen with
+/// probability proportional to its area, then a point is chosen uniformly on
+/// the face. For details please read:
+///
+/// > Physically Based Rendering (third edition), Chapter 13.6.
+/// > M. Pharr, W. Jakob, G. Humphreys.
+///
+/// The sampler precomputes the distribution of faces and borrows the mesh, so
+/// it must be recreated after the mesh is modified.
+#[derive(Debug, Clone)]
+pub struct SurfaceSampler<'a> {
+  mesh: &'a TriangularMesh,
+  // Cumulative areas of faces, normalized so that the last one is 1.
+  cdf: Vec<f64>,
+  surface_area: f64,
+}
+
+impl<'a> SurfaceSampler<'a> {
+  pub fn new(mesh: &'a TriangularMesh) -> Self {
+    let mut cdf = Vec::with_capacity(mesh.faces.len());
+    let mut sum = 0.0;
+    for f in 0..mesh.faces.len() {
+      sum += mesh.triangle(f).surface_area();
+      cdf.push(sum);
+    }
+    if sum > 0.0 {
+      for c in cdf.iter_mut() {
+        *c /= sum;
+      }
+    }
+    Self {
+      mesh: mesh,
+      cdf: cdf,
+      surface_area: sum,
+    }
+  }
+
+  pub fn surface_area(&self) -> f64 {
+    self.surface_area
+  }
+
+  /// Maps two uniformly distributed numbers in [0, 1) to a uniformly
+  /// distributed point on the surface, or returns None if the surface has no
+  /// area. The first number chooses the face and is then rescaled to be
+  /// reused on the face, so that well-stratified numbers (e.g. from a
+  /// low-discrepancy sequence) give well-stratified points.
+  pub fn sample(&self, u1: f64, u2: f64) -> Option<MeshPoint> {
+    assert!((0.0..1.0).contains(&u1) && (0.0..1.0).contains(&u2),
+        "Uniform numbers must be in [0, 1)!");
+    if self.surface_area <= 0.0 {
+      return None;
+    }
+    // The first face whose cumulative area exceeds u1, skipping faces of no
+    // area.
+    let f = self.cdf.partition_point(|c| *c <= u1).min(self.cdf.len() - 1);
+    let start = if f == 0 { 0.0 } else { self.cdf[f - 1] };
+    let u1 = ((u1 - start) / (self.cdf[f] - start)).clamp(0.0, 1.0);
+
+    // Uniform barycentric coordinates, see Chapter 13.6.5 in PBRT.
+    let s = u1.sqrt();
+    let a = 1.0 - s;
+    let b = u2 * s;
+    Some(MeshPoint::new(f, Vector3::new(a, b, (1.0 - a - b).max(0.0))))
+  }
+
+  /// Returns the probability density (with respect to surface area) of
+  /// sampling a point, which is 1 / area on faces of positive area, and 0 on
+  /// degenerate faces.
+  pub fn pdf(&self, p: &MeshPoint) -> f64 {
+    if self.mesh.triangle(p.face_id).surface_area() <= 0.0 {
+      return 0.0;
+    }
+    1.0 / self.surface_area
+  }
+
+  /// Generates an evenly spaced (blue noise) point set over the surface, in
+  /// which no two points are closer than a radius (in Euclidean distance).
+  /// Points are sampled uniformly and rejected if too close to an accepted
+  /// point (plain dart throwing), until max_attempts consecutive samples are
+  /// rejected, after which the surface is (nearly) maximally covered.
+  /// Accepted points are looked up in a hash grid of cells the size of the
+  /// radius. For details please read:
+  ///
+  /// > Stochastic Sampling in Computer Graphics. R. L. Cook. 1986.
+  ///
+  /// Results are reproducible for a given seed.
+  pub fn sample_poisson_disk(&self, radius: f64, max_attempts: usize,
+      seed: u64) -> Vec<MeshPoint> {
+    assert!(radius > 0.0, "Radius must be positive!");
+    let mut points = Vec::new();
+    if self.surface_area <= 0.0 {
+      return points;
+    }
+    let mut random = Random::new(seed);
+    // Accepted points within the radius are in the same or adjacent cells.
+    let cell_of = |p: Vector3| [(p.x() / radius).floor() as i64,
+        (p.y() / radius).floor() as i64, (p.z() / radius).floor() as i64];
+    let mut cells: HashMap<[i64; 3], Vec<Vector3>> = HashMap::new();
+    let mut attempts = 0;
+    while attempts < max_attempts {
+      let u1 = random.next_f64();
+      let u2 = random.next_f64();
+      let p = self.sample(u1, u2).unwrap();
+      let pos = self.mesh.position(&p);
+      let c = cell_of(pos);
+      let mut too_close = false;
+      'search: for dx in -1..2 {
+        for dy in -1..2 {
+          for dz in -1..2 {
+            let cell = [c[0] + dx, c[1] + dy, c[2] + dz];
+            if let Some(others) = cells.get(&cell) {
+              if others.iter().any(|q| (*q - pos).len() < radius) {
+                too_close = true;
+                break 'search;
+              }
+            }
+          }
+        }
+      }
+      if too_close {
+        attempts += 1;
+      } else {
+        attempts = 0;
+        cells.entry(c).or_default().push(pos);
+        points.push(p);
+      }
+    }
+    points
+  }
+}
+
+impl TriangularMesh {
+  /// Creates a sampler of uniformly distributed points on the surface, see
+  /// SurfaceSampler.
+  pub fn surface_sampler(&self) -> SurfaceSampler<'_> {
+    SurfaceSampler::new(self)
+  }
+}
@@ -0,0 +1,539 @@
+use common::constants;
+use geometry::{Plane, Quadric, Triangle3};
+use geometry::triangular_mesh::{TriangularMesh, Vertex, VertexNormalMode};
+use math::{Vector2, Vector3};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+// The weight of the penalty planes along boundary edges, relative to the
+// planes of faces, which keeps boundaries from drifting away.
+const BOUNDARY_WEIGHT: f64 = 1000.0;
+// The minimum cosine between normals of a face before and after a collapse,
+// rejecting collapses that fold faces over.
+const MIN_NORMAL_COS: f64 = 0.0;
+
+/// Options of TriangularMesh::simplify(). Simplification stops as soon as
+/// either the target face count is reached, or the next collapse would exceed
+/// the maximum error. By default, the mesh is simplified as much as possible,
+/// and boundaries and attribute seams are preserved.
+#[derive(Debug, Copy, Clone)]
+pub struct SimplificationOptions {
+  target_faces: usize,
+  max_error: f64,
+  preserve_boundaries: bool,
+  preserve_seams: bool,
+}
+
+impl Default for SimplificationOptions {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SimplificationOptions {
+  pub fn new() -> Self {
+    Self {
+      target_faces: 0,
+      max_error: constants::INFINITY,
+      preserve_boundaries: true,
+      preserve_seams: true,
+    }
+  }
+
+  /// Sets the number of faces to simplify the mesh down to.
+  pub fn set_target_faces(&mut self, target_faces: usize) {
+    self.target_faces = target_faces;
+  }
+
+  /// Sets the maximum error of a collapse, i.e. the sum of squared distances
+  /// from the new vertex to the planes of the original faces around it.
+  pub fn set_max_error(&mut self, max_error: f64) {
+    self.max_error = max_error;
+  }
+
+  /// Sets whether vertices on boundaries (other than seams) are kept in
+  /// place. Otherwise they may move, constrained by penalty planes.
+  pub fn set_preserve_boundaries(&mut self, preserve: bool) {
+    self.preserve_boundaries = preserve;
+  }
+
+  /// Sets whether vertices on attribute seams are kept in place. A seam is
+  /// either a boundary where vertices are duplicated (e.g. with different
+  /// normals or texture coordinates) to split attributes, where moving its
+  /// vertices opens cracks, or vertices whose faces have different corner
+  /// texture coordinates, where moving them distorts textures.
+  pub fn set_preserve_seams(&mut self, preserve: bool) {
+    self.preserve_seams = preserve;
+  }
+}
+
+/// Report of TriangularMesh::simplify().
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimplificationReport {
+  // New index of each old vertex, or None if it was collapsed.
+  vertex_map: Vec<Option<usize>>,
+  // New index of each old face, or None if it was removed.
+  face_map: Vec<Option<usize>>,
+  num_collapses: usize,
+  max_error: f64,
+}
+
+impl SimplificationReport {
+  /// Returns the new index of each vertex before the simplification, or None
+  /// if it was collapsed into another vertex.
+  pub fn vertex_map(&self) -> &[Option<usize>] {
+    &self.vertex_map
+  }
+
+  /// Returns the new index of each face before the simplification, or None
+  /// if it was removed by a collapse.
+  pub fn face_map(&self) -> &[Option<usize>] {
+    &self.face_map
+  }
+
+  /// Returns the number of edges collapsed.
+  pub fn num_collapses(&self) -> usize {
+    self.num_collapses
+  }
+
+  /// Returns the maximum error of all collapses, or 0 if none is made.
+  pub fn max_error(&self) -> f64 {
+    self.max_error
+  }
+}
+
+impl TriangularMesh {
+  /// Simplifies the mesh by repeatedly collapsing the edge of the smallest
+  /// quadric error into a single vertex, placed where the error is minimized.
+  /// The error of a vertex is the sum of squared distances to the planes of
+  /// all original faces merged into it. For more details please read:
+  ///
+  /// > Surface Simplification Using Quadric Error Metrics.
+  /// > M. Garland, P. S. Heckbert. SIGGRAPH 1997.
+  ///
+  /// Collapses that would make the mesh non-manifold (see link condition in
+  /// Dey et al., Topology Preserving Edge Contraction, 1999), fold faces
+  /// over, or create degenerate faces are skipped. Texture coordinates
+  /// (including corner ones) and user-specified normals of the new vertex are
+  /// interpolated along the collapsed edge, and other normals are recomputed.
+  pub fn simplify(&mut self, options: &SimplificationOptions)
+      -> SimplificationReport {
+    let mut decimation = Decimation::new(self, options);
+    let mut num_faces = self.faces.len();
+    let mut num_collapses = 0;
+    let mut max_error: f64 = 0.0;
+    while num_faces > options.target_faces {
+      let candidate = match decimation.heap.pop() {
+        Some(c) => c,
+        None => break,
+      };
+      if !decimation.is_current(&candidate) {
+        continue;
+      }
+      if candidate.cost > options.max_error {
+        break;
+      }
+      if let Some(removed) = decimation.collapse(&candidate) {
+        num_faces -= removed;
+        num_collapses += 1;
+        max_error = max_error.max(candidate.cost);
+      }
+    }
+
+    let mut vertex_map = vec![None; self.vertices.len()];
+    let mut vertices = Vec::new();
+    for (v, vertex) in decimation.vertices.iter().enumerate() {
+      if decimation.vertex_alive[v] {
+        vertex_map[v] = Some(vertices.len());
+        vertices.push(vertex.clone());
+      }
+    }
+    let mut face_map = vec![None; self.faces.len()];
+    let mut faces = Vec::new();
+    for (f, face) in self.faces.iter().enumerate() {
+      if decimation.face_alive[f] {
+        let v = decimation.faces[f];
+        let mut face = face.with_vertices([vertex_map[v[0]].unwrap(),
+            vertex_map[v[1]].unwrap(), vertex_map[v[2]].unwrap()]);
+        face.uvs = decimation.face_uvs[f];
+        face_map[f] = Some(faces.len());
+        faces.push(face);
+      }
+    }
+    self.rebuild(vertices, faces);
+    SimplificationReport {
+      vertex_map: vertex_map,
+      face_map: face_map,
+      num_collapses: num_collapses,
+      max_error: max_error,
+    }
+  }
+
+  // Returns whether each vertex lies on a boundary, and whether it lies on a
+  // seam, i.e. a boundary coincident with another vertex, or a vertex whose
+  // faces have different corner texture coordinates.
+  fn boundary_and_seam_vertices(&self) -> (Vec<bool>, Vec<bool>) {
+    let mut boundary = vec![false; self.vertices.len()];
+    for e in self.edges.iter().filter(|e| e.twin_edge.is_none()) {
+      boundary[e.start_vertex] = true;
+      boundary[e.end_vertex] = true;
+    }
+    let (labels, num_clusters) = self.cluster_vertices(constants::EPSILON);
+    let mut cluster_sizes = vec![0; num_clusters];
+    for l in labels.iter() {
+      cluster_sizes[*l] += 1;
+    }
+    let mut seam: Vec<bool> = (0..self.vertices.len())
+        .map(|v| boundary[v] && cluster_sizes[labels[v]] > 1)
+        .collect();
+    let mut corner_uvs: Vec<Option<Vector2>> = vec![None; self.vertices.len()];
+    for face in self.faces.iter() {
+      let uvs = match face.uvs {
+        Some(uvs) => uvs,
+        None => continue,
+      };
+      for (v, uv) in face.vertices.iter().zip(uvs.iter()) {
+        match corner_uvs[*v] {
+          Some(other) => if (other - *uv).len() > constants::EPSILON {
+            seam[*v] = true;
+          },
+          None => corner_uvs[*v] = Some(*uv),
+        }
+      }
+    }
+    (boundary, seam)
+  }
+}
+
+// A candidate collapse of an edge, ordered by its cost, the smallest first.
+#[derive(Debug, Copy, Clone)]
+struct Candidate {
+  cost: f64,
+  // The vertex kept (moved to the target) and the vertex removed.
+  keep: usize,
+  remove: usize,
+  target: Vector3,
+  // Versions of the two vertices when the candidate was evaluated.
+  versions: (usize, usize),
+}
+
+impl PartialEq for Candidate {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Candidate {
+  fn cmp(&self, other: &Self) -> Ordering {
+    // Reversed so that BinaryHeap pops the smallest cost first.
+    other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+  }
+}
+
+// The working state of a simplification, with faces as vertex triples and
+// vertex-to-face adjacency, which are cheaper to update than the DCEL.
+struct Decimation {
+  vertices: Vec<Vertex>,
+  faces: Vec<[usize; 3]>,
+  face_uvs: Vec<Option<[Vector2; 3]>>,
+  vertex_alive: Vec<bool>,
+  face_alive: Vec<bool>,
+  // Faces adjacent to each vertex, possibly including dead faces.
+  vertex_faces: Vec<Vec<usize>>,
+  quadrics: Vec<Quadric>,
+  boundary: Vec<bool>,
+  locked: Vec<bool>,
+  // Incremented whenever a vertex moves, invalidating its candidates.
+  versions: Vec<usize>,
+  user_normals: bool,
+  heap: BinaryHeap<Candidate>,
+}
+
+impl Decimation {
+  fn new(mesh: &TriangularMesh, options: &SimplificationOptions) -> Self {
+    let (boundary, seam) = mesh.boundary_and_seam_vertices();
+    let locked = (0..mesh.vertices.len())
+        .map(|v| if seam[v] {
+          options.preserve_seams
+        } else {
+          boundary[v] && options.preserve_boundaries
+        })
+        .collect();
+
+    let mut quadrics = vec![Quadric::zero(); mesh.vertices.len()];
+    let mut vertex_faces = vec![Vec::new(); mesh.vertices.len()];
+    for (f, face) in mesh.faces.iter().enumerate() {
+      let t = mesh.triangle(f);
+      for v in face.vertices.iter() {
+        vertex_faces[*v].push(f);
+      }
+      let n = match t.try_normal() {
+        Some(n) => n,
+        None => continue,
+      };
+      let q = Quadric::new_from_plane(&Plane::new_from_point(n, t.v(0)), 1.0);
+      for v in face.vertices.iter() {
+        quadrics[*v] += q;
+      }
+    }
+    for e in mesh.edges.iter().filter(|e| e.twin_edge.is_none()) {
+      let (p0, p1) = (mesh.vertices[e.start_vertex].pos,
+          mesh.vertices[e.end_vertex].pos);
+      if let Some(n) = mesh.faces[e.face].normal {
+        let side = (p1 - p0) ^ n;
+        if side.len2() > constants::EPSILON_TINY {
+          let q = Quadric::new_from_plane(&Plane::new_from_point(side, p0),
+              BOUNDARY_WEIGHT);
+          quadrics[e.start_vertex] += q;
+          quadrics[e.end_vertex] += q;
+        }
+      }
+    }
+
+    let mut decimation = Self {
+      vertices: mesh.vertices.clone(),
+      faces: mesh.faces.iter().map(|f| f.vertices).collect(),
+      face_uvs: mesh.faces.iter().map(|f| f.uvs).collect(),
+      vertex_alive: vec![true; mesh.vertices.len()],
+      face_alive: vec![true; mesh.faces.len()],
+      vertex_faces: vertex_faces,
+      quadrics: quadrics,
+      boundary: boundary,
+      locked: locked,
+      versions: vec![0; mesh.vertices.len()],
+      user_normals: mesh.vertex_normal_mode == VertexNormalMode::USER_SPECIFIED,
+      heap: BinaryHeap::new(),
+    };
+    for e in mesh.edges.iter() {
+      // Each spatial edge with twins is evaluated once, from the half-edge
+      // starting at the vertex of the smaller index.
+      if e.twin_edge.is_none() || e.start_vertex < e.end_vertex {
+        decimation.push_candidate(e.start_vertex, e.end_vertex);
+      }
+    }
+    decimation
+  }
+
+  fn is_current(&self, c: &Candidate) -> bool {
+    self.vertex_alive[c.keep] && self.vertex_alive[c.remove]
+        && self.versions[c.keep] == c.versions.0
+        && self.versions[c.remove] == c.versions.1
+  }
+
+  // Evaluates the collapse of an edge and adds it to the heap, unless both
+  // vertices are locked.
+  fn push_candidate(&mut self, a: usize, b: usize) {
+    let (keep, remove) = if self.locked[b] { (b, a) } else { (a, b) };
+    if self.locked[remove] {
+      return;
+    }
+    let q = self.quadrics[keep] + self.quadrics[remove];
+    let (pk, pr) = (self.vertices[keep].pos, self.vertices[remove].pos);
+    let mut targets = vec![pk];
+    if !self.locked[keep] {
+      targets.push(pr);
+      targets.push((pk + pr) * 0.5);
+      if let Some(p) = q.minimizer() {
+        targets.push(p);
+      }
+    }
+    let (target, cost) = targets.iter()
+        .map(|p| (*p, q.error(*p).max(0.0)))
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+        .unwrap();
+    self.heap.push(Candidate {
+      cost: cost,
+      keep: keep,
+      remove: remove,
+      target: target,
+      versions: (self.versions[keep], self.versions[remove]),
+    });
+  }
+
+  fn alive_faces(&self, v: usize) -> Vec<usize> {
+    self.vertex_faces[v].iter().cloned()
+        .filter(|f| self.face_alive[*f])
+        .collect()
+  }
+
+  // Returns vertices adjacent to a vertex through alive faces.
+  fn neighbors(&self, v: usize) -> Vec<usize> {
+    let mut neighbors: Vec<usize> = self.alive_faces(v).iter()
+        .flat_map(|f| self.faces[*f].iter().cloned())
+        .filter(|u| *u != v)
+        .collect();
+    neighbors.sort();
+    neighbors.dedup();
+    neighbors
+  }
+
+  // Checks whether collapsing an edge keeps the mesh manifold, see
+  // TriangularMesh::simplify().
+  fn satisfies_link_condition(&self, a: usize, b: usize) -> bool {
+    let shared: Vec<usize> = self.alive_faces(a).into_iter()
+        .filter(|f| self.faces[*f].contains(&b))
+        .collect();
+    // Non-manifold edges are never collapsed, nor are interior edges between
+    // two boundary vertices, which would pinch the surface.
+    if shared.is_empty() || shared.len() > 2
+        || (shared.len() == 2 && self.boundary[a] && self.boundary[b]) {
+      return false;
+    }
+    let mut opposite: Vec<usize> = shared.iter()
+        .map(|f| *self.faces[*f].iter().find(|u| **u != a && **u != b).unwrap())
+        .collect();
+    opposite.sort();
+    let nb = self.neighbors(b);
+    let common: Vec<usize> = self.neighbors(a).into_iter()
+        .filter(|u| nb.binary_search(u).is_ok())
+        .collect();
+    if common != opposite {
+      return false;
+    }
+    // No edge may be in the links of both vertices, e.g. in a tetrahedron.
+    let link_edges = |v: usize, other: usize| -> Vec<(usize, usize)> {
+      self.alive_faces(v).iter()
+          .filter(|f| !self.faces[**f].contains(&other))
+          .map(|f| {
+            let mut e = self.faces[*f].iter().cloned().filter(|u| *u != v);
+            let (x, y) = (e.next().unwrap(), e.next().unwrap());
+            (x.min(y), x.max(y))
+          })
+          .collect()
+    };
+    let eb = link_edges(b, a);
+    link_edges(a, b).iter().all(|e| !eb.contains(e))
+  }
+
+  // Checks that no face around the collapsed edge degenerates or flips.
+  fn preserves_faces(&self, c: &Candidate) -> bool {
+    for v in [c.keep, c.remove].iter() {
+      for f in self.alive_faces(*v) {
+        let face = self.faces[f];
+        if face.contains(&c.keep) && face.contains(&c.remove) {
+          continue;
+        }
+        let pos = |u: usize| if u == c.keep || u == c.remove {
+          c.target
+        } else {
+          self.vertices[u].pos
+        };
+        let before = Triangle3::new(self.vertices[face[0]].pos,
+            self.vertices[face[1]].pos, self.vertices[face[2]].pos);
+        let after = Triangle3::new(pos(face[0]), pos(face[1]), pos(face[2]));
+        match (before.try_normal(), after.try_normal()) {
+          (Some(n0), Some(n1)) => if n0 * n1 <= MIN_NORMAL_COS {
+            return false;
+          },
+          (_, None) => return false,
+          (None, Some(_)) => {},
+        }
+      }
+    }
+    true
+  }
+
+  // Collapses an edge if allowed, and returns the number of faces removed.
+  fn collapse(&mut self, c: &Candidate) -> Option<usize> {
+    if !self.satisfies_link_condition(c.keep, c.remove)
+        || !self.preserves_faces(c) {
+      return None;
+    }
+    let (keep, remove) = (c.keep, c.remove);
+    let t = self.interpolate_attributes(keep, remove, c.target);
+    self.interpolate_corner_uvs(keep, remove, t);
+    self.vertices[keep].pos = c.target;
+    self.quadrics[keep] = self.quadrics[keep] + self.quadrics[remove];
+    self.boundary[keep] = self.boundary[keep] || self.boundary[remove];
+    self.vertex_alive[remove] = false;
+    self.versions[keep] += 1;
+
+    let mut removed = 0;
+    for f in self.alive_faces(remove) {
+      if self.faces[f].contains(&keep) {
+        self.face_alive[f] = false;
+        removed += 1;
+      } else {
+        for u in self.faces[f].iter_mut() {
+          if *u == remove {
+            *u = keep;
+          }
+        }
+        self.vertex_faces[keep].push(f);
+      }
+    }
+    let alive = self.alive_faces(keep);
+    self.vertex_faces[keep] = alive;
+    for u in self.neighbors(keep) {
+      self.push_candidate(keep, u);
+    }
+    Some(removed)
+  }
+
+  // Interpolates texture coordinates and user-specified normals of the kept
+  // vertex at the projection of the target onto the collapsed edge, and
+  // returns the parameter of the projection along the edge.
+  fn interpolate_attributes(&mut self, keep: usize, remove: usize,
+      target: Vector3) -> f64 {
+    let (pk, pr) = (self.vertices[keep].pos, self.vertices[remove].pos);
+    let d = pr - pk;
+    let t = if d.len2() < constants::EPSILON_TINY {
+      0.0
+    } else {
+      ((target - pk) * d / d.len2()).clamp(0.0, 1.0)
+    };
+    if let (Some(uk), Some(ur)) =
+        (self.vertices[keep].uv, self.vertices[remove].uv) {
+      self.vertices[keep].uv = Some(uk * (1.0 - t) + ur * t);
+    }
+    if self.user_normals {
+      if let (Some(nk), Some(nr)) =
+          (self.vertices[keep].normal, self.vertices[remove].normal) {
+        let n = nk * (1.0 - t) + nr * t;
+        if n.len2() > constants::EPSILON_TINY {
+          self.vertices[keep].normal = Some(n.normalize());
+        }
+      }
+    }
+    t
+  }
+
+  // Interpolates corner texture coordinates along the collapsed edge, taken
+  // from a face sharing the edge, and assigns them to all corners of both
+  // vertices. Unless seams are not preserved, neither vertex is on a texture
+  // seam, and thus all their corners share the same texture coordinates.
+  fn interpolate_corner_uvs(&mut self, keep: usize, remove: usize, t: f64) {
+    let uv = self.alive_faces(keep).into_iter()
+        .filter(|f| self.faces[*f].contains(&remove))
+        .filter_map(|f| {
+          let uvs = self.face_uvs[f]?;
+          let i = self.faces[f].iter().position(|u| *u == keep).unwrap();
+          let j = self.faces[f].iter().position(|u| *u == remove).unwrap();
+          Some(uvs[i] * (1.0 - t) + uvs[j] * t)
+        })
+        .next();
+    let uv = match uv {
+      Some(uv) => uv,
+      None => return,
+    };
+    for v in [keep, remove].iter() {
+      for f in self.alive_faces(*v) {
+        if let Some(ref mut uvs) = self.face_uvs[f] {
+          for (corner, u) in uvs.iter_mut().zip(self.faces[f].iter()) {
+            if u == v {
+              *corner = uv;
+            }
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,554 @@
+use common::constants;
+use geometry::Plane;
+use geometry::triangular_mesh::{
+    Face, FaceNormalMode, TriangularMesh, Vertex};
+use math::{Vector2, Vector3};
+use std::collections::HashMap;
+
+/// A polyline along which a mesh intersects a plane, see
+/// TriangularMesh::cross_section().
+#[derive(Debug, Clone)]
+pub struct SectionPolyline {
+  points: Vec<Vector3>,
+  closed: bool,
+}
+
+impl SectionPolyline {
+  /// Returns the points of the polyline in order. The first point of a loop
+  /// is not repeated at its end.
+  pub fn points(&self) -> &[Vector3] {
+    &self.points
+  }
+
+  /// Returns whether the polyline is a closed loop. Polylines are open where
+  /// they end at boundaries of the mesh.
+  pub fn is_closed(&self) -> bool {
+    self.closed
+  }
+
+  /// Returns the length of the polyline, including the closing segment of a
+  /// loop.
+  pub fn length(&self) -> f64 {
+    let n = self.points.len();
+    let segments = if self.closed { n } else { n - 1 };
+    (0..segments)
+        .map(|i| (self.points[(i + 1) % n] - self.points[i]).len())
+        .sum()
+  }
+}
+
+// A point where an edge from a vertex below the plane to a vertex above it
+// crosses the plane, at a parameter t along the edge. Crossings at vertices
+// on the plane have below = above.
+struct Crossing {
+  below: usize,
+  above: usize,
+  t: f64,
+  pos: Vector3,
+}
+
+// The intersection of a mesh with a plane.
+struct Section {
+  // Signed distances of vertices to the plane. Vertices on the plane (within
+  // a tolerance) have distance 0, and count as above it.
+  distances: Vec<f64>,
+  crossings: Vec<Crossing>,
+  // Crossing of each half-edge from one side of the plane to the other.
+  edge_crossings: Vec<Option<usize>>,
+  // Chains of consecutive crossings, with whether they are closed.
+  chains: Vec<(Vec<usize>, bool)>,
+}
+
+// A corner of a face of a half, either at a vertex of the mesh, or where an
+// edge crosses the plane.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum Corner {
+  VERTEX(usize),
+  CROSSING(usize),
+}
+
+// Vertices and faces of a half of a split mesh.
+struct Half {
+  vertices: Vec<Vertex>,
+  faces: Vec<Face>,
+  ids: HashMap<Corner, usize>,
+}
+
+impl TriangularMesh {
+  /// Intersects the mesh with a plane, returning the polylines of the cross
+  /// section. They are traced through adjacent faces, so that each connected
+  /// piece of the intersection is one polyline. Vertices on the plane count
+  /// as being on its positive side, so that faces merely touching the plane
+  /// from the positive side, or lying in it, do not contribute. For closed
+  /// meshes with faces oriented outwards all polylines are loops, which run
+  /// counter-clockwise around the plane normal around the material, i.e.
+  /// clockwise around holes in the section.
+  pub fn cross_section(&self, plane: &Plane) -> Vec<SectionPolyline> {
+    let section = self.section(plane);
+    section.chains.iter()
+        .map(|&(ref chain, closed)| SectionPolyline {
+          points: chain.iter().map(|c| section.crossings[*c].pos).collect(),
+          closed: closed,
+        })
+        .collect()
+  }
+
+  /// Splits the mesh by a plane into the parts on its negative and positive
+  /// sides, in this order, see cross_section(). Faces crossing the plane are
+  /// cut along it, with normals and texture coordinates of new vertices and
+  /// corners interpolated along the edges. If capped, each closed loop of the
+  /// cross section is filled, by triangulating the polygons (with holes) it
+  /// bounds, with faces facing out of each part. The caps have their own
+  /// vertices on the rims, with the plane normal (or its negation) as vertex
+  /// normals, so that normals are sharp there. Thus halves of closed meshes
+  /// are closed geometrically but not topologically, see weld_vertices().
+  pub fn split_by_plane(&self, plane: &Plane, cap: bool)
+      -> (TriangularMesh, TriangularMesh) {
+    let section = self.section(plane);
+    let mut halves = [Half::new(), Half::new()];
+    for face in self.faces.iter() {
+      let v = face.vertices;
+      let above: Vec<bool> =
+          v.iter().map(|u| section.distances[*u] >= 0.0).collect();
+      if above[0] == above[1] && above[1] == above[2] {
+        let corners = [Corner::VERTEX(v[0]), Corner::VERTEX(v[1]),
+            Corner::VERTEX(v[2])];
+        halves[above[0] as usize].add_face(self, &section, face, corners,
+            face.uvs);
+        continue;
+      }
+      // The corner alone on its side, and the edges from and to it.
+      let i = (0..3).find(|i| above[*i] != above[(i + 1) % 3]
+          && above[*i] != above[(i + 2) % 3]).unwrap();
+      let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+      let ab = section.edge_crossings[face.edges[i]].unwrap();
+      let ca = section.edge_crossings[face.edges[k]].unwrap();
+      let uvs = face.uvs.map(|uvs| {
+        let lerp = |from: usize, to: usize| {
+          let (d0, d1) = (section.distances[v[from]],
+              section.distances[v[to]]);
+          uvs[from] + (uvs[to] - uvs[from]) * (d0 / (d0 - d1))
+        };
+        [uvs[i], uvs[j], uvs[k], lerp(i, j), lerp(k, i)]
+      });
+      let pick = |c: [usize; 3]| uvs.map(|uvs| [uvs[c[0]], uvs[c[1]],
+          uvs[c[2]]]);
+      let [a, b, c] = [Corner::VERTEX(v[i]), Corner::VERTEX(v[j]),
+          Corner::VERTEX(v[k])];
+      let (ab, ca) = (Corner::CROSSING(ab), Corner::CROSSING(ca));
+      halves[above[i] as usize].add_face(self, &section, face, [a, ab, ca],
+          pick([0, 3, 4]));
+      let other = &mut halves[above[j] as usize];
+      other.add_face(self, &section, face, [ab, b, c], pick([3, 1, 2]));
+      other.add_face(self, &section, face, [ab, c, ca], pick([3, 2, 4]));
+    }
+    if cap {
+      self.add_caps(plane, &section, &mut halves);
+    }
+
+    let [below, above] = halves;
+    (self.half_mesh(below), self.half_mesh(above))
+  }
+
+  // Computes the intersection of the mesh with a plane, tracing chains of
+  // crossings from face to face through twins of crossing half-edges.
+  fn section(&self, plane: &Plane) -> Section {
+    let extent = self.vertices.iter()
+        .map(|v| v.pos.x().abs().max(v.pos.y().abs()).max(v.pos.z().abs()))
+        .fold(0.0, f64::max);
+    let tolerance = constants::EPSILON_TINY * extent.max(1.0);
+    let distances: Vec<f64> = self.vertices.iter()
+        .map(|v| {
+          let d = plane.signed_distance(v.pos);
+          if d.abs() <= tolerance { 0.0 } else { d }
+        })
+        .collect();
+    let is_below = |v: usize| distances[v] < 0.0;
+
+    // Crossings are shared by twins, and by all edges to a vertex on the
+    // plane.
+    let mut crossings = Vec::new();
+    let mut ids: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut edge_crossings = vec![None; self.edges.len()];
+    for (e, edge) in self.edges.iter().enumerate() {
+      let (s, t) = (edge.start_vertex, edge.end_vertex);
+      if is_below(s) == is_below(t) {
+        continue;
+      }
+      let (below, above) = if is_below(s) { (s, t) } else { (t, s) };
+      let key = if distances[above] == 0.0 { (above, above) } else {
+        (below, above)
+      };
+      let id = *ids.entry(key).or_insert_with(|| {
+        let (d0, d1) = (distances[key.0], distances[key.1]);
+        let t = if key.0 == key.1 { 1.0 } else { d0 / (d0 - d1) };
+        let (p0, p1) = (self.vertices[below].pos, self.vertices[above].pos);
+        crossings.push(Crossing {
+          below: below,
+          above: above,
+          t: t,
+          pos: if t == 1.0 { p1 } else { p0 + (p1 - p0) * t },
+        });
+        crossings.len() - 1
+      });
+      edge_crossings[e] = Some(id);
+    }
+
+    // Each face crossing the plane has one half-edge going down and one going
+    // up, and its segment of the section runs from the first to the second,
+    // after which the section continues in the face across the latter. Chains
+    // starting at boundary edges are traced first, so that the remaining ones
+    // are loops.
+    let goes_down =
+        |e: usize| !is_below(self.edges[e].start_vertex)
+            && is_below(self.edges[e].end_vertex);
+    let mut starts: Vec<usize> =
+        (0..self.edges.len()).filter(|e| goes_down(*e)).collect();
+    starts.sort_by_key(|e| self.edges[*e].twin_edge.is_some());
+    let mut visited = vec![false; self.edges.len()];
+    let mut chains = Vec::new();
+    for &start in starts.iter() {
+      if visited[start] {
+        continue;
+      }
+      let mut chain: Vec<usize> = Vec::new();
+      let mut closed = false;
+      let mut e = start;
+      loop {
+        visited[e] = true;
+        chain.push(edge_crossings[e].unwrap());
+        let up = self.face_edges(self.edges[e].face)
+            .find(|u| edge_crossings[*u].is_some() && !goes_down(*u))
+            .unwrap();
+        match self.edges[up].twin_edge {
+          Some(next) if next == start => {
+            closed = true;
+            break;
+          },
+          Some(next) if !visited[next] && goes_down(next) => e = next,
+          _ => {
+            chain.push(edge_crossings[up].unwrap());
+            break;
+          },
+        }
+      }
+      // Consecutive crossings coincide around vertices on the plane.
+      chain.dedup();
+      if closed && chain.len() > 1 && chain[0] == chain[chain.len() - 1] {
+        chain.pop();
+      }
+      if chain.len() >= if closed { 3 } else { 2 } {
+        chains.push((chain, closed));
+      }
+    }
+
+    Section {
+      distances: distances,
+      crossings: crossings,
+      edge_crossings: edge_crossings,
+      chains: chains,
+    }
+  }
+
+  // Adds faces filling the loops of a section to both halves, facing along
+  // the plane normal for the negative half, and against it for the positive
+  // one.
+  fn add_caps(&self, plane: &Plane, section: &Section, halves: &mut [Half]) {
+    let n = plane.normal();
+    let e1 = Self::orthonormalize(Vector3::x_unit(), n)
+        .or_else(|| Self::orthonormalize(Vector3::y_unit(), n))
+        .unwrap();
+    let e2 = n ^ e1;
+    let loops: Vec<&Vec<usize>> = section.chains.iter()
+        .filter(|c| c.1)
+        .map(|c| &c.0)
+        .collect();
+    let projected: Vec<Vec<Vector2>> = loops.iter()
+        .map(|l| l.iter()
+            .map(|c| {
+              let p = section.crossings[*c].pos;
+              Vector2::new(p * e1, p * e2)
+            })
+            .collect())
+        .collect();
+    let triangles = triangulate_polygons(&projected);
+
+    for (side, half) in halves.iter_mut().enumerate() {
+      let normal = if side == 0 { n } else { -n };
+      let mut ids: HashMap<usize, usize> = HashMap::new();
+      for triangle in triangles.iter() {
+        let mut v = [0; 3];
+        for (id, &(l, k)) in v.iter_mut().zip(triangle.iter()) {
+          let c = loops[l][k];
+          *id = *ids.entry(c).or_insert_with(|| {
+            half.vertices.push(Vertex::new_with_normal(
+                section.crossings[c].pos, normal));
+            half.vertices.len() - 1
+          });
+        }
+        if side == 1 {
+          v.swap(1, 2);
+        }
+        let mut face = Face::new(v, [0; 3]);
+        if self.face_normal_mode == FaceNormalMode::USER_SPECIFIED {
+          face.normal = Some(normal);
+        }
+        half.faces.push(face);
+      }
+    }
+  }
+
+  // Builds a mesh from a half, with the normal modes of this mesh.
+  fn half_mesh(&self, half: Half) -> TriangularMesh {
+    let mut mesh = TriangularMesh {
+      vertices: Vec::new(),
+      edges: Vec::new(),
+      faces: Vec::new(),
+      vertex_normal_mode: self.vertex_normal_mode,
+      face_normal_mode: self.face_normal_mode,
+      normal_weighting: self.normal_weighting,
+    };
+    mesh.rebuild(half.vertices, half.faces);
+    mesh
+  }
+}
+
+impl Half {
+  fn new() -> Self {
+    Self {
+      vertices: Vec::new(),
+      faces: Vec::new(),
+      ids: HashMap::new(),
+    }
+  }
+
+  // Adds a piece of a face of the mesh with given corners and texture
+  // coordinates, unless it is degenerate, i.e. two corners are the same
+  // vertex on the plane.
+  fn add_face(&mut self, mesh: &TriangularMesh, section: &Section,
+      face: &Face, corners: [Corner; 3], uvs: Option<[Vector2; 3]>) {
+    let mut v = [0; 3];
+    for (id, corner) in v.iter_mut().zip(corners.iter()) {
+      *id = self.vertex(mesh, section, *corner);
+    }
+    if v[0] == v[1] || v[1] == v[2] || v[2] == v[0] {
+      return;
+    }
+    let mut piece = face.with_vertices(v);
+    piece.uvs = uvs;
+    self.faces.push(piece);
+  }
+
+  // Returns the index of the vertex at a corner, adding it if new. Vertices
+  // at crossings interpolate the attributes of the vertices of their edge.
+  fn vertex(&mut self, mesh: &TriangularMesh, section: &Section,
+      corner: Corner) -> usize {
+    let corner = match corner {
+      Corner::CROSSING(c) if section.crossings[c].t == 1.0 => {
+        Corner::VERTEX(section.crossings[c].above)
+      },
+      _ => corner,
+    };
+    if let Some(id) = self.ids.get(&corner) {
+      return *id;
+    }
+    let vertex = match corner {
+      Corner::VERTEX(v) => mesh.vertices[v].clone(),
+      Corner::CROSSING(c) => {
+        let crossing = &section.crossings[c];
+        let (a, b) = (&mesh.vertices[crossing.below],
+            &mesh.vertices[crossing.above]);
+        let mut vertex = Vertex::new(crossing.pos);
+        if let (Some(n0), Some(n1)) = (a.normal, b.normal) {
+          vertex.normal = Some((n0 + (n1 - n0) * crossing.t).normalize());
+        }
+        if let (Some(uv0), Some(uv1)) = (a.uv, b.uv) {
+          vertex.uv = Some(uv0 + (uv1 - uv0) * crossing.t);
+        }
+        vertex
+      },
+    };
+    self.vertices.push(vertex);
+    self.ids.insert(corner, self.vertices.len() - 1);
+    self.vertices.len() - 1
+  }
+}
+
+// Triangulates polygons with holes, given as loops of points running
+// counter-clockwise around the polygons and clockwise around their holes.
+// Each hole is connected to the innermost polygon containing it by a bridge,
+// and the resulting simple polygons are triangulated by ear clipping.
+// Returns counter-clockwise triangles as (loop, point) indices. For details
+// please read:
+//
+// > Triangulation by Ear Clipping. D. Eberly. 2002.
+fn triangulate_polygons(loops: &[Vec<Vector2>])
+    -> Vec<[(usize, usize); 3]> {
+  let areas: Vec<f64> = loops.iter().map(|l| signed_area(l)).collect();
+  let mut holes: Vec<Vec<usize>> = vec![Vec::new(); loops.len()];
+  for h in (0..loops.len()).filter(|h| areas[*h] < 0.0) {
+    let outer = (0..loops.len())
+        .filter(|o| areas[*o] > 0.0 && contains(&loops[*o], loops[h][0]))
+        .min_by(|a, b| areas[*a].partial_cmp(&areas[*b]).unwrap());
+    if let Some(o) = outer {
+      holes[o].push(h);
+    }
+  }
+
+  let mut triangles = Vec::new();
+  for o in (0..loops.len()).filter(|o| areas[*o] > 0.0) {
+    let mut polygon: Vec<(usize, usize)> =
+        (0..loops[o].len()).map(|k| (o, k)).collect();
+    // Holes are bridged from right to left, so that bridges do not cross
+    // holes yet to be bridged.
+    let max_u = |h: &usize| loops[*h].iter()
+        .map(|p| p.u())
+        .fold(-constants::INFINITY, f64::max);
+    holes[o].sort_by(|a, b| max_u(b).partial_cmp(&max_u(a)).unwrap());
+    for h in holes[o].iter() {
+      bridge_hole(&mut polygon, loops, *h);
+    }
+    clip_ears(polygon, loops, &mut triangles);
+  }
+  triangles
+}
+
+// Connects a hole to a polygon containing it by a bridge from the rightmost
+// point of the hole to a point of the polygon visible from it, traversed in
+// both directions.
+fn bridge_hole(polygon: &mut Vec<(usize, usize)>, loops: &[Vec<Vector2>],
+    h: usize) {
+  let at = |p: (usize, usize)| loops[p.0][p.1];
+  let hole = &loops[h];
+  let m = (0..hole.len())
+      .max_by(|a, b| hole[*a].u().partial_cmp(&hole[*b].u()).unwrap())
+      .unwrap();
+  let pm = hole[m];
+
+  // The nearest edge hit by the ray from the point to the right, and the
+  // endpoint of the edge furthest to the right.
+  let n = polygon.len();
+  let mut hit = constants::INFINITY;
+  let mut candidate = None;
+  for i in 0..n {
+    let (a, b) = (at(polygon[i]), at(polygon[(i + 1) % n]));
+    if (a.v() > pm.v()) == (b.v() > pm.v()) {
+      continue;
+    }
+    let u = a.u() + (pm.v() - a.v()) * (b.u() - a.u()) / (b.v() - a.v());
+    if u >= pm.u() && u < hit {
+      hit = u;
+      candidate = Some(if a.u() > b.u() { i } else { (i + 1) % n });
+    }
+  }
+  let mut visible = match candidate {
+    Some(i) => i,
+    None => return,
+  };
+  // Reflex points inside the triangle of the point, the hit and the
+  // candidate may block the view, in which case the one closest in angle to
+  // the ray is visible.
+  let (pi, pc) = (Vector2::new(hit, pm.v()), at(polygon[visible]));
+  let mut best = None;
+  for j in 0..n {
+    let q = at(polygon[j]);
+    let (prev, next) = (at(polygon[(j + n - 1) % n]), at(polygon[(j + 1) % n]));
+    if j == visible || cross(prev, q, next) >= 0.0
+        || !in_triangle(q, pm, pi, pc) {
+      continue;
+    }
+    let d = q - pm;
+    let key = (d.v().abs().atan2(d.u()), d.len2());
+    if best.is_none_or(|(k, _)| key < k) {
+      best = Some((key, j));
+    }
+  }
+  if let Some((_, j)) = best {
+    visible = j;
+  }
+
+  let mut bridged = Vec::with_capacity(n + hole.len() + 2);
+  bridged.extend_from_slice(&polygon[..visible + 1]);
+  bridged.extend((0..hole.len() + 1).map(|k| (h, (m + k) % hole.len())));
+  bridged.extend_from_slice(&polygon[visible..]);
+  *polygon = bridged;
+}
+
+// Triangulates a simple polygon, possibly touching itself along bridges, by
+// repeatedly clipping an ear, i.e. a convex corner whose triangle contains
+// no other point of the polygon.
+fn clip_ears(mut polygon: Vec<(usize, usize)>, loops: &[Vec<Vector2>],
+    triangles: &mut Vec<[(usize, usize); 3]>) {
+  let at = |p: (usize, usize)| loops[p.0][p.1];
+  let mut i = 0;
+  while polygon.len() > 3 {
+    let n = polygon.len();
+    let corner = |i: usize| (polygon[(i + n - 1) % n], polygon[i % n],
+        polygon[(i + 1) % n]);
+    let is_ear = |i: usize| {
+      let (a, b, c) = corner(i);
+      cross(at(a), at(b), at(c)) > 0.0 && polygon.iter().all(|p| {
+        *p == a || *p == b || *p == c || !in_triangle(at(*p), at(a), at(b),
+            at(c))
+      })
+    };
+    // Without ears due to rounding, the most convex corner is clipped.
+    let ear = (0..n).map(|k| (i + k) % n).find(|k| is_ear(*k))
+        .unwrap_or_else(|| (0..n)
+            .max_by(|x, y| {
+              let ((a, b, c), (d, e, f)) = (corner(*x), corner(*y));
+              cross(at(a), at(b), at(c))
+                  .partial_cmp(&cross(at(d), at(e), at(f))).unwrap()
+            })
+            .unwrap());
+    let (a, b, c) = corner(ear);
+    if cross(at(a), at(b), at(c)) > 0.0 {
+      triangles.push([a, b, c]);
+    }
+    polygon.remove(ear);
+    i = ear;
+  }
+  if polygon.len() == 3 && cross(at(polygon[0]), at(polygon[1]),
+      at(polygon[2])) > 0.0 {
+    triangles.push([polygon[0], polygon[1], polygon[2]]);
+  }
+}
+
+// Returns twice the signed area of the triangle (a, b, c), which is positive
+// if it is counter-clockwise.
+fn cross(a: Vector2, b: Vector2, c: Vector2) -> f64 {
+  let (ab, ac) = (b - a, c - a);
+  ab.u() * ac.v() - ab.v() * ac.u()
+}
+
+// Returns the signed area of a polygon, which is positive if it runs
+// counter-clockwise.
+fn signed_area(points: &[Vector2]) -> f64 {
+  let n = points.len();
+  (0..n).map(|i| {
+    let (a, b) = (points[i], points[(i + 1) % n]);
+    a.u() * b.v() - a.v() * b.u()
+  }).sum::<f64>() / 2.0
+}
+
+// Returns whether a point is inside a polygon, by the crossing number.
+fn contains(points: &[Vector2], p: Vector2) -> bool {
+  let n = points.len();
+  let mut inside = false;
+  for i in 0..n {
+    let (a, b) = (points[i], points[(i + 1) % n]);
+    if (a.v() > p.v()) != (b.v() > p.v())
+        && p.u() < a.u() + (p.v() - a.v()) * (b.u() - a.u()) / (b.v() - a.v()) {
+      inside = !inside;
+    }
+  }
+  inside
+}
+
+// Returns whether a point is inside a triangle of any orientation, or on its
+// boundary.
+fn in_triangle(p: Vector2, a: Vector2, b: Vector2, c: Vector2) -> bool {
+  let (d0, d1, d2) = (cross(a, b, p), cross(b, c, p), cross(c, a, p));
+  (d0 >= 0.0 && d1 >= 0.0 && d2 >= 0.0) || (d0 <= 0.0 && d1 <= 0.0 && d2 <= 0.0)
+}
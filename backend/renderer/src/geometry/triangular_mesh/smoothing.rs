@@ -0,0 +1,200 @@
+use geometry::triangular_mesh::TriangularMesh;
+use math::Vector3;
+
+/// Definition of different weights of neighbors in Laplacian smoothing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SmoothingWeighting {
+  /// All neighbors contribute equally. Vertices also move along the surface,
+  /// towards regular tessellations.
+  UNIFORM,
+  /// Each neighbor is weighted by (cot a + cot b) / 2, where a and b are the
+  /// angles opposite to the edge, see TriangularMesh::cotan_laplacian().
+  /// Vertices move (nearly) along the normals only, so that flat regions are
+  /// kept as they are. Negative weights of edges opposite to obtuse angles
+  /// are clamped to 0, which keeps the smoothing stable.
+  COTANGENT
+}
+
+/// Options of TriangularMesh::smooth_laplacian() and
+/// TriangularMesh::smooth_taubin(). By default, one iteration of uniform
+/// smoothing is made with lambda = 0.33 (and mu = -0.34 for Taubin
+/// smoothing), boundary vertices are kept in place, and normals are
+/// recomputed afterwards.
+#[derive(Debug, Copy, Clone)]
+pub struct SmoothingOptions {
+  weighting: SmoothingWeighting,
+  iterations: usize,
+  lambda: f64,
+  mu: f64,
+  keep_boundaries: bool,
+  recompute_normals: bool,
+}
+
+impl Default for SmoothingOptions {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SmoothingOptions {
+  pub fn new() -> Self {
+    Self {
+      weighting: SmoothingWeighting::UNIFORM,
+      iterations: 1,
+      lambda: 0.33,
+      mu: -0.34,
+      keep_boundaries: true,
+      recompute_normals: true,
+    }
+  }
+
+  pub fn set_weighting(&mut self, weighting: SmoothingWeighting) {
+    self.weighting = weighting;
+  }
+
+  /// Sets the number of iterations. A Taubin iteration consists of a
+  /// shrinking step and an inflating step.
+  pub fn set_iterations(&mut self, iterations: usize) {
+    self.iterations = iterations;
+  }
+
+  /// Sets the factor in (0, 1] by which vertices move towards the weighted
+  /// average of their neighbors in each step.
+  pub fn set_lambda(&mut self, lambda: f64) {
+    assert!(lambda > 0.0 && lambda <= 1.0, "Lambda must be in (0, 1]!");
+    self.lambda = lambda;
+  }
+
+  /// Sets the negative factor of the inflating steps of Taubin smoothing,
+  /// which must satisfy mu < -lambda. The pass-band frequency 1 / lambda +
+  /// 1 / mu is typically between 0.01 and 0.1.
+  pub fn set_mu(&mut self, mu: f64) {
+    assert!(mu < 0.0, "Mu must be negative!");
+    self.mu = mu;
+  }
+
+  /// Sets whether boundary vertices are kept in place. Otherwise they are
+  /// smoothed along the boundary, i.e. towards their neighbors on the
+  /// boundary only, so that open surfaces do not shrink inwards.
+  pub fn set_keep_boundaries(&mut self, keep: bool) {
+    self.keep_boundaries = keep;
+  }
+
+  /// Sets whether normals (and tangent frames) are recomputed afterwards,
+  /// see TriangularMesh::recompute_normals(). Otherwise they are left as they
+  /// were, e.g. when more vertices are to be moved.
+  pub fn set_recompute_normals(&mut self, recompute: bool) {
+    self.recompute_normals = recompute;
+  }
+}
+
+impl TriangularMesh {
+  /// Smooths the mesh by repeatedly moving each vertex towards the weighted
+  /// average of its neighbors:
+  ///
+  /// > x_i += lambda * (sum_j w_ij * x_j / sum_j w_ij - x_i).
+  ///
+  /// This removes high-frequency noise, but also shrinks the surface in
+  /// every step, see smooth_taubin(). Isolated vertices are kept in place.
+  /// For details please read:
+  ///
+  /// > Implicit Fairing of Irregular Meshes using Diffusion and Curvature
+  /// > Flow. M. Desbrun, M. Meyer, P. Schröder, A. H. Barr. SIGGRAPH 1999.
+  pub fn smooth_laplacian(&mut self, options: &SmoothingOptions) {
+    for _ in 0..options.iterations {
+      self.smoothing_step(options.lambda, options);
+    }
+    if options.recompute_normals {
+      self.recompute_normals();
+    }
+  }
+
+  /// Smooths the mesh like smooth_laplacian(), but follows each shrinking
+  /// step (by lambda) with an inflating step (by mu < -lambda), which
+  /// together act as a low-pass filter, removing noise without shrinking
+  /// the surface. For details please read:
+  ///
+  /// > A Signal Processing Approach to Fair Surface Design.
+  /// > G. Taubin. SIGGRAPH 1995.
+  pub fn smooth_taubin(&mut self, options: &SmoothingOptions) {
+    assert!(options.mu < -options.lambda, "Mu must be less than -lambda!");
+    for _ in 0..options.iterations {
+      self.smoothing_step(options.lambda, options);
+      self.smoothing_step(options.mu, options);
+    }
+    if options.recompute_normals {
+      self.recompute_normals();
+    }
+  }
+
+  // Moves all vertices by a factor towards the weighted average of their
+  // neighbors, computed from the positions before the step.
+  fn smoothing_step(&mut self, factor: f64, options: &SmoothingOptions) {
+    let cotangents: Vec<[f64; 3]> = match options.weighting {
+      SmoothingWeighting::UNIFORM => Vec::new(),
+      SmoothingWeighting::COTANGENT => {
+        (0..self.faces.len()).map(|f| self.face_cotangents(f)).collect()
+      },
+    };
+    // Neighbors of boundary vertices along the boundary.
+    let mut boundary_neighbors: Vec<Vec<usize>> =
+        vec![Vec::new(); self.vertices.len()];
+    for edge in self.edges.iter().filter(|e| e.twin_edge.is_none()) {
+      boundary_neighbors[edge.start_vertex].push(edge.end_vertex);
+      boundary_neighbors[edge.end_vertex].push(edge.start_vertex);
+    }
+
+    let positions: Vec<Vector3> = self.vertices.iter().map(|v| v.pos).collect();
+    for v in 0..self.vertices.len() {
+      let average = if !boundary_neighbors[v].is_empty() {
+        if options.keep_boundaries {
+          continue;
+        }
+        let neighbors = &boundary_neighbors[v];
+        neighbors.iter().fold(Vector3::zero(), |s, u| s + positions[*u])
+            / neighbors.len() as f64
+      } else {
+        match self.neighbor_average(v, &positions, &cotangents) {
+          Some(average) => average,
+          None => continue,
+        }
+      };
+      self.vertices[v].pos = positions[v] + (average - positions[v]) * factor;
+    }
+  }
+
+  // Returns the weighted average of the neighbors of an interior vertex,
+  // with uniform weights if no cotangents of faces are given, or if all
+  // cotangent weights vanish. Returns None for isolated vertices.
+  fn neighbor_average(&self, v: usize, positions: &[Vector3],
+      cotangents: &[[f64; 3]]) -> Option<Vector3> {
+    let mut sum = Vector3::zero();
+    let mut weights = 0.0;
+    if !cotangents.is_empty() {
+      // Angles opposite to an outgoing half-edge and its twin.
+      let opposite_cot = |e: usize| {
+        let f = self.edges[e].face;
+        let i = self.face_edges(f).position(|x| x == e).unwrap();
+        cotangents[f][(i + 2) % 3]
+      };
+      for e in self.outgoing_edges(v) {
+        let edge = &self.edges[e];
+        let cot = opposite_cot(e) + edge.twin_edge.map_or(0.0, opposite_cot);
+        let w = (cot / 2.0).max(0.0);
+        sum += positions[edge.end_vertex] * w;
+        weights += w;
+      }
+    }
+    if weights <= 0.0 {
+      sum = Vector3::zero();
+      for u in self.vertex_neighbors(v) {
+        sum += positions[u];
+        weights += 1.0;
+      }
+    }
+    if weights <= 0.0 {
+      return None;
+    }
+    Some(sum * (1.0 / weights))
+  }
+}
@@ -0,0 +1,314 @@
+use geometry::triangular_mesh::{TriangularMesh, Vertex};
+use math::Vector3;
+use std::collections::HashSet;
+use std::f64::consts::PI;
+
+/// Options of TriangularMesh::subdivide_loop() and
+/// TriangularMesh::loop_limit_positions(). By default, the mesh is subdivided
+/// once, only boundary edges are creases, and vertices are not projected onto
+/// the limit surface.
+#[derive(Debug, Clone)]
+pub struct SubdivisionOptions {
+  levels: usize,
+  crease_edges: Vec<[usize; 2]>,
+  crease_angle: f64,
+  project_to_limit: bool,
+}
+
+impl Default for SubdivisionOptions {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SubdivisionOptions {
+  pub fn new() -> Self {
+    Self {
+      levels: 1,
+      crease_edges: Vec::new(),
+      crease_angle: PI,
+      project_to_limit: false,
+    }
+  }
+
+  /// Sets the number of times the mesh is subdivided. Each level splits every
+  /// face into four.
+  pub fn set_levels(&mut self, levels: usize) {
+    self.levels = levels;
+  }
+
+  /// Marks the edge between two vertices as a crease (i.e. a sharp edge),
+  /// which is subdivided as a uniform cubic B-spline curve, independently of
+  /// the surface on either side. Boundary edges are always creases.
+  pub fn add_crease_edge(&mut self, v0: usize, v1: usize) {
+    self.crease_edges.push([v0, v1]);
+  }
+
+  /// Sets the dihedral angle (in radians, between the normals of the two
+  /// adjacent faces) above which edges are creases as well. Defaults to pi,
+  /// i.e. no edges are creases by their angles.
+  pub fn set_crease_angle(&mut self, angle: f64) {
+    self.crease_angle = angle;
+  }
+
+  /// Sets whether vertices are moved onto the limit surface after the last
+  /// level, see TriangularMesh::loop_limit_positions().
+  pub fn set_project_to_limit(&mut self, project: bool) {
+    self.project_to_limit = project;
+  }
+}
+
+impl TriangularMesh {
+  /// Returns a new mesh subdivided by Loop's scheme, which converges to a
+  /// smooth (C2 except at extraordinary vertices) surface. Each level splits
+  /// every face into four by inserting a vertex on each edge:
+  ///
+  /// - An edge vertex is 3/8 * (a + b) + 1/8 * (c + d), where a and b are the
+  ///   ends of the edge and c and d the opposite vertices of its faces, or
+  ///   (a + b) / 2 on creases.
+  /// - An old vertex of valence n is (1 - n * beta) * v + beta * (sum of
+  ///   neighbors), where beta = (5/8 - (3/8 + cos(2 * pi / n) / 4)^2) / n. A
+  ///   vertex on exactly two creases is 3/4 * v + 1/8 * (a + b), where a and b
+  ///   are its neighbors along the creases, and a vertex on more than two
+  ///   creases (a corner) is kept in place.
+  ///
+  /// For details please read:
+  ///
+  /// > Smooth Subdivision Surfaces Based on Triangles. C. Loop. 1987.
+  /// > Piecewise Smooth Surface Reconstruction. H. Hoppe, T. DeRose,
+  /// > T. Duchamp, M. Halstead, H. Jin, J. McDonald, J. Schweitzer,
+  /// > W. Stuetzle. SIGGRAPH 1994.
+  ///
+  /// Old vertices keep their indices, followed by the edge vertices. Faces
+  /// #4f to #4f+3 subdivide face #f, as (v0, e01, e20), (e01, v1, e12),
+  /// (e20, e12, v2) and (e01, e12, e20), where eij is the vertex on the edge
+  /// from vertex #i to #j of the face. Texture coordinates (including corner
+  /// ones), user-specified vertex normals (normalized) and user-specified
+  /// face normals (copied) are interpolated linearly, and other normals are
+  /// recomputed.
+  pub fn subdivide_loop(&self, options: &SubdivisionOptions) -> TriangularMesh {
+    let mut mesh = self.clone();
+    let mut creases = self.crease_flags(options);
+    for _ in 0..options.levels {
+      let (next, next_creases) = mesh.loop_step(&creases);
+      mesh = next;
+      creases = next_creases;
+    }
+    if options.project_to_limit {
+      let positions = mesh.limit_positions(&creases);
+      for (vertex, p) in mesh.vertices.iter_mut().zip(positions) {
+        vertex.pos = p;
+      }
+      mesh.recompute_normals();
+    }
+    mesh
+  }
+
+  /// Returns the positions of all vertices on the limit surface of Loop
+  /// subdivision, i.e. where they converge after infinitely many levels (see
+  /// subdivide_loop() for creases):
+  ///
+  /// - A vertex of valence n goes to (1 - n * chi) * v + chi * (sum of
+  ///   neighbors), where chi = 1 / (3 / (8 * beta) + n).
+  /// - A vertex on exactly two creases goes to 2/3 * v + 1/6 * (a + b).
+  /// - A corner stays in place.
+  ///
+  /// The number of levels and whether to project are ignored.
+  pub fn loop_limit_positions(&self, options: &SubdivisionOptions)
+      -> Vec<Vector3> {
+    self.limit_positions(&self.crease_flags(options))
+  }
+
+  // Returns whether each half-edge is a crease, either on the boundary,
+  // marked in the options, or with a dihedral angle above the crease angle.
+  fn crease_flags(&self, options: &SubdivisionOptions) -> Vec<bool> {
+    let marked: HashSet<(usize, usize)> = options.crease_edges.iter()
+        .map(|e| (e[0].min(e[1]), e[0].max(e[1])))
+        .collect();
+    let mut found = HashSet::new();
+    let mut creases = vec![false; self.edges.len()];
+    for (e, edge) in self.edges.iter().enumerate() {
+      let key = (edge.start_vertex.min(edge.end_vertex),
+          edge.start_vertex.max(edge.end_vertex));
+      let twin = match edge.twin_edge {
+        Some(twin) => twin,
+        None => {
+          creases[e] = true;
+          found.insert(key);
+          continue;
+        },
+      };
+      if marked.contains(&key) {
+        creases[e] = true;
+        found.insert(key);
+      } else if options.crease_angle < PI {
+        let n0 = self.triangle(edge.face).try_normal();
+        let n1 = self.triangle(self.edges[twin].face).try_normal();
+        if let (Some(n0), Some(n1)) = (n0, n1) {
+          creases[e] = (n0 * n1).clamp(-1.0, 1.0).acos() > options.crease_angle;
+        }
+      }
+    }
+    assert!(marked.iter().all(|key| found.contains(key)),
+        "Crease edges must be edges of the mesh!");
+    creases
+  }
+
+  // Returns the neighbors of each vertex along creases.
+  fn crease_neighbors(&self, creases: &[bool]) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); self.vertices.len()];
+    for (e, edge) in self.edges.iter().enumerate() {
+      // Each spatial edge is visited once, through its half-edge of the
+      // smaller index.
+      if !creases[e] || edge.twin_edge.is_some_and(|twin| twin < e) {
+        continue;
+      }
+      neighbors[edge.start_vertex].push(edge.end_vertex);
+      neighbors[edge.end_vertex].push(edge.start_vertex);
+    }
+    neighbors
+  }
+
+  // Subdivides the mesh once, returning the new mesh and the crease flags of
+  // its half-edges.
+  fn loop_step(&self, creases: &[bool]) -> (TriangularMesh, Vec<bool>) {
+    let crease_neighbors = self.crease_neighbors(creases);
+    let mut vertices: Vec<Vertex> = Vec::with_capacity(
+        self.vertices.len() + self.edges.len() / 2);
+    for (v, vertex) in self.vertices.iter().enumerate() {
+      let mut new_vertex = vertex.clone();
+      new_vertex.pos = self.loop_vertex_position(v, &crease_neighbors[v]);
+      vertices.push(new_vertex);
+    }
+
+    let mut edge_vertices: Vec<Option<usize>> = vec![None; self.edges.len()];
+    for (e, edge) in self.edges.iter().enumerate() {
+      if edge_vertices[e].is_some() {
+        continue;
+      }
+      let (a, b) = (&self.vertices[edge.start_vertex],
+          &self.vertices[edge.end_vertex]);
+      let pos = match edge.twin_edge {
+        Some(twin) if !creases[e] => {
+          let c = self.edges[edge.next_edge].end_vertex;
+          let d = self.edges[self.edges[twin].next_edge].end_vertex;
+          (a.pos + b.pos) * (3.0 / 8.0)
+              + (self.vertices[c].pos + self.vertices[d].pos) * (1.0 / 8.0)
+        },
+        _ => (a.pos + b.pos) * 0.5,
+      };
+      let mut vertex = Vertex::new(pos);
+      if let (Some(n0), Some(n1)) = (a.normal, b.normal) {
+        vertex.normal = Some((n0 + n1).normalize());
+      }
+      if let (Some(uv0), Some(uv1)) = (a.uv, b.uv) {
+        vertex.uv = Some((uv0 + uv1) * 0.5);
+      }
+      edge_vertices[e] = Some(vertices.len());
+      if let Some(twin) = edge.twin_edge {
+        edge_vertices[twin] = Some(vertices.len());
+      }
+      vertices.push(vertex);
+    }
+
+    let mut faces = Vec::with_capacity(self.faces.len() * 4);
+    let mut next_creases = vec![false; self.faces.len() * 12];
+    for (f, face) in self.faces.iter().enumerate() {
+      let [a, b, c] = face.vertices;
+      let [e_ab, e_bc, e_ca] = face.edges;
+      let (ab, bc, ca) = (edge_vertices[e_ab].unwrap(),
+          edge_vertices[e_bc].unwrap(), edge_vertices[e_ca].unwrap());
+      let children = [[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]];
+      let child_uvs = face.uvs.map(|[uv_a, uv_b, uv_c]| {
+        let (uv_ab, uv_bc, uv_ca) =
+            ((uv_a + uv_b) * 0.5, (uv_b + uv_c) * 0.5, (uv_c + uv_a) * 0.5);
+        [[uv_a, uv_ab, uv_ca], [uv_ab, uv_b, uv_bc], [uv_ca, uv_bc, uv_c],
+            [uv_ab, uv_bc, uv_ca]]
+      });
+      for (k, child) in children.iter().enumerate() {
+        let mut child_face = face.with_vertices(*child);
+        child_face.uvs = child_uvs.map(|uvs| uvs[k]);
+        faces.push(child_face);
+      }
+      // Half-edges of the children along the edges of the face, see
+      // TriangularMeshBuilder::build() for their indices.
+      let base = 12 * f;
+      next_creases[base] = creases[e_ab];
+      next_creases[base + 2] = creases[e_ca];
+      next_creases[base + 3] = creases[e_ab];
+      next_creases[base + 4] = creases[e_bc];
+      next_creases[base + 7] = creases[e_bc];
+      next_creases[base + 8] = creases[e_ca];
+    }
+
+    let mut mesh = TriangularMesh {
+      vertices: Vec::new(),
+      edges: Vec::new(),
+      faces: Vec::new(),
+      vertex_normal_mode: self.vertex_normal_mode,
+      face_normal_mode: self.face_normal_mode,
+      normal_weighting: self.normal_weighting,
+    };
+    mesh.rebuild(vertices, faces);
+    (mesh, next_creases)
+  }
+
+  // Returns the new position of an old vertex in a subdivision step, given
+  // its neighbors along creases.
+  fn loop_vertex_position(&self, v: usize, crease_neighbors: &[usize])
+      -> Vector3 {
+    let p = self.vertices[v].pos;
+    match crease_neighbors.len() {
+      0 | 1 => {
+        let (n, sum) = self.one_ring_sum(v);
+        if n == 0 {
+          return p;
+        }
+        let beta = loop_beta(n);
+        p * (1.0 - n as f64 * beta) + sum * beta
+      },
+      2 => p * 0.75 + (self.vertices[crease_neighbors[0]].pos
+          + self.vertices[crease_neighbors[1]].pos) * 0.125,
+      _ => p,
+    }
+  }
+
+  // Returns the positions of all vertices on the limit surface.
+  fn limit_positions(&self, creases: &[bool]) -> Vec<Vector3> {
+    let crease_neighbors = self.crease_neighbors(creases);
+    (0..self.vertices.len())
+        .map(|v| {
+          let p = self.vertices[v].pos;
+          let neighbors = &crease_neighbors[v];
+          match neighbors.len() {
+            0 | 1 => {
+              let (n, sum) = self.one_ring_sum(v);
+              if n == 0 {
+                return p;
+              }
+              let chi = 1.0 / (3.0 / (8.0 * loop_beta(n)) + n as f64);
+              p * (1.0 - n as f64 * chi) + sum * chi
+            },
+            2 => p * (2.0 / 3.0) + (self.vertices[neighbors[0]].pos
+                + self.vertices[neighbors[1]].pos) * (1.0 / 6.0),
+            _ => p,
+          }
+        })
+        .collect()
+  }
+
+  // Returns the valence of a vertex and the sum of its neighbors.
+  fn one_ring_sum(&self, v: usize) -> (usize, Vector3) {
+    self.vertex_neighbors(v)
+        .fold((0, Vector3::zero()), |(n, sum), u| {
+          (n + 1, sum + self.vertices[u].pos)
+        })
+  }
+}
+
+// Returns the weight of each neighbor of a vertex of valence n in Loop
+// subdivision.
+fn loop_beta(n: usize) -> f64 {
+  let c = 3.0 / 8.0 + (2.0 * PI / n as f64).cos() / 4.0;
+  (5.0 / 8.0 - c * c) / n as f64
+}
@@ -0,0 +1,202 @@
+use common::constants;
+use geometry::triangular_mesh::{TriangularMesh, FaceNormalMode};
+use math::{Vector2, Vector3};
+
+/// A tangent frame at a corner of a face, used to transform normals from
+/// normal maps (in tangent space) to object space:
+///
+/// > n_object = x * tangent + y * bitangent + z * normal,
+///
+/// where (x, y, z) is the normal sampled from the normal map. The tangent is
+/// a unit vector orthogonal to the shading normal, and the bitangent is
+///
+/// > bitangent = sign * (normal ^ tangent),
+///
+/// where the sign is -1 if the texture is mirrored, and 1 otherwise.
+#[derive(Debug, Copy, Clone)]
+pub struct TangentFrame {
+  tangent: Vector3,
+  bitangent: Vector3,
+  sign: f64,
+}
+
+impl TangentFrame {
+  pub fn tangent(&self) -> Vector3 {
+    self.tangent
+  }
+
+  pub fn bitangent(&self) -> Vector3 {
+    self.bitangent
+  }
+
+  /// Returns the handedness of the frame, either 1 or -1.
+  pub fn sign(&self) -> f64 {
+    self.sign
+  }
+}
+
+impl PartialEq for TangentFrame {
+  fn eq(&self, other: &Self) -> bool {
+    self.tangent == other.tangent && self.bitangent == other.bitangent
+        && self.sign == other.sign
+  }
+}
+
+impl TriangularMesh {
+  /// Returns texture coordinates of the three corners of a face, i.e. the
+  /// corner texture coordinates of the face if given, otherwise those of its
+  /// vertices. Returns None if any of them is missing.
+  pub fn corner_uvs(&self, f: usize) -> Option<[Vector2; 3]> {
+    if let Some(uvs) = self.faces[f].uvs {
+      return Some(uvs);
+    }
+    let v = self.faces[f].vertices;
+    Some([self.vertices[v[0]].uv?, self.vertices[v[1]].uv?,
+        self.vertices[v[2]].uv?])
+  }
+
+  /// Recomputes tangent frames at all corners of textured faces, following
+  /// the conventions of MikkTSpace, so that normal maps baked by common tools
+  /// are reproduced:
+  ///
+  /// - The tangent of each face is dp/du, projected onto the tangent plane of
+  ///   the shading normal at each corner (the vertex normal in PHONG mode if
+  ///   available, otherwise the face normal) and normalized.
+  /// - Corners sharing a vertex, a normal, texture coordinates and
+  ///   handedness are merged: their tangents are averaged, weighted by the
+  ///   angles of the corners.
+  /// - The bitangent is derived from the tangent, the normal and the
+  ///   handedness, see TangentFrame, rather than averaged separately.
+  ///
+  /// For details please read:
+  ///
+  /// > Simulation of Wrinkled Surfaces Revisited. M. Mikkelsen. 2008.
+  ///
+  /// Vertices whose corners are all merged (i.e. not on texture seams) also
+  /// get the tangent frame, see Vertex::tangent_frame(). This is called when
+  /// normals are recomputed.
+  pub fn recompute_tangent_frames(&mut self) {
+    for v in 0..self.vertices.len() {
+      self.refresh_tangent_frame(v);
+    }
+  }
+
+  // Recomputes tangent frames at corners of given vertices only, after a
+  // local edit of the mesh.
+  pub(super) fn refresh_tangent_frames(&mut self, vertices: &[usize]) {
+    for v in vertices.iter() {
+      self.refresh_tangent_frame(*v);
+    }
+  }
+
+  // Recomputes tangent frames at all corners of a vertex, see
+  // recompute_tangent_frames().
+  fn refresh_tangent_frame(&mut self, v: usize) {
+    // Corners (face, index) merged into groups, each with its key attributes
+    // and accumulated tangent.
+    struct Group {
+      uv: Vector2,
+      normal: Vector3,
+      sign: f64,
+      tangent: Vector3,
+      corners: Vec<(usize, usize)>,
+    }
+    let mut groups: Vec<Group> = Vec::new();
+    let mut all_merged = true;
+    let faces: Vec<usize> = self.vertex_faces(v).collect();
+    for f in faces {
+      let i = self.faces[f].vertices.iter().position(|u| *u == v).unwrap();
+      self.faces[f].tangent_frames[i] = None;
+      let uvs = match self.corner_uvs(f) {
+        Some(uvs) => uvs,
+        None => {
+          all_merged = false;
+          continue;
+        },
+      };
+      let face_normal = self.faces[f].normal;
+      let normal = match self.face_normal_mode {
+        FaceNormalMode::PHONG => self.vertices[v].normal.or(face_normal),
+        _ => face_normal,
+      };
+      let derivatives = self.uv_derivatives(f);
+      let (normal, (dpdu, _, sign)) = match (normal, derivatives) {
+        (Some(n), Some(d)) => (n, d),
+        _ => {
+          all_merged = false;
+          continue;
+        },
+      };
+      let angle = self.triangle(f).angle(i);
+      let t = Self::orthonormalize(dpdu, normal).unwrap_or(Vector3::zero());
+      let group = groups.iter().position(|g| g.sign == sign
+          && (g.uv - uvs[i]).len() < constants::EPSILON && g.normal == normal);
+      match group {
+        Some(g) => {
+          groups[g].tangent += t * angle;
+          groups[g].corners.push((f, i));
+        },
+        None => groups.push(Group {
+          uv: uvs[i],
+          normal: normal,
+          sign: sign,
+          tangent: t * angle,
+          corners: vec![(f, i)],
+        }),
+      }
+    }
+
+    self.vertices[v].tangent_frame = None;
+    for g in groups.iter() {
+      let tangent = match Self::orthonormalize(g.tangent, g.normal) {
+        Some(t) => t,
+        None => {
+          all_merged = false;
+          continue;
+        },
+      };
+      let frame = TangentFrame {
+        tangent: tangent,
+        bitangent: (g.normal ^ tangent) * g.sign,
+        sign: g.sign,
+      };
+      for &(f, i) in g.corners.iter() {
+        self.faces[f].tangent_frames[i] = Some(frame);
+      }
+      if groups.len() == 1 && all_merged {
+        self.vertices[v].tangent_frame = Some(frame);
+      }
+    }
+  }
+
+  // Returns the (unnormalized) partial derivatives of the position with
+  // respect to the texture coordinates over a face, i.e. dp/du and dp/dv,
+  // together with the handedness of the texture mapping (-1 if mirrored).
+  // Returns None if the face has no or degenerate texture coordinates.
+  pub(super) fn uv_derivatives(&self, f: usize)
+      -> Option<(Vector3, Vector3, f64)> {
+    let uvs = self.corner_uvs(f)?;
+    let t = self.triangle(f);
+    let dp1 = t.e(0);
+    let dp2 = -t.e(2);
+    let duv1 = uvs[1] - uvs[0];
+    let duv2 = uvs[2] - uvs[0];
+    let det = duv1.u() * duv2.v() - duv1.v() * duv2.u();
+    if det.abs() < constants::EPSILON_TINY {
+      return None;
+    }
+    let dpdu = (dp1 * duv2.v() - dp2 * duv1.v()) * (1.0 / det);
+    let dpdv = (dp2 * duv1.u() - dp1 * duv2.u()) * (1.0 / det);
+    Some((dpdu, dpdv, if det > 0.0 { 1.0 } else { -1.0 }))
+  }
+
+  // Projects a vector onto the plane orthogonal to a unit normal and
+  // normalizes it, or returns None if it is (nearly) parallel to the normal.
+  pub(super) fn orthonormalize(v: Vector3, normal: Vector3) -> Option<Vector3> {
+    let t = v - normal * (normal * v);
+    if t.len2() < constants::EPSILON_TINY * v.len2().max(constants::EPSILON) {
+      return None;
+    }
+    Some(t * (1.0 / t.len()))
+  }
+}
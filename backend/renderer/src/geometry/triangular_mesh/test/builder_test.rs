@@ -0,0 +1,134 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder, MeshBuildError};
+use math::{Vector2, Vector3};
+
+// Builds a tetrahedron with outward-facing (counter-clockwise) faces.
+fn build_tetrahedron() -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::z_unit()]);
+  builder.add_faces(&[[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]]);
+  builder.build().unwrap()
+}
+
+// Checks the basic invariants of the half-edge structure.
+fn check_links(mesh: &TriangularMesh) {
+  for id in 0..mesh.num_edges() {
+    let e = mesh.edge(id);
+    assert_eq!(mesh.edge(e.next_edge()).prev_edge(), id);
+    assert_eq!(mesh.edge(e.prev_edge()).next_edge(), id);
+    assert_eq!(mesh.edge(e.next_edge()).start_vertex(), e.end_vertex());
+    assert_eq!(mesh.edge(e.next_edge()).face(), e.face());
+    assert!(mesh.outgoing_edges(e.start_vertex()).any(|x| x == id));
+    if let Some(twin) = e.twin_edge() {
+      assert_eq!(mesh.edge(twin).twin_edge(), Some(id));
+      assert_eq!(mesh.edge(twin).start_vertex(), e.end_vertex());
+      assert_eq!(mesh.edge(twin).end_vertex(), e.start_vertex());
+    }
+  }
+}
+
+#[test]
+fn test_build_closed_mesh() {
+  let mesh = build_tetrahedron();
+  assert_eq!(mesh.num_vertices(), 4);
+  assert_eq!(mesh.num_faces(), 4);
+  assert_eq!(mesh.num_edges(), 12);
+  check_links(&mesh);
+  assert!((0..mesh.num_edges()).all(|e| !mesh.edge(e).is_boundary()));
+  assert!((0..4).all(|v| mesh.outgoing_edges(v).count() == 3));
+
+  let f = mesh.face(3);
+  assert_eq!(f.vertices(), [1, 2, 3]);
+  let edges: Vec<usize> = mesh.face_edges(3).collect();
+  assert_eq!(mesh.edge(edges[1]).start_vertex(), 2);
+  assert_eq!(mesh.edge(edges[1]).end_vertex(), 3);
+  assert_eq!(mesh.triangle(3).normal(), Vector3::identity(1.0 / 3.0f64.sqrt()));
+  assert_eq!(mesh.vertex(1).pos(), Vector3::x_unit());
+  assert!(mesh.vertex(1).normal().is_some());
+}
+
+#[test]
+fn test_build_open_mesh() {
+  // A unit square split into two triangles, with normals.
+  let mut builder = TriangularMeshBuilder::new();
+  for p in [Vector3::zero(), Vector3::x_unit(), Vector3::new(1.0, 1.0, 0.0),
+      Vector3::y_unit()].iter() {
+    builder.add_vertex_with_normal(*p, Vector3::z_unit());
+  }
+  assert_eq!(builder.add_face(0, 1, 2), 0);
+  assert_eq!(builder.add_face(0, 2, 3), 1);
+  let mesh = builder.build().unwrap();
+  check_links(&mesh);
+
+  let boundary = (0..mesh.num_edges())
+      .filter(|e| mesh.edge(*e).is_boundary())
+      .count();
+  assert_eq!(boundary, 4);
+  // The diagonal 0 -> 2 is the only interior edge.
+  assert_eq!(mesh.edge(2).start_vertex(), 2);
+  assert_eq!(mesh.edge(2).twin_edge(), Some(3));
+  assert_eq!(mesh.outgoing_edges(0).collect::<Vec<_>>(), vec![0, 3]);
+  assert_eq!(mesh.vertex(3).normal(), Some(Vector3::z_unit()));
+
+  // Flipping the second face makes orientations inconsistent, and the
+  // diagonal is no longer linked.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[Vector3::zero(), Vector3::x_unit(),
+      Vector3::new(1.0, 1.0, 0.0), Vector3::y_unit()]);
+  builder.add_faces(&[[0, 1, 2], [0, 3, 2]]);
+  let mesh = builder.build().unwrap();
+  check_links(&mesh);
+  assert!((0..mesh.num_edges()).all(|e| mesh.edge(e).is_boundary()));
+}
+
+#[test]
+fn test_build_errors() {
+  let positions = [Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::z_unit(), -Vector3::z_unit()];
+
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&positions);
+  builder.add_faces(&[[0, 1, 2], [0, 1, 5]]);
+  let err = builder.build().unwrap_err();
+  assert_eq!(err, MeshBuildError::VertexIndexOutOfRange {
+    face: 1,
+    vertex: 5,
+    num_vertices: 5,
+  });
+  assert_eq!(format!("{}", err),
+      "Face #1 refers to vertex #5, but there are only 5 vertices");
+
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&positions);
+  builder.add_face(2, 3, 2);
+  assert_eq!(builder.build().unwrap_err(),
+      MeshBuildError::RepeatedVertexInFace { face: 0, vertex: 2 });
+
+  // Three faces sharing the edge between vertex 0 and 1.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&positions);
+  builder.add_faces(&[[0, 1, 2], [1, 0, 3], [0, 1, 4]]);
+  let err = builder.build().unwrap_err();
+  assert_eq!(err, MeshBuildError::NonManifoldEdge {
+    vertices: (0, 1),
+    faces: vec![0, 1, 2],
+  });
+  assert!(format!("{}", err).contains("shared by 3 faces"));
+
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&positions);
+  builder.add_face(0, 1, 2);
+  builder.set_vertex_uv(5, Vector2::zero());
+  assert_eq!(builder.build().unwrap_err(),
+      MeshBuildError::UvVertexOutOfRange { vertex: 5, num_vertices: 5 });
+
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&positions);
+  builder.add_face(0, 1, 2);
+  builder.set_face_uvs(1, [Vector2::zero(); 3]);
+  let err = builder.build().unwrap_err();
+  assert_eq!(err, MeshBuildError::UvFaceOutOfRange { face: 1, num_faces: 1 });
+  assert_eq!(format!("{}", err),
+      "Texture coordinates are set for face #1, but there are only 1 faces");
+}
@@ -0,0 +1,119 @@
+use super::super::{MeshBvh, TriangularMesh, TriangularMeshBuilder};
+use common::random::Random;
+use geometry::{HasBoundingBox3, Plane};
+use math::Vector3;
+
+// Returns a random point in the cube [-2, 2]^3.
+fn random_point(random: &mut Random) -> Vector3 {
+  Vector3::new(random.next_f64(), random.next_f64(), random.next_f64())
+      * 4.0 - Vector3::identity(2.0)
+}
+
+#[test]
+fn test_closest_point() {
+  let mesh = TriangularMesh::new_torus(Vector3::zero(), 1.0, 0.4, 48, 24);
+  let bvh = MeshBvh::new(&mesh);
+  assert!(bvh.bounding_box3() == mesh.bounding_box3());
+  let mut random = Random::new(1);
+  for _ in 0..200 {
+    let p = random_point(&mut random);
+    // Brute force over all faces.
+    let expected = (0..mesh.num_faces())
+        .map(|f| (mesh.triangle(f).closest_point(p) - p).len())
+        .fold(f64::INFINITY, f64::min);
+    let closest = bvh.closest_point(p).unwrap();
+    let distance = (mesh.position(&closest) - p).len();
+    assert!((distance - expected).abs() < 1e-12);
+    assert!((bvh.distance(p).unwrap() - expected).abs() < 1e-12);
+  }
+  let empty = TriangularMeshBuilder::new().build().unwrap();
+  let bvh = MeshBvh::new(&empty);
+  assert!(bvh.closest_point(Vector3::zero()).is_none());
+  assert!(bvh.signed_distance(Vector3::zero()).is_none());
+  assert_eq!(bvh.winding_number(Vector3::zero()), 0.0);
+}
+
+#[test]
+fn test_winding_number() {
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1.0, 3);
+  assert!((mesh.winding_number(Vector3::zero()) - 1.0).abs() < 1e-9);
+  assert!((mesh.winding_number(Vector3::new(0.5, -0.5, 0.3)) - 1.0).abs()
+      < 1e-9);
+  assert!(mesh.winding_number(Vector3::new(1.5, 0.0, 0.0)).abs() < 1e-9);
+
+  // Inside the tube of a torus, and in its hole.
+  let torus = TriangularMesh::new_torus(Vector3::zero(), 1.0, 0.4, 48, 24);
+  assert!((torus.winding_number(Vector3::x_unit()) - 1.0).abs() < 1e-9);
+  assert!(torus.winding_number(Vector3::zero()).abs() < 1e-9);
+
+  // The fast approximation is close to the exact winding number.
+  let bvh = MeshBvh::new(&torus);
+  let mut random = Random::new(2);
+  for _ in 0..200 {
+    let p = random_point(&mut random);
+    assert!((bvh.winding_number(p) - torus.winding_number(p)).abs() < 0.01);
+  }
+}
+
+#[test]
+fn test_imperfect_mesh() {
+  // A sphere with its top cut off still has its inside, which an open mesh
+  // has no ray-parity for.
+  let sphere = TriangularMesh::new_uv_sphere(Vector3::zero(), 1.0, 16, 32);
+  let (open, _) = sphere.split_by_plane(&Plane::new(Vector3::z_unit(), 0.7),
+      false);
+  assert!(!open.validate().is_watertight());
+  let bvh = MeshBvh::new(&open);
+  assert!(bvh.contains(Vector3::zero()));
+  assert!(bvh.contains(Vector3::new(0.0, 0.0, -0.9)));
+  assert!(!bvh.contains(Vector3::new(0.0, 0.0, 1.0)));
+  assert!(!bvh.contains(Vector3::new(1.1, 0.0, 0.0)));
+
+  // Overlapping copies of the sphere, whose winding number is 2 inside.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_allow_non_manifold_edges(true);
+  for k in 0..2 {
+    let first = k * sphere.num_vertices();
+    for v in 0..sphere.num_vertices() {
+      builder.add_vertex(sphere.vertex(v).pos());
+    }
+    for f in 0..sphere.num_faces() {
+      let v = sphere.face(f).vertices();
+      builder.add_face(first + v[0], first + v[1], first + v[2]);
+    }
+  }
+  let doubled = builder.build().unwrap();
+  let bvh = MeshBvh::new(&doubled);
+  assert!((doubled.winding_number(Vector3::zero()) - 2.0).abs() < 1e-9);
+  assert!((bvh.winding_number(Vector3::zero()) - 2.0).abs() < 0.1);
+  assert!(bvh.contains(Vector3::zero()));
+  assert!(!bvh.contains(Vector3::identity(1.0)));
+}
+
+#[test]
+fn test_signed_distance() {
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1.0, 4);
+  let bvh = MeshBvh::new(&mesh);
+  let mut random = Random::new(3);
+  for _ in 0..200 {
+    let p = random_point(&mut random);
+    let d = bvh.signed_distance(p).unwrap();
+    assert!((d - (p.len() - 1.0)).abs() < 2e-3);
+  }
+  assert!((bvh.signed_distance(Vector3::zero()).unwrap() + 1.0).abs() < 2e-3);
+
+  // Tiny meshes work the same.
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1e-4, 3);
+  let bvh = MeshBvh::new(&mesh);
+  assert!(bvh.contains(Vector3::zero()));
+  assert!((bvh.signed_distance(Vector3::zero()).unwrap() + 1e-4).abs()
+      < 1e-6);
+
+  let mesh = TriangularMesh::new_box(Vector3::zero(), Vector3::new(2.0, 2.0,
+      2.0));
+  let bvh = MeshBvh::new(&mesh);
+  assert!((bvh.signed_distance(Vector3::new(0.5, 0.0, 0.0)).unwrap() + 0.5)
+      .abs() < 1e-12);
+  assert!((bvh.signed_distance(Vector3::new(2.0, 2.0, 1.0)).unwrap()
+      - 2.0f64.sqrt()).abs() < 1e-12);
+}
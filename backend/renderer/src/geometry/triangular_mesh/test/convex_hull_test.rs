@@ -0,0 +1,115 @@
+use super::super::TriangularMesh;
+use common::random::Random;
+use geometry::{HasSurfaceArea, HasVolume};
+use math::Vector3;
+use std::f64::consts::PI;
+
+// Asserts that the mesh is a closed convex surface oriented outwards, which
+// contains all points.
+fn assert_convex_hull(hull: &TriangularMesh, points: &[Vector3]) {
+  let validation = hull.validate();
+  assert!(validation.is_watertight());
+  assert_eq!(validation.genus(), Some(0));
+  assert!(hull.volume() > 0.0);
+  for f in 0..hull.num_faces() {
+    let t = hull.triangle(f);
+    let n = t.try_normal().unwrap();
+    for p in points.iter() {
+      assert!((*p - t.v(0)) * n < 1e-9);
+    }
+  }
+}
+
+#[test]
+fn test_cube() {
+  // Corners of a cube, with duplicates, points inside, and points on its
+  // edges and faces.
+  let mut random = Random::new(3);
+  let mut points = Vec::new();
+  for i in 0..8 {
+    points.push(Vector3::new((i & 1) as f64, ((i >> 1) & 1) as f64,
+        ((i >> 2) & 1) as f64) * 2.0 - Vector3::one());
+  }
+  for i in 0..8 {
+    points.push(points[i]);
+  }
+  for _ in 0..100 {
+    let p = Vector3::new(random.next_f64(), random.next_f64(),
+        random.next_f64()) * 2.0 - Vector3::one();
+    points.push(p);
+    points.push(Vector3::new(1.0, p.y(), p.z()));
+    points.push(Vector3::new(p.x(), -1.0, 1.0));
+  }
+  random.shuffle(&mut points);
+  let hull = TriangularMesh::new_convex_hull(&points).unwrap();
+  assert_convex_hull(&hull, &points);
+  assert_eq!(hull.num_vertices(), 8);
+  assert_eq!(hull.num_faces(), 12);
+  assert!((hull.volume() - 8.0).abs() < 1e-9);
+  assert!((hull.surface_area() - 24.0).abs() < 1e-9);
+  for v in 0..hull.num_vertices() {
+    let p = hull.vertex(v).pos();
+    assert!(p.x().abs() == 1.0 && p.y().abs() == 1.0 && p.z().abs() == 1.0);
+  }
+}
+
+#[test]
+fn test_near_flat_cap() {
+  // A gently curved cap over an apex, whose points are all corners.
+  let mut points = Vec::new();
+  for i in 0..21 {
+    for j in 0..21 {
+      let (x, y) = (i as f64 / 10.0 - 1.0, j as f64 / 10.0 - 1.0);
+      points.push(Vector3::new(x, y, -(x * x + y * y) * 1e-3));
+    }
+  }
+  points.push(-Vector3::z_unit());
+  let hull = TriangularMesh::new_convex_hull(&points).unwrap();
+  assert_convex_hull(&hull, &points);
+  assert_eq!(hull.num_vertices(), points.len());
+}
+
+#[test]
+fn test_sphere() {
+  let mut random = Random::new(5);
+  let mut points = Vec::new();
+  for _ in 0..500 {
+    let z = 2.0 * random.next_f64() - 1.0;
+    let phi = 2.0 * PI * random.next_f64();
+    let r = (1.0 - z * z).sqrt();
+    points.push(Vector3::new(r * phi.cos(), r * phi.sin(), z) * 10.0
+        + Vector3::new(100.0, 0.0, -50.0));
+  }
+  let hull = TriangularMesh::new_convex_hull(&points).unwrap();
+  assert_convex_hull(&hull, &points);
+  // All points are on the hull, in the input order.
+  assert_eq!(hull.num_vertices(), points.len());
+  for (v, p) in points.iter().enumerate() {
+    assert_eq!(hull.vertex(v).pos(), *p);
+  }
+  assert_eq!(hull.num_faces(), 2 * points.len() - 4);
+  let volume = 4.0 / 3.0 * PI * 1000.0;
+  assert!(hull.volume() < volume && hull.volume() > 0.95 * volume);
+}
+
+#[test]
+fn test_degenerate() {
+  assert!(TriangularMesh::new_convex_hull(&[]).is_none());
+  let points = [Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::z_unit()];
+  assert!(TriangularMesh::new_convex_hull(&points[..3]).is_none());
+  let hull = TriangularMesh::new_convex_hull(&points).unwrap();
+  assert_convex_hull(&hull, &points);
+  assert!((hull.volume() - 1.0 / 6.0).abs() < 1e-9);
+
+  // Duplicate, collinear and coplanar points span no volume.
+  assert!(TriangularMesh::new_convex_hull(&[Vector3::one(); 10]).is_none());
+  let collinear: Vec<Vector3> =
+      (0..10).map(|i| Vector3::one() * i as f64).collect();
+  assert!(TriangularMesh::new_convex_hull(&collinear).is_none());
+  let coplanar: Vec<Vector3> = (0..100)
+      .map(|i| Vector3::new((i % 10) as f64, (i / 10) as f64,
+          (i % 10) as f64 + (i / 10) as f64))
+      .collect();
+  assert!(TriangularMesh::new_convex_hull(&coplanar).is_none());
+}
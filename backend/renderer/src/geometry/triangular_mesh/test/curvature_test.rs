@@ -0,0 +1,138 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder};
+use math::Vector3;
+use std::f64::consts::PI;
+
+// Builds an open cylinder of radius 1 around the z axis, with given numbers
+// of rings (from z = 0 to z = 1) and segments.
+fn build_cylinder(rings: usize, segments: usize) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  for i in 0..rings {
+    let z = i as f64 / (rings - 1) as f64;
+    for j in 0..segments {
+      let phi = 2.0 * PI * j as f64 / segments as f64;
+      builder.add_vertex(Vector3::new(phi.cos(), phi.sin(), z));
+    }
+  }
+  let id = |i: usize, j: usize| i * segments + j % segments;
+  for i in 0..rings - 1 {
+    for j in 0..segments {
+      builder.add_face(id(i, j), id(i, j + 1), id(i + 1, j + 1));
+      builder.add_face(id(i, j), id(i + 1, j + 1), id(i + 1, j));
+    }
+  }
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_sphere() {
+  let r = 2.0;
+  let mesh = TriangularMesh::new_uv_sphere(Vector3::zero(), r, 24, 48);
+  let curvatures = mesh.vertex_curvatures();
+  let mut total = 0.0;
+  for (v, c) in curvatures.iter().enumerate() {
+    let c = c.unwrap();
+    total += c.gaussian() * c.area();
+    assert!(c.normal() * mesh.vertex(v).pos() / r > 0.999);
+    assert!((c.mean() - 1.0 / r).abs() < 0.01);
+    assert!((c.gaussian() - 1.0 / (r * r)).abs() < 0.01);
+    let (k1, k2) = c.principal_curvatures();
+    assert!(k1 >= k2);
+    assert!((k1 - 1.0 / r).abs() < 0.05 && (k2 - 1.0 / r).abs() < 0.05);
+    let (d1, d2) = c.principal_directions();
+    assert!((d1 * c.normal()).abs() < 1e-9 && (d2 * c.normal()).abs() < 1e-9);
+    assert!((d1.len() - 1.0).abs() < 1e-9 && (d2.len() - 1.0).abs() < 1e-9);
+  }
+  // Gauss-Bonnet theorem: the total curvature of a sphere is 4 * pi.
+  assert!((total - 4.0 * PI).abs() < 1e-9);
+}
+
+#[test]
+fn test_cylinder() {
+  let (rings, segments) = (9, 64);
+  let mesh = build_cylinder(rings, segments);
+  for v in 0..mesh.num_vertices() {
+    let c = mesh.vertex_curvature(v);
+    if v < segments || v >= (rings - 1) * segments {
+      assert!(c.is_none());
+      continue;
+    }
+    let c = c.unwrap();
+    let p = mesh.vertex(v).pos();
+    assert!((c.mean() - 0.5).abs() < 0.01);
+    assert!(c.gaussian().abs() < 1e-9);
+    let (k1, k2) = c.principal_curvatures();
+    assert!((k1 - 1.0).abs() < 0.01 && k2.abs() < 0.01);
+    // The surface bends around the axis, and is straight along it.
+    let (d1, d2) = c.principal_directions();
+    assert!(d1.z().abs() < 1e-2 && (d2.z().abs() - 1.0).abs() < 1e-2);
+    let tangent = Vector3::new(-p.y(), p.x(), 0.0);
+    let s = c.shape_operator();
+    assert!((tangent * (s * tangent) - k1).abs() < 1e-2);
+    assert!((s * Vector3::z_unit()).len() < 1e-2);
+    assert_eq!(s * c.normal(), Vector3::zero());
+  }
+}
+
+#[test]
+fn test_small_cylinder() {
+  // Principal directions must not depend on the scale, nor on the axis.
+  for &r in [0.01, 0.001].iter() {
+    let (segments, rings) = (64, 40);
+    let mesh = TriangularMesh::new_parametric(|u, v| {
+      let phi = 2.0 * PI * u;
+      Vector3::new(4.0 * r * v, r * phi.cos(), r * phi.sin())
+    }, segments, rings, true, false);
+    for v in segments..rings * segments {
+      let c = mesh.vertex_curvature(v).unwrap();
+      let (k1, k2) = c.principal_curvatures();
+      assert!((k1 * r - 1.0).abs() < 0.01 && (k2 * r).abs() < 0.01);
+      let (d1, d2) = c.principal_directions();
+      assert!(d1.x().abs() < 1e-2 && (d2.x().abs() - 1.0).abs() < 1e-2);
+    }
+  }
+}
+
+#[test]
+fn test_small_sphere() {
+  // Degeneracy tests are relative to the size of the one-ring.
+  for &r in [1e-4, 1e-6].iter() {
+    let mesh = TriangularMesh::new_icosphere(Vector3::zero(), r, 3);
+    for v in 0..mesh.num_vertices() {
+      let c = mesh.vertex_curvature(v).unwrap();
+      assert!((c.mean() * r - 1.0).abs() < 0.01);
+      assert!(c.normal() * mesh.vertex(v).pos() / r > 0.999);
+    }
+  }
+}
+
+#[test]
+fn test_plane() {
+  let n = 4;
+  let mut builder = TriangularMeshBuilder::new();
+  for y in 0..n + 1 {
+    for x in 0..n + 1 {
+      builder.add_vertex(Vector3::new(x as f64, y as f64, 0.0));
+    }
+  }
+  for y in 0..n {
+    for x in 0..n {
+      let a = y * (n + 1) + x;
+      builder.add_faces(&[[a, a + 1, a + n + 2], [a, a + n + 2, a + n + 1]]);
+    }
+  }
+  let mesh = builder.build().unwrap();
+  for y in 0..n + 1 {
+    for x in 0..n + 1 {
+      let c = mesh.vertex_curvature(y * (n + 1) + x);
+      if x == 0 || y == 0 || x == n || y == n {
+        assert!(c.is_none());
+        continue;
+      }
+      let c = c.unwrap();
+      assert_eq!(c.normal(), Vector3::z_unit());
+      assert!(c.mean().abs() < 1e-9 && c.gaussian().abs() < 1e-9);
+      assert!((c.area() - 1.0).abs() < 1e-9);
+      assert!(c.shape_operator().trace().abs() < 1e-9);
+    }
+  }
+}
@@ -0,0 +1,173 @@
+use super::super::{
+    TriangularMesh, TriangularMeshBuilder, MeshEditError, MeshPoint,
+};
+use common::random::Random;
+use math::Vector3;
+
+// Builds a grid of n x n unit squares over [0, n]^2 in the plane z = 0, where
+// vertex (i, j) has index i * (n + 1) + j and position (j, i, 0).
+fn build_grid(n: usize) -> TriangularMesh {
+  let size = n as f64;
+  TriangularMesh::new_plane(Vector3::new(size, size, 0.0) / 2.0, size, size,
+      n, n)
+}
+
+fn find_edge(mesh: &TriangularMesh, from: usize, to: usize) -> usize {
+  mesh.outgoing_edges(from)
+      .find(|e| mesh.edge(*e).end_vertex() == to)
+      .unwrap()
+}
+
+#[test]
+fn test_check_invariants() {
+  let mut mesh = build_grid(2);
+  assert_eq!(mesh.check_invariants(), Ok(()));
+  let torus = TriangularMesh::new_torus(Vector3::zero(), 2.0, 1.0, 4, 4);
+  assert_eq!(torus.check_invariants(), Ok(()));
+
+  let twin = mesh.edges[1].twin_edge.unwrap();
+  mesh.edges[twin].twin_edge = None;
+  assert!(mesh.check_invariants().is_err());
+  mesh.edges[twin].twin_edge = Some(1);
+  mesh.vertices[0].edges.push(1);
+  assert!(mesh.check_invariants().is_err());
+}
+
+#[test]
+fn test_flip_edge() {
+  let mut mesh = build_grid(1);
+  let e = find_edge(&mesh, 0, 3);
+  let flipped = mesh.flip_edge(e).unwrap();
+  mesh.check_invariants().unwrap();
+  let (a, b) = (mesh.edge(flipped).start_vertex(),
+      mesh.edge(flipped).end_vertex());
+  assert_eq!((a.min(b), a.max(b)), (1, 2));
+  assert!(mesh.edge(flipped).twin_edge().is_some());
+  for f in 0..2 {
+    assert_eq!(mesh.face(f).normal(), Some(Vector3::z_unit()));
+  }
+  assert_eq!(mesh.outgoing_edges(0).count(), 1);
+  assert_eq!(mesh.outgoing_edges(1).count(), 2);
+
+  // Flipping back restores the original diagonal.
+  let flipped = mesh.flip_edge(flipped).unwrap();
+  mesh.check_invariants().unwrap();
+  let (a, b) = (mesh.edge(flipped).start_vertex(),
+      mesh.edge(flipped).end_vertex());
+  assert_eq!((a.min(b), a.max(b)), (0, 3));
+
+  let boundary = find_edge(&mesh, 0, 1);
+  assert_eq!(mesh.flip_edge(boundary),
+      Err(MeshEditError::BoundaryEdge { edge: boundary }));
+
+  let mut tetrahedron = {
+    let mut builder = TriangularMeshBuilder::new();
+    builder.add_vertices(&[
+        Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+        Vector3::z_unit()]);
+    builder.add_faces(&[[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]]);
+    builder.build().unwrap()
+  };
+  match tetrahedron.flip_edge(0) {
+    Err(MeshEditError::EdgeExists { .. }) => {},
+    r => panic!("Unexpected result {:?}", r),
+  }
+}
+
+#[test]
+fn test_split() {
+  let mut mesh = build_grid(2);
+  let e = find_edge(&mesh, 1, 4);
+  let m = mesh.split_edge(e, 0.25);
+  mesh.check_invariants().unwrap();
+  assert_eq!(m, 9);
+  assert_eq!(mesh.num_faces(), 10);
+  assert_eq!(mesh.vertex(m).pos(), Vector3::new(1.0, 0.25, 0.0));
+  assert_eq!(mesh.outgoing_edges(m).count(), 4);
+  assert_eq!(mesh.vertex(m).normal(), Some(Vector3::z_unit()));
+
+  let boundary = find_edge(&mesh, 0, 1);
+  let m = mesh.split_edge(boundary, 0.5);
+  mesh.check_invariants().unwrap();
+  assert_eq!(mesh.num_faces(), 11);
+  assert_eq!(mesh.vertex(m).pos(), Vector3::new(0.5, 0.0, 0.0));
+
+  let m = mesh.split_face(&MeshPoint::new(7, Vector3::new(0.2, 0.3, 0.5)));
+  mesh.check_invariants().unwrap();
+  assert_eq!(mesh.num_faces(), 13);
+  assert_eq!(mesh.outgoing_edges(m).count(), 3);
+  let validation = mesh.validate();
+  assert!(validation.is_manifold());
+  assert_eq!(validation.genus(), Some(0));
+  assert!(validation.degenerate_faces().is_empty());
+}
+
+#[test]
+fn test_collapse_edge() {
+  let mut mesh = build_grid(3);
+  let e = find_edge(&mesh, 5, 6);
+  let kept = mesh.collapse_edge(e, Vector3::new(1.5, 1.0, 0.0)).unwrap();
+  mesh.check_invariants().unwrap();
+  assert_eq!(mesh.num_vertices(), 15);
+  assert_eq!(mesh.num_faces(), 16);
+  assert_eq!(kept, 5);
+  assert_eq!(mesh.vertex(kept).pos(), Vector3::new(1.5, 1.0, 0.0));
+  // The last vertex was moved into the place of the removed one.
+  assert_eq!(mesh.vertex(6).pos(), Vector3::new(3.0, 3.0, 0.0));
+  assert!(mesh.validate().is_manifold());
+
+  // Collapsing the other interior vertex into a corner keeps the last vertex.
+  let e = find_edge(&mesh, 0, 5);
+  assert_eq!(mesh.collapse_edge(e, Vector3::zero()), Ok(0));
+  mesh.check_invariants().unwrap();
+  assert_eq!(mesh.num_vertices(), 14);
+
+  // An interior edge between two boundary vertices.
+  let mut square = build_grid(1);
+  let e = find_edge(&square, 0, 3);
+  assert_eq!(square.collapse_edge(e, Vector3::zero()),
+      Err(MeshEditError::LinkConditionViolated { edge: e }));
+  // A boundary edge of a single triangle.
+  let e = find_edge(&square, 0, 1);
+  assert!(square.collapse_edge(e, Vector3::zero()).is_ok());
+  square.check_invariants().unwrap();
+  assert_eq!(square.num_faces(), 1);
+}
+
+#[test]
+fn test_random_edits() {
+  let mut mesh = TriangularMesh::new_torus(Vector3::zero(), 2.0, 1.0, 8, 8);
+  let mut random = Random::new(38);
+  let (mut flips, mut collapses) = (0, 0);
+  for i in 0..300 {
+    let e = random.next_usize(mesh.num_edges());
+    match i % 4 {
+      0 => {
+        if mesh.flip_edge(e).is_ok() {
+          flips += 1;
+        }
+      },
+      1 => {
+        mesh.split_edge(e, random.next_f64());
+      },
+      2 => {
+        let f = random.next_usize(mesh.num_faces());
+        let a = random.next_f64();
+        let b = random.next_f64() * (1.0 - a);
+        mesh.split_face(&MeshPoint::new(f, Vector3::new(a, b, 1.0 - a - b)));
+      },
+      _ => {
+        let pos = mesh.vertex(mesh.edge(e).start_vertex()).pos();
+        if mesh.collapse_edge(e, pos).is_ok() {
+          collapses += 1;
+        }
+      },
+    }
+    mesh.check_invariants().unwrap();
+  }
+  assert!(flips > 0 && collapses > 0);
+  let validation = mesh.validate();
+  assert!(validation.is_watertight());
+  assert_eq!(validation.genus(), Some(1));
+  assert!(validation.isolated_vertices().is_empty());
+}
@@ -0,0 +1,77 @@
+use super::super::{
+    TriangularMesh, TriangularMeshBuilder, MeshPoint, FaceNormalMode,
+    VertexNormalMode,
+};
+use math::{Vector2, Vector3};
+
+// Builds a unit square in the plane z = 0 with two faces, whose texture
+// coordinates equal (x, y) and user-specified vertex normals tilt towards
+// +x along x.
+fn build_square(mode: FaceNormalMode) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_vertex_normal_mode(VertexNormalMode::USER_SPECIFIED);
+  builder.set_face_normal_mode(mode);
+  for p in [Vector3::zero(), Vector3::x_unit(), Vector3::new(1.0, 1.0, 0.0),
+      Vector3::y_unit()].iter() {
+    let v = builder.add_vertex_with_normal(*p,
+        Vector3::new(p.x(), 0.0, 1.0).normalize());
+    builder.set_vertex_uv(v, Vector2::new(p.x(), p.y()));
+  }
+  builder.add_faces(&[[0, 1, 2], [0, 2, 3]]);
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_position_and_uv() {
+  let mesh = build_square(FaceNormalMode::FLAT);
+  let p = MeshPoint::new(0, Vector3::new(0.25, 0.25, 0.5));
+  assert_eq!(p.face_id(), 0);
+  assert_eq!(mesh.position(&p), Vector3::new(0.75, 0.5, 0.0));
+  assert_eq!(mesh.uv(&p), Some(Vector2::new(0.75, 0.5)));
+  let p = MeshPoint::new(1, Vector3::new(0.0, 0.0, 1.0));
+  assert_eq!(mesh.position(&p), Vector3::y_unit());
+  assert_eq!(mesh.uv(&p), Some(Vector2::new(0.0, 1.0)));
+
+  // No texture coordinates.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[Vector3::zero(), Vector3::x_unit(), Vector3::y_unit()]);
+  builder.add_face(0, 1, 2);
+  let plain = builder.build().unwrap();
+  let p = MeshPoint::new(0, Vector3::identity(1.0 / 3.0));
+  assert_eq!(plain.uv(&p), None);
+  assert_eq!(plain.tangent(&p), None);
+}
+
+#[test]
+fn test_normals_and_tangents() {
+  let flat = build_square(FaceNormalMode::FLAT);
+  let phong = build_square(FaceNormalMode::PHONG);
+  // Midpoint of the edge between vertex #1 and #2.
+  let p = MeshPoint::new(0, Vector3::new(0.0, 0.5, 0.5));
+
+  assert_eq!(flat.geometric_normal(&p), Some(Vector3::z_unit()));
+  assert_eq!(flat.shading_normal(&p), Some(Vector3::z_unit()));
+  assert_eq!(phong.geometric_normal(&p), Some(Vector3::z_unit()));
+  assert_eq!(phong.shading_normal(&p),
+      Some(Vector3::new(1.0, 0.0, 1.0).normalize()));
+  let p0 = MeshPoint::new(0, Vector3::new(1.0, 0.0, 0.0));
+  assert_eq!(phong.shading_normal(&p0), Some(Vector3::z_unit()));
+
+  // Tangents follow increasing u, i.e. +x, orthogonal to the normal.
+  assert_eq!(flat.tangent(&p), Some(Vector3::x_unit()));
+  let h = 0.5f64.sqrt();
+  assert_eq!(phong.tangent(&p), Some(Vector3::new(h, 0.0, -h)));
+}
+
+#[test]
+fn test_mesh_point_from_hits() {
+  let mesh = build_square(FaceNormalMode::FLAT);
+  let p = mesh.mesh_point_at(1, Vector3::new(0.25, 0.5, 0.0)).unwrap();
+  assert_eq!(p.face_id(), 1);
+  assert_eq!(p.params(), Vector3::new(0.5, 0.25, 0.25));
+  assert_eq!(mesh.position(&p), Vector3::new(0.25, 0.5, 0.0));
+
+  // Points slightly off the face are projected and clamped into it.
+  let p = mesh.mesh_point_at(0, Vector3::new(1.0 + 1e-12, 0.5, 1e-3)).unwrap();
+  assert_eq!(mesh.position(&p), Vector3::new(1.0, 0.5, 0.0));
+}
@@ -0,0 +1,195 @@
+use super::super::TriangularMesh;
+use geometry::{HasSurfaceArea, HasVolume};
+use math::Vector3;
+use std::f64::consts::PI;
+
+// Asserts that all faces have texture coordinates in [0, max_u] x [0, 1],
+// oriented like the faces themselves, i.e. no face is flipped or stretched
+// across a seam.
+fn assert_uvs(mesh: &TriangularMesh, max_u: f64) {
+  for f in 0..mesh.num_faces() {
+    let uvs = mesh.corner_uvs(f).unwrap();
+    for uv in uvs.iter() {
+      assert!(uv.u() >= 0.0 && uv.u() <= max_u + 1e-12);
+      assert!(uv.v() >= 0.0 && uv.v() <= 1.0 + 1e-12);
+    }
+    let (a, b) = (uvs[1] - uvs[0], uvs[2] - uvs[0]);
+    let area = (a.u() * b.v() - a.v() * b.u()) / 2.0;
+    assert!(area > 0.0 && area < 0.5 + 1e-12);
+  }
+}
+
+// Asserts that vertex normals are unit vectors pointing outwards, i.e. along
+// the face normals of adjacent faces.
+fn assert_normals(mesh: &TriangularMesh) {
+  for f in 0..mesh.num_faces() {
+    let n = mesh.triangle(f).normal();
+    for v in mesh.face(f).vertices().iter() {
+      let vertex_normal = mesh.vertex(*v).normal().unwrap();
+      assert!((vertex_normal.len() - 1.0).abs() < 1e-9);
+      assert!(vertex_normal * n > 0.0);
+    }
+  }
+}
+
+#[test]
+fn test_uv_sphere() {
+  let center = Vector3::new(1.0, 2.0, 3.0);
+  let mesh = TriangularMesh::new_uv_sphere(center, 2.0, 32, 64);
+  assert_eq!(mesh.num_vertices(), 31 * 64 + 2);
+  assert_eq!(mesh.num_faces(), 2 * 30 * 64 + 2 * 64);
+  let report = mesh.validate();
+  assert!(report.is_watertight());
+  assert_eq!(report.genus(), Some(0));
+  assert!((mesh.volume() / (4.0 / 3.0 * PI * 8.0) - 1.0).abs() < 0.01);
+  for v in 0..mesh.num_vertices() {
+    let vertex = mesh.vertex(v);
+    assert!(((vertex.pos() - center).len() - 2.0).abs() < 1e-9);
+    assert!(vertex.normal().unwrap() == (vertex.pos() - center) / 2.0);
+  }
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+}
+
+#[test]
+fn test_icosphere() {
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1.0, 0);
+  assert_eq!(mesh.num_vertices(), 12);
+  assert_eq!(mesh.num_faces(), 20);
+  assert!(mesh.volume() > 0.0);
+
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1.0, 4);
+  assert_eq!(mesh.num_faces(), 20 * 256);
+  assert_eq!(mesh.num_vertices(), 10 * 256 + 2);
+  let report = mesh.validate();
+  assert!(report.is_watertight());
+  assert_eq!(report.genus(), Some(0));
+  assert!((mesh.volume() / (4.0 / 3.0 * PI) - 1.0).abs() < 0.01);
+  assert!((mesh.surface_area() / (4.0 * PI) - 1.0).abs() < 0.01);
+  // Faces are nearly uniform.
+  let areas: Vec<f64> = (0..mesh.num_faces())
+      .map(|f| mesh.triangle(f).surface_area())
+      .collect();
+  let max = areas.iter().cloned().fold(0.0, f64::max);
+  let min = areas.iter().cloned().fold(1.0, f64::min);
+  assert!(max / min < 1.5);
+  assert_normals(&mesh);
+  // Faces across the seam continue beyond u = 1.
+  assert_uvs(&mesh, 1.1);
+}
+
+#[test]
+fn test_box() {
+  let mesh = TriangularMesh::new_box(Vector3::new(1.0, 1.0, 1.0),
+      Vector3::new(1.0, 2.0, 3.0));
+  assert_eq!(mesh.num_vertices(), 8);
+  assert_eq!(mesh.num_faces(), 12);
+  assert!(mesh.validate().is_watertight());
+  assert!((mesh.volume() - 6.0).abs() < 1e-12);
+  assert!((mesh.surface_area() - 22.0).abs() < 1e-12);
+  assert!(mesh.volume_centroid().unwrap() == Vector3::new(1.0, 1.0, 1.0));
+  assert_uvs(&mesh, 1.0);
+}
+
+#[test]
+fn test_plane() {
+  let mesh = TriangularMesh::new_plane(Vector3::zero(), 4.0, 2.0, 8, 4);
+  assert_eq!(mesh.num_vertices(), 9 * 5);
+  assert_eq!(mesh.num_faces(), 2 * 8 * 4);
+  let report = mesh.validate();
+  assert_eq!(report.num_boundary_loops(), 1);
+  assert_eq!(report.num_boundary_edges(), 2 * (8 + 4));
+  assert!((mesh.surface_area() - 8.0).abs() < 1e-12);
+  for f in 0..mesh.num_faces() {
+    assert!(mesh.triangle(f).normal() == Vector3::z_unit());
+  }
+  for v in 0..mesh.num_vertices() {
+    let vertex = mesh.vertex(v);
+    let uv = vertex.uv().unwrap();
+    assert!((uv.u() - (vertex.pos().x() / 4.0 + 0.5)).abs() < 1e-12);
+    assert!((uv.v() - (vertex.pos().y() / 2.0 + 0.5)).abs() < 1e-12);
+  }
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+}
+
+#[test]
+fn test_cylinder() {
+  let mesh = TriangularMesh::new_cylinder(Vector3::zero(), 1.0, 2.0, 128, 4);
+  // The side, and the caps with their centers.
+  assert_eq!(mesh.num_vertices(), 128 * 5 + 2 * 129);
+  assert_eq!(mesh.num_faces(), 2 * 128 * 4 + 2 * 128);
+  assert_eq!(mesh.validate().num_boundary_loops(), 4);
+  assert!((mesh.volume() / (2.0 * PI) - 1.0).abs() < 0.01);
+  assert!((mesh.surface_area() / (6.0 * PI) - 1.0).abs() < 0.01);
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+}
+
+#[test]
+fn test_cone() {
+  let mesh = TriangularMesh::new_cone(Vector3::zero(), 1.0, 3.0, 128);
+  assert_eq!(mesh.num_faces(), 2 * 128);
+  // The side faces share the apex, the base has its own rim.
+  assert_eq!(mesh.num_vertices(), 2 * 128 + 2);
+  assert_eq!(mesh.vertex_faces(128).count(), 128);
+  assert_eq!(mesh.vertex(128).normal(), Some(Vector3::z_unit()));
+  assert!((mesh.volume() / PI - 1.0).abs() < 0.01);
+  let slant = 10.0f64.sqrt();
+  assert!((mesh.surface_area() / (PI * (1.0 + slant)) - 1.0).abs() < 0.01);
+  for v in 0..mesh.num_vertices() {
+    assert!(mesh.vertex(v).pos().z().abs() == 1.5);
+  }
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+}
+
+#[test]
+fn test_torus() {
+  let mesh = TriangularMesh::new_torus(Vector3::zero(), 2.0, 0.5, 128, 64);
+  assert_eq!(mesh.num_vertices(), 128 * 64);
+  let report = mesh.validate();
+  assert!(report.is_watertight());
+  assert_eq!(report.genus(), Some(1));
+  let volume = 2.0 * PI * PI * 2.0 * 0.25;
+  assert!((mesh.volume() / volume - 1.0).abs() < 0.01);
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+}
+
+#[test]
+fn test_parametric() {
+  // A torus, closed in both directions.
+  let torus = |u: f64, v: f64| {
+    let (phi, theta) = (2.0 * PI * u, 2.0 * PI * v);
+    Vector3::new((2.0 + theta.cos()) * phi.cos(),
+        (2.0 + theta.cos()) * phi.sin(), theta.sin())
+  };
+  let mesh = TriangularMesh::new_parametric(torus, 64, 32, true, true);
+  assert_eq!(mesh.num_vertices(), 64 * 32);
+  let report = mesh.validate();
+  assert!(report.is_watertight());
+  assert_eq!(report.genus(), Some(1));
+  assert!(mesh.volume() > 0.0);
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+
+  // A tube, closed around only.
+  let tube = |u: f64, v: f64| {
+    let phi = 2.0 * PI * u;
+    Vector3::new(phi.cos(), phi.sin(), v)
+  };
+  let mesh = TriangularMesh::new_parametric(tube, 16, 4, true, false);
+  assert_eq!(mesh.num_vertices(), 16 * 5);
+  assert_eq!(mesh.validate().num_boundary_loops(), 2);
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+
+  // A height field.
+  let height = |u: f64, v: f64| Vector3::new(u, v, u * v);
+  let mesh = TriangularMesh::new_parametric(height, 10, 10, false, false);
+  assert_eq!(mesh.num_vertices(), 121);
+  assert_eq!(mesh.validate().num_boundary_loops(), 1);
+  assert_normals(&mesh);
+  assert_uvs(&mesh, 1.0);
+}
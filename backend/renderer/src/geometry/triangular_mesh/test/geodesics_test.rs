@@ -0,0 +1,126 @@
+use super::super::{
+    TriangularMesh, TriangularMeshBuilder, MeshPoint, GeodesicSource,
+};
+use common::constants;
+use math::Vector3;
+use std::f64::consts::PI;
+
+// Builds an n x n grid of squares over [0, size]^2 in the plane z = 0, each
+// split into two faces along alternating diagonals, which bounds the error
+// of shortest paths along edges in all directions (unlike new_plane(), whose
+// diagonals are all parallel).
+fn build_grid(n: usize, size: f64) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  for y in 0..n + 1 {
+    for x in 0..n + 1 {
+      builder.add_vertex(Vector3::new(x as f64, y as f64, 0.0)
+          * (size / n as f64));
+    }
+  }
+  let id = |x: usize, y: usize| y * (n + 1) + x;
+  for y in 0..n {
+    for x in 0..n {
+      let (a, b, c, d) =
+          (id(x, y), id(x + 1, y), id(x + 1, y + 1), id(x, y + 1));
+      if (x + y) % 2 == 0 {
+        builder.add_faces(&[[a, b, c], [a, c, d]]);
+      } else {
+        builder.add_faces(&[[a, b, d], [b, c, d]]);
+      }
+    }
+  }
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_plane() {
+  let n = 20;
+  let mesh = build_grid(n, 1.0);
+  let center = (n / 2) * (n + 1) + n / 2;
+  let sources = [GeodesicSource::VERTEX(center)];
+  let dijkstra = mesh.geodesic_distances_dijkstra(&sources);
+  let heat = mesh.geodesic_distances_heat(&sources).unwrap();
+  let c = mesh.vertex(center).pos();
+  assert_eq!(dijkstra[center], 0.0);
+  assert!(heat[center] < 1e-9);
+  let mut max_dijkstra_error: f64 = 0.0;
+  let mut max_heat_error: f64 = 0.0;
+  for v in 0..mesh.num_vertices() {
+    let exact = (mesh.vertex(v).pos() - c).len();
+    assert!(dijkstra[v] >= exact - 1e-9);
+    max_dijkstra_error = max_dijkstra_error.max(dijkstra[v] - exact);
+    max_heat_error = max_heat_error.max((heat[v] - exact).abs());
+  }
+  // Paths along edges zigzag, while the heat method is accurate up to about
+  // the edge length.
+  assert!(max_dijkstra_error < 0.1 * 0.5f64.sqrt());
+  assert!(max_heat_error < 1.0 / n as f64);
+  assert!(max_heat_error < max_dijkstra_error);
+
+  // Distances scale with the mesh.
+  let small = build_grid(n, 1e-4).geodesic_distances_heat(&sources).unwrap();
+  for v in 0..mesh.num_vertices() {
+    assert!((small[v] * 1e4 - heat[v]).abs() < 1e-6);
+  }
+}
+
+#[test]
+fn test_sphere() {
+  let mesh = TriangularMesh::new_uv_sphere(Vector3::zero(), 1.0, 32, 64);
+  let sources = [GeodesicSource::VERTEX(0)];
+  let dijkstra = mesh.geodesic_distances_dijkstra(&sources);
+  let heat = mesh.geodesic_distances_heat(&sources).unwrap();
+  for v in 0..mesh.num_vertices() {
+    // The geodesic distance from the north pole is the polar angle.
+    let exact = mesh.vertex(v).pos().z().clamp(-1.0, 1.0).acos();
+    assert!((dijkstra[v] - exact).abs() < 0.01 * PI);
+    assert!((heat[v] - exact).abs() < 0.01 * PI);
+  }
+}
+
+#[test]
+fn test_sources() {
+  let n = 10;
+  let mut mesh = build_grid(n, 1.0);
+  // A point source at the center of the first face (0, 0), (0.1, 0),
+  // (0.1, 0.1), and a vertex source at (1, 1).
+  let p = MeshPoint::new(0, Vector3::identity(1.0 / 3.0));
+  let source = mesh.position(&p);
+  let corner = mesh.num_vertices() - 1;
+  let sources = [GeodesicSource::POINT(p), GeodesicSource::VERTEX(corner)];
+  let dijkstra = mesh.geodesic_distances_dijkstra(&sources);
+  assert!((dijkstra[0] - source.len()).abs() < 1e-9);
+  assert_eq!(dijkstra[corner], 0.0);
+  let heat = mesh.geodesic_distances_heat(&sources).unwrap();
+  for (v, h) in heat.iter().enumerate() {
+    let pos = mesh.vertex(v).pos();
+    let exact = (pos - source).len()
+        .min((pos - Vector3::new(1.0, 1.0, 0.0)).len());
+    assert!((h - exact).abs() < 1.0 / n as f64);
+  }
+
+  // Vertices not connected to any source, e.g. a new isolated vertex, are
+  // infinitely far away. An isolated source is only at distance 0 to itself.
+  let mut builder = TriangularMeshBuilder::new();
+  for v in 0..mesh.num_vertices() {
+    builder.add_vertex(mesh.vertex(v).pos());
+  }
+  let isolated = builder.add_vertex(Vector3::new(5.0, 5.0, 0.0));
+  for f in 0..mesh.num_faces() {
+    let v = mesh.face(f).vertices();
+    builder.add_face(v[0], v[1], v[2]);
+  }
+  mesh = builder.build().unwrap();
+  let heat = mesh.geodesic_distances_heat(&sources).unwrap();
+  assert_eq!(heat[isolated], constants::INFINITY);
+  let dijkstra = mesh.geodesic_distances_dijkstra(
+      &[GeodesicSource::VERTEX(isolated)]);
+  assert_eq!(dijkstra[isolated], 0.0);
+  assert_eq!(dijkstra[0], constants::INFINITY);
+  let heat = mesh.geodesic_distances_heat(
+      &[GeodesicSource::VERTEX(isolated)]).unwrap();
+  assert_eq!(heat[isolated], 0.0);
+  assert_eq!(heat[0], constants::INFINITY);
+  assert!(mesh.geodesic_distances_dijkstra(&[]).iter()
+      .all(|d| *d == constants::INFINITY));
+}
@@ -0,0 +1,95 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder};
+use geometry::{HasBoundingBox3, HasBoundingSphere, HasSurfaceArea, HasVolume};
+use math::{Matrix3, Vector3};
+use std::f64::consts::PI;
+
+#[test]
+fn test_cube() {
+  let corner = Vector3::new(1.0, -2.0, 3.0);
+  let center = corner + Vector3::identity(1.0);
+  let cube = TriangularMesh::new_box(center, Vector3::identity(2.0));
+  assert!((cube.surface_area() - 24.0).abs() < 1e-9);
+  assert!((cube.volume() - 8.0).abs() < 1e-9);
+  assert_eq!(cube.surface_centroid(), Some(center));
+  assert_eq!(cube.volume_centroid(), Some(center));
+  // I = m * s^2 / 6 on the diagonal, with mass m = s^3.
+  assert_eq!(cube.inertia_tensor(),
+      Some(Matrix3::identity() * (32.0 / 6.0)));
+  let b = cube.bounding_box3();
+  assert_eq!(b.min_corner(), corner);
+  assert_eq!(b.max_corner(), corner + Vector3::identity(2.0));
+  let s = cube.bounding_sphere();
+  assert_eq!(s.center(), center);
+  assert!((s.radius() - 3.0f64.sqrt()).abs() < 1e-9);
+
+  // Inward orientations negate the volume, but not the centroid.
+  let mut inverted = TriangularMeshBuilder::new();
+  for v in 0..cube.num_vertices() {
+    inverted.add_vertex(cube.vertex(v).pos());
+  }
+  for f in 0..cube.num_faces() {
+    let v = cube.face(f).vertices();
+    inverted.add_face(v[0], v[2], v[1]);
+  }
+  let inverted = inverted.build().unwrap();
+  assert!((inverted.volume() + 8.0).abs() < 1e-9);
+  assert_eq!(inverted.volume_centroid(), Some(center));
+
+  // Tiny solids still have centroids.
+  for &s in [1e-3, 1e-5].iter() {
+    let mut tiny = TriangularMeshBuilder::new();
+    for v in 0..cube.num_vertices() {
+      tiny.add_vertex((cube.vertex(v).pos() - center) * s);
+    }
+    for f in 0..cube.num_faces() {
+      let v = cube.face(f).vertices();
+      tiny.add_face(v[0], v[1], v[2]);
+    }
+    let tiny = tiny.build().unwrap();
+    assert_eq!(tiny.surface_centroid(), Some(Vector3::zero()));
+    assert_eq!(tiny.volume_centroid(), Some(Vector3::zero()));
+    assert!(tiny.inertia_tensor().is_some());
+  }
+}
+
+#[test]
+fn test_sphere() {
+  let center = Vector3::new(0.5, 1.0, -1.0);
+  let r = 2.0;
+  let sphere = TriangularMesh::new_uv_sphere(center, r, 64, 128);
+  let area = 4.0 * PI * r * r;
+  let volume = 4.0 / 3.0 * PI * r * r * r;
+  // The inscribed polyhedron converges quadratically to the sphere.
+  assert!((sphere.surface_area() - area).abs() < 5e-3 * area);
+  assert!((sphere.volume() - volume).abs() < 5e-3 * volume);
+  assert_eq!(sphere.surface_centroid(), Some(center));
+  assert_eq!(sphere.volume_centroid(), Some(center));
+
+  // I = 2 / 5 * m * r^2 on the diagonal.
+  let inertia = sphere.inertia_tensor().unwrap();
+  let expected = 0.4 * volume * r * r;
+  for i in 0..3 {
+    for j in 0..3 {
+      let e = if i == j { expected } else { 0.0 };
+      assert!((inertia[i][j] - e).abs() < 1e-2 * expected);
+    }
+  }
+  let b = sphere.bounding_box3();
+  assert_eq!(b.max_z(), center.z() + r);
+  assert_eq!(b.min_z(), center.z() - r);
+}
+
+#[test]
+fn test_open_surface() {
+  // A single square has area and a surface centroid, but no volume.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[Vector3::zero(), Vector3::x_unit(),
+      Vector3::new(1.0, 1.0, 0.0), Vector3::y_unit()]);
+  builder.add_faces(&[[0, 1, 2], [0, 2, 3]]);
+  let square = builder.build().unwrap();
+  assert!((square.surface_area() - 1.0).abs() < 1e-9);
+  assert_eq!(square.surface_centroid(), Some(Vector3::new(0.5, 0.5, 0.0)));
+  assert!(square.volume().abs() < 1e-9);
+  assert_eq!(square.volume_centroid(), None);
+  assert_eq!(square.inertia_tensor(), None);
+}
@@ -0,0 +1,41 @@
+use super::super::TriangularMesh;
+use geometry::HasSurfaceArea;
+use math::Vector3;
+
+#[test]
+fn test_cotan_laplacian() {
+  let n = 4;
+  let mesh =
+      TriangularMesh::new_plane(Vector3::new(0.5, 0.5, 0.0), 1.0, 1.0, n, n);
+  let l = mesh.cotan_laplacian();
+  assert_eq!(l.num_rows(), mesh.num_vertices());
+  // Symmetric, and constant functions are in the null space.
+  let ones = vec![1.0; mesh.num_vertices()];
+  for (v, r) in l.mul_vec(&ones).iter().enumerate() {
+    assert!(r.abs() < 1e-9);
+    for u in 0..mesh.num_vertices() {
+      assert!((l.get(v, u) - l.get(u, v)).abs() < 1e-12);
+    }
+  }
+  // Right angles have no weight, 45 degree angles a weight of 1 / 2.
+  assert!((l.get(0, 1) + 0.5).abs() < 1e-12);
+  assert!(l.get(0, n + 2).abs() < 1e-12);
+  // Linear functions are harmonic in the interior of a flat mesh.
+  let linear: Vec<f64> = (0..mesh.num_vertices())
+      .map(|v| {
+        let p = mesh.vertex(v).pos();
+        2.0 * p.x() - 3.0 * p.y()
+      })
+      .collect();
+  let r = l.mul_vec(&linear);
+  for y in 1..n {
+    for x in 1..n {
+      assert!(r[y * (n + 1) + x].abs() < 1e-9);
+    }
+  }
+
+  let areas = mesh.vertex_areas();
+  assert!((areas.iter().sum::<f64>() - mesh.surface_area()).abs() < 1e-9);
+  // Vertex (1, 1) is shared by six faces.
+  assert!((areas[n + 2] - 1.0 / 16.0).abs() < 1e-9);
+}
@@ -0,0 +1,21 @@
+mod builder_test;
+mod normals_test;
+mod evaluation_test;
+mod traversal_test;
+mod validation_test;
+mod repair_test;
+mod simplification_test;
+mod editing_test;
+mod tangents_test;
+mod integrals_test;
+mod sampling_test;
+mod laplacian_test;
+mod geodesics_test;
+mod curvature_test;
+mod smoothing_test;
+mod subdivision_test;
+mod convex_hull_test;
+mod generators_test;
+mod slicing_test;
+mod bvh_test;
+mod voxelization_test;
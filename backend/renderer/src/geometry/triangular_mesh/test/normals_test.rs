@@ -0,0 +1,118 @@
+use super::super::{
+    TriangularMesh, TriangularMeshBuilder, MeshBuildError, VertexNormalMode,
+    FaceNormalMode, NormalWeighting,
+};
+use math::Vector3;
+
+// Builds the corner of a unit cube at the origin: three faces around vertex
+// #0, lying in planes x = 0, y = 0 and z = 0, with normals pointing inwards
+// (+x, +y, +z). The face in plane z = 0 is split into two triangles, so
+// vertex #0 has four adjacent faces.
+fn build_corner(weighting: NormalWeighting) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_normal_weighting(weighting);
+  builder.add_vertices(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(), Vector3::z_unit(),
+      Vector3::new(1.0, 1.0, 0.0)]);
+  builder.add_faces(&[[0, 2, 3], [0, 3, 1], [0, 1, 4], [0, 4, 2]]);
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_flat_and_averaged_normals() {
+  let mesh = build_corner(NormalWeighting::ANGLE);
+  assert_eq!(mesh.vertex_normal_mode(), VertexNormalMode::AVERAGED);
+  assert_eq!(mesh.face_normal_mode(), FaceNormalMode::FLAT);
+  assert_eq!(mesh.face(0).normal(), Some(Vector3::x_unit()));
+  assert_eq!(mesh.face(1).normal(), Some(Vector3::y_unit()));
+  assert_eq!(mesh.face(2).normal(), Some(Vector3::z_unit()));
+  assert_eq!(mesh.face(3).normal(), Some(Vector3::z_unit()));
+
+  // All three planes span an angle of 90 degrees at the corner.
+  assert_eq!(mesh.vertex(0).normal(),
+      Some(Vector3::identity(1.0 / 3.0f64.sqrt())));
+  // Uniform weights count the split face twice.
+  let mesh = build_corner(NormalWeighting::UNIFORM);
+  assert_eq!(mesh.vertex(0).normal(),
+      Some(Vector3::new(1.0, 1.0, 2.0).normalize()));
+  // Areas of the faces are 1/2, 1/2, 1/2, 1/2.
+  let mesh = build_corner(NormalWeighting::AREA);
+  assert_eq!(mesh.vertex(0).normal(),
+      Some(Vector3::new(1.0, 1.0, 2.0).normalize()));
+  assert_eq!(mesh.vertex(4).normal(), Some(Vector3::z_unit()));
+  assert!(mesh.inverted_vertex_normals().is_empty());
+}
+
+#[test]
+fn test_user_specified_normals() {
+  let positions = [Vector3::zero(), Vector3::x_unit(), Vector3::y_unit()];
+
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_vertex_normal_mode(VertexNormalMode::USER_SPECIFIED);
+  builder.add_vertex_with_normal(positions[0], Vector3::new(0.0, 0.0, 2.0));
+  builder.add_vertex_with_normal(positions[1], Vector3::x_unit());
+  builder.add_vertex(positions[2]);
+  builder.add_face(0, 1, 2);
+  assert_eq!(builder.clone().build().unwrap_err(),
+      MeshBuildError::MissingVertexNormal { vertex: 2 });
+
+  builder.set_vertex_normal_mode(VertexNormalMode::AVERAGED);
+  builder.set_face_normal_mode(FaceNormalMode::USER_SPECIFIED);
+  assert_eq!(builder.clone().build().unwrap_err(),
+      MeshBuildError::MissingFaceNormal { face: 0 });
+
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_vertex_normal_mode(VertexNormalMode::USER_SPECIFIED);
+  builder.set_face_normal_mode(FaceNormalMode::PHONG);
+  builder.add_vertex_with_normal(positions[0], Vector3::new(0.0, 0.0, 2.0));
+  builder.add_vertex_with_normal(positions[1], Vector3::x_unit());
+  builder.add_vertex_with_normal(positions[2], -Vector3::z_unit());
+  builder.add_face(0, 1, 2);
+  let mesh = builder.build().unwrap();
+  assert_eq!(mesh.vertex(0).normal(), Some(Vector3::z_unit()));
+  assert_eq!(mesh.face(0).normal(), Some(Vector3::z_unit()));
+  // Vertex #1 is orthogonal to the face, and vertex #2 is below it.
+  assert_eq!(mesh.inverted_vertex_normals(), vec![1, 2]);
+}
+
+#[test]
+fn test_correct_inverted_normals() {
+  // A fan of four faces around vertex #0 in the plane z = 0, three of which
+  // are given normal +z, and one is given a normal pointing downwards. The
+  // plain average is below the last face.
+  let tilted = Vector3::new(1.0, 0.0, -0.5).normalize();
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_face_normal_mode(FaceNormalMode::USER_SPECIFIED);
+  builder.set_normal_weighting(NormalWeighting::UNIFORM);
+  builder.add_vertices(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      -Vector3::x_unit(), -Vector3::y_unit()]);
+  builder.add_face_with_normal(0, 1, 2, Vector3::z_unit());
+  builder.add_face_with_normal(0, 2, 3, Vector3::z_unit());
+  builder.add_face_with_normal(0, 3, 4, Vector3::z_unit());
+  builder.add_face_with_normal(0, 4, 1, tilted);
+  let mesh = builder.build().unwrap();
+
+  let average = (Vector3::z_unit() * 3.0 + tilted).normalize();
+  assert!(average * tilted < 0.0);
+  let n = mesh.vertex(0).normal().unwrap();
+  assert!(n * tilted > 0.0);
+  assert!(n * Vector3::z_unit() > 0.0);
+  assert!((n.len() - 1.0).abs() < 1e-9);
+  assert!(mesh.inverted_vertex_normals().is_empty());
+}
+
+#[test]
+fn test_recompute_after_degenerate_faces() {
+  // The second face is degenerate, and has no normal.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::new(2.0, 0.0, 0.0)]);
+  builder.add_faces(&[[0, 1, 2], [0, 3, 1]]);
+  let mesh = builder.build().unwrap();
+  assert_eq!(mesh.face(0).normal(), Some(Vector3::z_unit()));
+  assert!(mesh.face(1).normal().is_none());
+  assert_eq!(mesh.vertex(3).normal(), None);
+  assert_eq!(mesh.vertex(1).normal(), Some(Vector3::z_unit()));
+}
@@ -0,0 +1,127 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder};
+use math::Vector3;
+
+fn build(positions: &[Vector3], faces: &[[usize; 3]]) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_allow_non_manifold_edges(true);
+  builder.add_vertices(positions);
+  builder.add_faces(faces);
+  builder.build().unwrap()
+}
+
+fn tetrahedron_vertices() -> Vec<Vector3> {
+  vec![Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::z_unit()]
+}
+
+// Faces of a unit cube with outward orientations, the last two of which are
+// its top.
+fn cube() -> (Vec<Vector3>, Vec<[usize; 3]>) {
+  let mut positions = Vec::new();
+  for i in 0..8 {
+    positions.push(Vector3::new((i & 1) as f64, ((i >> 1) & 1) as f64,
+        (i >> 2) as f64));
+  }
+  let faces = vec![
+      [0, 2, 3], [0, 3, 1], [0, 1, 5], [0, 5, 4], [1, 3, 7], [1, 7, 5],
+      [3, 2, 6], [3, 6, 7], [2, 0, 4], [2, 4, 6], [4, 5, 7], [4, 7, 6]];
+  (positions, faces)
+}
+
+#[test]
+fn test_weld_vertices() {
+  // Two triangles sharing a spatial edge, with duplicated vertices #3 and #4
+  // (the latter slightly off), and a sliver triangle collapsed by welding.
+  let mut mesh = build(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::x_unit(), Vector3::new(0.0, 1.0 + 1e-5, 0.0),
+      Vector3::new(1.0, 1.0, 0.0), Vector3::new(1e-5, 0.0, 0.0)],
+      &[[0, 1, 2], [3, 5, 4], [0, 6, 2]]);
+  assert_eq!(mesh.validate().num_components(), 2);
+
+  let report = mesh.weld_vertices(1e-4);
+  assert_eq!(report.vertex_map(),
+      &[Some(0), Some(1), Some(2), Some(1), Some(2), Some(3), Some(0)]);
+  assert_eq!(report.welded_vertices(), vec![3, 4, 6]);
+  assert_eq!(report.removed_faces(), vec![2]);
+  assert_eq!(mesh.num_vertices(), 4);
+  assert_eq!(mesh.num_faces(), 2);
+  assert_eq!(mesh.vertex(2).pos(), Vector3::y_unit());
+  let validation = mesh.validate();
+  assert_eq!(validation.num_components(), 1);
+  assert_eq!(validation.num_boundary_edges(), 4);
+
+  assert!(mesh.weld_vertices(1e-4).is_empty());
+}
+
+#[test]
+fn test_remove_faces_and_vertices() {
+  let mut positions = tetrahedron_vertices();
+  positions.push(Vector3::new(2.0, 0.0, 0.0));
+  positions.push(Vector3::new(5.0, 5.0, 5.0));
+  let mut mesh = build(&positions,
+      &[[0, 2, 1], [0, 1, 3], [1, 0, 3], [0, 1, 4], [0, 3, 2], [1, 2, 3]]);
+
+  let report = mesh.remove_duplicate_faces();
+  assert_eq!(report.removed_faces(), vec![2]);
+  assert_eq!(report.face_map()[3], Some(2));
+  assert_eq!(mesh.num_faces(), 5);
+
+  let report = mesh.remove_degenerate_faces();
+  assert_eq!(report.removed_faces(), vec![2]);
+  assert_eq!(mesh.num_faces(), 4);
+  assert!(mesh.remove_degenerate_faces().is_empty());
+
+  let report = mesh.remove_unreferenced_vertices();
+  assert_eq!(report.removed_vertices(), vec![4, 5]);
+  assert_eq!(mesh.num_vertices(), 4);
+  assert!(mesh.validate().is_watertight());
+}
+
+#[test]
+fn test_orient_faces() {
+  // A tetrahedron with all faces inwards, except face #1.
+  let mut mesh = build(&tetrahedron_vertices(),
+      &[[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 3, 2]]);
+  assert_eq!(mesh.validate().inconsistent_edges().len(), 3);
+  let report = mesh.orient_faces();
+  assert_eq!(report.flipped_faces(), &[0, 2, 3]);
+  assert!(mesh.validate().is_watertight());
+  assert_eq!(mesh.face(0).normal(), Some(-Vector3::z_unit()));
+  assert!(mesh.orient_faces().is_empty());
+
+  // An open strip keeps the orientation of its first face.
+  let mut strip = build(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::new(1.0, 1.0, 0.0), Vector3::new(2.0, 1.0, 0.0)],
+      &[[0, 1, 2], [1, 2, 3], [1, 4, 3]]);
+  let report = strip.orient_faces();
+  assert_eq!(report.flipped_faces(), &[1]);
+  assert!(strip.validate().is_manifold());
+  for f in 0..3 {
+    assert_eq!(strip.face(f).normal(), Some(Vector3::z_unit()));
+  }
+}
+
+#[test]
+fn test_fill_holes() {
+  let (positions, faces) = cube();
+  let mut mesh = build(&positions, &faces[..10]);
+  let mut tetrahedron = build(&tetrahedron_vertices(),
+      &[[0, 2, 1], [0, 1, 3], [0, 3, 2]]);
+
+  assert!(mesh.fill_holes(3).is_empty());
+  let report = mesh.fill_holes(4);
+  assert_eq!(report.added_vertices(), &[8]);
+  assert_eq!(report.added_faces(), &[10, 11, 12, 13]);
+  assert_eq!(mesh.vertex(8).pos(), Vector3::new(0.5, 0.5, 1.0));
+  assert!(mesh.validate().is_watertight());
+  for f in 10..14 {
+    assert_eq!(mesh.face(f).normal(), Some(Vector3::z_unit()));
+  }
+
+  let report = tetrahedron.fill_holes(3);
+  assert!(report.added_vertices().is_empty());
+  assert_eq!(report.added_faces(), &[3]);
+  assert!(tetrahedron.validate().is_watertight());
+}
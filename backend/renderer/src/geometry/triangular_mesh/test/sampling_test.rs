@@ -0,0 +1,88 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder, MeshPoint};
+use common::random::Random;
+use math::Vector3;
+
+// Builds a 4x1 rectangle in the plane z = 0, split into a 1x1 square of two
+// faces on the left and a 3x1 rectangle of two faces on the right, plus a
+// degenerate face.
+fn build_strip() -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[Vector3::zero(), Vector3::x_unit(),
+      Vector3::new(4.0, 0.0, 0.0), Vector3::y_unit(), Vector3::new(1.0, 1.0, 0.0),
+      Vector3::new(4.0, 1.0, 0.0), Vector3::new(2.0, 0.0, 0.0)]);
+  builder.add_faces(&[[0, 1, 4], [0, 4, 3], [1, 6, 2], [1, 2, 5], [1, 5, 4]]);
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_uniform_sampling() {
+  let mesh = build_strip();
+  let sampler = mesh.surface_sampler();
+  assert!((sampler.surface_area() - 4.0).abs() < 1e-9);
+  assert_eq!(sampler.pdf(&MeshPoint::new(0, Vector3::identity(1.0 / 3.0))),
+      0.25);
+  assert_eq!(sampler.pdf(&MeshPoint::new(2, Vector3::identity(1.0 / 3.0))),
+      0.0);
+
+  // Faces are chosen by their areas, and the first number is reused on the
+  // face.
+  let p = sampler.sample(0.0, 0.0).unwrap();
+  assert_eq!(p.face_id(), 0);
+  assert_eq!(p.params(), Vector3::new(1.0, 0.0, 0.0));
+  let p = sampler.sample(0.125, 0.5).unwrap();
+  assert_eq!(p.face_id(), 1);
+  assert_eq!(p.params(), Vector3::new(1.0, 0.0, 0.0));
+  assert_eq!(sampler.sample(0.99, 0.5).unwrap().face_id(), 4);
+
+  // Points are uniformly distributed, e.g. a quarter falls into x < 1 and
+  // the mean is the center.
+  let mut random = Random::new(41);
+  let n = 20000;
+  let mut left = 0;
+  let mut mean = Vector3::zero();
+  for _ in 0..n {
+    let p = sampler.sample(random.next_f64(), random.next_f64()).unwrap();
+    assert!(p.face_id() != 2);
+    let pos = mesh.position(&p);
+    if pos.x() < 1.0 {
+      left += 1;
+    }
+    mean += pos / n as f64;
+  }
+  assert!((left as f64 / n as f64 - 0.25).abs() < 0.01);
+  assert!((mean - Vector3::new(2.0, 0.5, 0.0)).len() < 0.02);
+}
+
+#[test]
+fn test_poisson_disk_sampling() {
+  let mesh = build_strip();
+  let sampler = mesh.surface_sampler();
+  let r = 0.2;
+  let points = sampler.sample_poisson_disk(r, 1000, 7);
+  let positions: Vec<Vector3> = points.iter().map(|p| mesh.position(p))
+      .collect();
+  for i in 0..positions.len() {
+    for j in 0..i {
+      assert!((positions[i] - positions[j]).len() >= r);
+    }
+  }
+  // A maximal set covers the surface with disks of radius r, i.e. at least
+  // area / (pi * r^2) points, while disks of radius r / 2 do not overlap.
+  let pi = ::std::f64::consts::PI;
+  assert!(points.len() as f64 >= 4.0 / (pi * r * r));
+  assert!(points.len() as f64 <= (4.0 + 5.0 * r) / (pi * r * r / 4.0));
+  // Reproducible for the same seed.
+  let again = sampler.sample_poisson_disk(r, 1000, 7);
+  assert_eq!(again.len(), points.len());
+  assert_eq!(mesh.position(&again[10]), positions[10]);
+
+  // Nothing to sample without area.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[Vector3::zero(), Vector3::x_unit(),
+      Vector3::new(2.0, 0.0, 0.0)]);
+  builder.add_face(0, 1, 2);
+  let flat = builder.build().unwrap();
+  let sampler = flat.surface_sampler();
+  assert!(sampler.sample(0.5, 0.5).is_none());
+  assert!(sampler.sample_poisson_disk(r, 10, 7).is_empty());
+}
@@ -0,0 +1,116 @@
+use super::super::{
+    TriangularMesh, TriangularMeshBuilder, SimplificationOptions,
+};
+use geometry::HasSurfaceArea;
+use math::Vector3;
+
+// Builds a grid of n x n unit squares in the plane z = 0, where vertex (i, j)
+// has position (j, i, 0), and vertices of the column j = split are
+// duplicated, so that the grid consists of two halves joined by a seam.
+fn build_split_grid(n: usize, split: usize) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  let mut index = vec![vec![0; n + 1]; n + 1];
+  let mut right = vec![0; n + 1];
+  for (i, row) in index.iter_mut().enumerate() {
+    for (j, id) in row.iter_mut().enumerate() {
+      let p = Vector3::new(j as f64, i as f64, 0.0);
+      *id = builder.add_vertex(p);
+      if split == j {
+        right[i] = builder.add_vertex(p);
+      }
+    }
+  }
+  for i in 0..n {
+    for j in 0..n {
+      let v = |a: usize, b: usize| if split == b && b == j {
+        right[a]
+      } else {
+        index[a][b]
+      };
+      builder.add_faces(&[[v(i, j), v(i, j + 1), v(i + 1, j + 1)],
+          [v(i, j), v(i + 1, j + 1), v(i + 1, j)]]);
+    }
+  }
+  builder.build().unwrap()
+}
+
+fn total_area(mesh: &TriangularMesh) -> f64 {
+  (0..mesh.num_faces()).map(|f| mesh.triangle(f).surface_area()).sum()
+}
+
+#[test]
+fn test_simplify_flat_grid() {
+  let mut mesh =
+      TriangularMesh::new_plane(Vector3::new(3.0, 3.0, 0.0), 6.0, 6.0, 6, 6);
+  let report = mesh.simplify(&SimplificationOptions::new());
+  // All interior vertices are removed at no cost, and boundary vertices are
+  // kept in place.
+  assert_eq!(mesh.num_vertices(), 24);
+  assert_eq!(report.num_collapses(), 25);
+  assert!(report.max_error() < 1e-9);
+  assert!((total_area(&mesh) - 36.0).abs() < 1e-9);
+  let validation = mesh.validate();
+  assert!(validation.is_manifold());
+  assert_eq!(validation.num_boundary_edges(), 24);
+  assert_eq!(validation.genus(), Some(0));
+  for v in 0..mesh.num_vertices() {
+    let p = mesh.vertex(v).pos();
+    assert!(p.x() == 0.0 || p.x() == 6.0 || p.y() == 0.0 || p.y() == 6.0);
+  }
+}
+
+#[test]
+fn test_simplify_to_target() {
+  let torus = || TriangularMesh::new_torus(Vector3::zero(), 2.0, 1.0, 16, 16);
+  let mut mesh = torus();
+  assert_eq!(mesh.num_faces(), 512);
+  let mut options = SimplificationOptions::new();
+  options.set_target_faces(100);
+  // Texture seams of the torus are not kept, so that it is simplified evenly.
+  options.set_preserve_seams(false);
+  let report = mesh.simplify(&options);
+  assert!(mesh.num_faces() <= 100 && mesh.num_faces() >= 98);
+  assert_eq!(report.vertex_map().len(), 256);
+  assert_eq!(report.vertex_map().iter().filter(|v| v.is_some()).count(),
+      mesh.num_vertices());
+  assert_eq!(report.face_map().len(), 512);
+  let kept: Vec<usize> = report.face_map().iter().filter_map(|f| *f).collect();
+  assert_eq!(kept, (0..mesh.num_faces()).collect::<Vec<_>>());
+  let validation = mesh.validate();
+  assert!(validation.is_watertight());
+  assert_eq!(validation.genus(), Some(1));
+  assert!(validation.degenerate_faces().is_empty());
+  assert!(mesh.inverted_vertex_normals().is_empty());
+
+  // The error bound stops the simplification before the target.
+  let mut bounded = torus();
+  let max_error = report.max_error() * 0.5;
+  options.set_max_error(max_error);
+  let report = bounded.simplify(&options);
+  assert!(report.max_error() <= max_error);
+  assert!(bounded.num_faces() > 100 && bounded.num_faces() < 512);
+}
+
+#[test]
+fn test_simplify_with_seams() {
+  let mut mesh = build_split_grid(6, 3);
+  assert_eq!(mesh.num_vertices(), 56);
+  let mut options = SimplificationOptions::new();
+  options.set_preserve_boundaries(false);
+  options.set_max_error(1e-9);
+  let report = mesh.simplify(&options);
+  assert!(report.num_collapses() > 0);
+  // Both copies of the seam are kept in place.
+  let on_seam = (0..mesh.num_vertices())
+      .filter(|v| (mesh.vertex(*v).pos().x() - 3.0).abs() < 1e-9)
+      .count();
+  assert_eq!(on_seam, 14);
+  assert!((total_area(&mesh) - 36.0).abs() < 1e-6);
+
+  let mut cracked = build_split_grid(6, 3);
+  options.set_preserve_seams(false);
+  options.set_max_error(1e20);
+  options.set_target_faces(10);
+  cracked.simplify(&options);
+  assert!(cracked.num_faces() <= 10);
+}
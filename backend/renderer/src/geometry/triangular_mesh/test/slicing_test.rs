@@ -0,0 +1,143 @@
+use super::super::TriangularMesh;
+use geometry::{HasVolume, Plane};
+use math::Vector3;
+use std::f64::consts::PI;
+
+// Returns the signed area enclosed by a loop, projected along a normal.
+fn loop_area(points: &[Vector3], normal: Vector3) -> f64 {
+  let n = points.len();
+  (0..n).map(|i| (points[i] ^ points[(i + 1) % n]) * normal).sum::<f64>() / 2.0
+}
+
+#[test]
+fn test_cross_section_sphere() {
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1.0, 4);
+  let plane = Plane::new(Vector3::z_unit(), 0.6);
+  let sections = mesh.cross_section(&plane);
+  assert_eq!(sections.len(), 1);
+  let section = &sections[0];
+  assert!(section.is_closed());
+  for p in section.points().iter() {
+    assert!((p.z() - 0.6).abs() < 1e-12);
+    let r = (p.x() * p.x() + p.y() * p.y()).sqrt();
+    assert!(r < 0.8 + 1e-12 && r > 0.79);
+  }
+  // The loop runs counter-clockwise around the plane normal.
+  assert!((section.length() / (1.6 * PI) - 1.0).abs() < 0.01);
+  assert!((loop_area(section.points(), Vector3::z_unit()) / (0.64 * PI)
+      - 1.0).abs() < 0.01);
+
+  // The opposite plane reverses the loop.
+  let sections = mesh.cross_section(&plane.flip());
+  assert_eq!(sections.len(), 1);
+  assert!(loop_area(sections[0].points(), -Vector3::z_unit()) > 0.0);
+
+  assert!(mesh.cross_section(&Plane::new(Vector3::z_unit(), 2.0)).is_empty());
+}
+
+#[test]
+fn test_cross_section_torus() {
+  let mesh = TriangularMesh::new_torus(Vector3::zero(), 2.0, 0.5, 64, 32);
+  // Two concentric loops, the inner one around a hole.
+  let sections = mesh.cross_section(&Plane::new(Vector3::z_unit(), 0.1));
+  assert_eq!(sections.len(), 2);
+  let mut areas: Vec<f64> = sections.iter()
+      .map(|s| loop_area(s.points(), Vector3::z_unit()))
+      .collect();
+  areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+  assert!(areas[0] < 0.0 && areas[1] > 0.0);
+  assert!(areas[1] > -areas[0]);
+  // Two separate loops around the tube.
+  let sections = mesh.cross_section(&Plane::new(Vector3::x_unit(), 0.0));
+  assert_eq!(sections.len(), 2);
+  for section in sections.iter() {
+    assert!(section.is_closed());
+    let area = loop_area(section.points(), Vector3::x_unit());
+    assert!((area / (0.25 * PI) - 1.0).abs() < 0.01);
+  }
+}
+
+#[test]
+fn test_cross_section_open() {
+  let mesh = TriangularMesh::new_plane(Vector3::zero(), 2.0, 1.0, 4, 4);
+  let sections = mesh.cross_section(&Plane::new(Vector3::x_unit(), 0.3));
+  assert_eq!(sections.len(), 1);
+  assert!(!sections[0].is_closed());
+  assert!((sections[0].length() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_split_through_vertices() {
+  // The equator of the sphere is a ring of vertices.
+  let mesh = TriangularMesh::new_uv_sphere(Vector3::zero(), 1.0, 8, 16);
+  let plane = Plane::new(Vector3::z_unit(), 0.0);
+  let sections = mesh.cross_section(&plane);
+  assert_eq!(sections.len(), 1);
+  assert_eq!(sections[0].points().len(), 16);
+
+  let (below, above) = mesh.split_by_plane(&plane, false);
+  assert_eq!(below.num_faces() + above.num_faces(), mesh.num_faces());
+  assert_eq!(below.validate().num_boundary_loops(), 1);
+  assert_eq!(above.validate().num_boundary_loops(), 1);
+
+  let (below, above) = mesh.split_by_plane(&plane, true);
+  assert!((below.volume() - mesh.volume() / 2.0).abs() < 1e-12);
+  assert!((above.volume() - mesh.volume() / 2.0).abs() < 1e-12);
+  for v in 0..below.num_vertices() {
+    assert!(below.vertex(v).pos().z() <= 1e-12);
+  }
+}
+
+#[test]
+fn test_split_attributes() {
+  let mesh = TriangularMesh::new_uv_sphere(Vector3::zero(), 1.0, 16, 32);
+  let plane = Plane::new(Vector3::new(1.0, 1.0, 1.0).normalize(), 0.3);
+  let (below, above) = mesh.split_by_plane(&plane, true);
+  assert!((below.volume() + above.volume() - mesh.volume()).abs() < 1e-9);
+  for half in [&below, &above].iter() {
+    let report = half.validate();
+    assert!(report.is_manifold());
+    assert!(report.degenerate_faces().is_empty());
+    for v in 0..half.num_vertices() {
+      let normal = half.vertex(v).normal().unwrap();
+      assert!((normal.len() - 1.0).abs() < 1e-9);
+    }
+    for f in 0..half.num_faces() {
+      for uv in half.corner_uvs(f).iter().flat_map(|uvs| uvs.iter()) {
+        assert!(uv.u() >= 0.0 && uv.u() <= 1.0 && uv.v() >= 0.0
+            && uv.v() <= 1.0);
+      }
+    }
+  }
+  // Cut faces are on their sides of the plane.
+  for f in 0..below.num_faces() {
+    let c = below.triangle(f).centroid();
+    assert!(plane.signed_distance(c) <= 1e-12);
+  }
+}
+
+#[test]
+fn test_split_caps_with_holes() {
+  let mesh = TriangularMesh::new_torus(Vector3::zero(), 2.0, 0.5, 64, 32);
+  // The cap of each half is an annulus.
+  let (below, above) = mesh.split_by_plane(
+      &Plane::new(Vector3::z_unit(), 0.0), true);
+  assert!((below.volume() - mesh.volume() / 2.0).abs() < 1e-9);
+  assert!((above.volume() - mesh.volume() / 2.0).abs() < 1e-9);
+  // Two caps of each half.
+  let (below, above) = mesh.split_by_plane(
+      &Plane::new(Vector3::x_unit(), 0.3), true);
+  assert!((below.volume() + above.volume() - mesh.volume()).abs() < 1e-9);
+  let mut welded = below.clone();
+  welded.weld_vertices(1e-9);
+  assert!(welded.validate().is_watertight());
+  assert_eq!(welded.validate().num_components(), 1);
+
+  // A box cut diagonally.
+  let mesh = TriangularMesh::new_box(Vector3::zero(),
+      Vector3::new(1.0, 1.0, 1.0));
+  let normal = Vector3::new(1.0, 1.0, 0.0).normalize();
+  let (below, above) = mesh.split_by_plane(&Plane::new(normal, 0.0), true);
+  assert!((below.volume() - 0.5).abs() < 1e-12);
+  assert!((above.volume() - 0.5).abs() < 1e-12);
+}
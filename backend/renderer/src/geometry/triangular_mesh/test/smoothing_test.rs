@@ -0,0 +1,116 @@
+use super::super::{TriangularMesh, SmoothingOptions, SmoothingWeighting};
+use common::random::Random;
+use geometry::HasVolume;
+use math::Vector3;
+
+// Builds an n x n grid in the plane z = 0, with columns at x = (i / n)^2 and
+// rows at y = j / n, and each rectangle split along a diagonal. Heights are
+// displaced by the given function of (i, j).
+fn build_grid<F>(n: usize, height: F) -> TriangularMesh
+    where F: Fn(usize, usize) -> f64 {
+  let index = |t: f64| (t * n as f64).round() as usize;
+  TriangularMesh::new_parametric(|u, v| {
+    Vector3::new(u * u, v, height(index(u), index(v)))
+  }, n, n, false, false)
+}
+
+// Builds a unit UV sphere with given numbers of rings and segments, with
+// vertices displaced along their normals by uniform noise of given amplitude.
+fn build_sphere(rings: usize, segments: usize, noise: f64) -> TriangularMesh {
+  let mut mesh =
+      TriangularMesh::new_uv_sphere(Vector3::zero(), 1.0, rings, segments);
+  let mut random = Random::new(7);
+  for vertex in mesh.vertices.iter_mut() {
+    vertex.pos *= 1.0 + noise * (random.next_f64() - 0.5);
+  }
+  mesh
+}
+
+// Returns the variance of distances from vertices to the origin.
+fn radius_variance(mesh: &TriangularMesh) -> f64 {
+  let n = mesh.num_vertices() as f64;
+  let radii: Vec<f64> =
+      (0..mesh.num_vertices()).map(|v| mesh.vertex(v).pos().len()).collect();
+  let mean = radii.iter().sum::<f64>() / n;
+  radii.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n
+}
+
+#[test]
+fn test_laplacian() {
+  let n = 10;
+  let noisy = build_grid(n, |x, y| if (x + y) % 2 == 0 { 0.01 } else { -0.01 });
+  let mut mesh = noisy.clone();
+  let mut options = SmoothingOptions::new();
+  options.set_iterations(10);
+  mesh.smooth_laplacian(&options);
+  for y in 0..n + 1 {
+    for x in 0..n + 1 {
+      let v = y * (n + 1) + x;
+      let (before, after) = (noisy.vertex(v).pos(), mesh.vertex(v).pos());
+      if x == 0 || y == 0 || x == n || y == n {
+        assert_eq!(before, after);
+      } else {
+        assert!(after.z().abs() < 0.2 * before.z().abs());
+      }
+    }
+  }
+  let center = n / 2 * (n + 2);
+  assert!(mesh.vertex(center).normal().unwrap() * Vector3::z_unit() > 0.999);
+
+  // Boundary vertices move along the boundary, e.g. corners move inwards,
+  // and others follow them slowly.
+  options.set_keep_boundaries(false);
+  options.set_recompute_normals(false);
+  let mut mesh = noisy.clone();
+  mesh.smooth_laplacian(&options);
+  assert!(mesh.vertex(0).pos().x() > 0.0 && mesh.vertex(0).pos().y() > 0.0);
+  assert!(mesh.vertex(n / 2).pos().y().abs() < 1e-3);
+  assert!(mesh.vertex(n / 2).pos().z().abs() < 0.2 * 0.01);
+  assert_eq!(mesh.vertex(center).normal(), noisy.vertex(center).normal());
+}
+
+#[test]
+fn test_cotangent() {
+  // Cotangent weights keep a flat mesh in place, as linear functions are
+  // harmonic, while uniform weights even out the spacing of columns.
+  let n = 6;
+  let flat = build_grid(n, |_, _| 0.0);
+  let mut options = SmoothingOptions::new();
+  options.set_weighting(SmoothingWeighting::COTANGENT);
+  options.set_iterations(5);
+  let mut mesh = flat.clone();
+  mesh.smooth_laplacian(&options);
+  for v in 0..mesh.num_vertices() {
+    assert!((mesh.vertex(v).pos() - flat.vertex(v).pos()).len() < 1e-9);
+  }
+  options.set_weighting(SmoothingWeighting::UNIFORM);
+  mesh.smooth_laplacian(&options);
+  assert!((mesh.vertex(n + 2).pos() - flat.vertex(n + 2).pos()).len() > 1e-3);
+  assert!(mesh.vertex(n + 2).pos().z().abs() < 1e-9);
+
+  // Noise is removed along the normals.
+  let noisy = build_sphere(16, 32, 0.05);
+  let mut mesh = noisy.clone();
+  options.set_weighting(SmoothingWeighting::COTANGENT);
+  mesh.smooth_taubin(&options);
+  assert!(radius_variance(&mesh) < 0.5 * radius_variance(&noisy));
+}
+
+#[test]
+fn test_taubin() {
+  // Laplacian smoothing shrinks the sphere, while Taubin smoothing keeps its
+  // volume.
+  let noisy = build_sphere(16, 32, 0.05);
+  let volume = noisy.volume();
+  let mut options = SmoothingOptions::new();
+  options.set_iterations(20);
+  let mut laplacian = noisy.clone();
+  laplacian.smooth_laplacian(&options);
+  assert!(laplacian.volume() < 0.9 * volume);
+  let mut taubin = noisy.clone();
+  taubin.smooth_taubin(&options);
+  assert!((taubin.volume() - volume).abs() < 0.02 * volume);
+
+  assert!(radius_variance(&taubin) < 0.25 * radius_variance(&noisy));
+}
+
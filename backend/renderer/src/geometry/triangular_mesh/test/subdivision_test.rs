@@ -0,0 +1,157 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder, SubdivisionOptions};
+use geometry::HasBoundingBox3;
+use math::{Vector2, Vector3};
+use std::f64::consts::PI;
+
+// Builds a regular octahedron with vertices on the unit axes.
+fn build_octahedron() -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertex(Vector3::z_unit());
+  builder.add_vertex(Vector3::x_unit());
+  builder.add_vertex(Vector3::y_unit());
+  builder.add_vertex(-Vector3::x_unit());
+  builder.add_vertex(-Vector3::y_unit());
+  builder.add_vertex(-Vector3::z_unit());
+  builder.add_faces(&[[0, 1, 2], [0, 2, 3], [0, 3, 4], [0, 4, 1],
+      [5, 2, 1], [5, 3, 2], [5, 4, 3], [5, 1, 4]]);
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_subdivide() {
+  let mesh = build_octahedron();
+  let mut options = SubdivisionOptions::new();
+  let subdivided = mesh.subdivide_loop(&options);
+  assert_eq!(subdivided.num_vertices(), 6 + 12);
+  assert_eq!(subdivided.num_faces(), 8 * 4);
+  // Vertices of valence 4 have beta = 31 / 256.
+  assert_eq!(subdivided.vertex(0).pos(), Vector3::z_unit() * (1.0 - 0.484375));
+  // The vertex on the edge between vertex #0 and #1 of face #0.
+  let e01 = subdivided.face(0).vertices()[1];
+  let e12 = subdivided.face(3).vertices()[1];
+  assert_eq!(subdivided.face(1).vertices(), [e01, 1, e12]);
+  assert_eq!(subdivided.vertex(e01).pos(), Vector3::new(0.375, 0.0, 0.375));
+
+  options.set_levels(3);
+  let subdivided = mesh.subdivide_loop(&options);
+  assert_eq!(subdivided.num_faces(), 8 * 64);
+  let validation = subdivided.validate();
+  assert!(validation.is_watertight());
+  assert_eq!(validation.genus(), Some(0));
+  for v in 0..subdivided.num_vertices() {
+    let n = subdivided.vertex(v).normal().unwrap();
+    assert!(n * subdivided.vertex(v).pos().normalize() > 0.9);
+  }
+}
+
+#[test]
+fn test_limit() {
+  // Limit positions do not change with subdivision.
+  let mesh = build_octahedron();
+  let mut options = SubdivisionOptions::new();
+  let limit = mesh.loop_limit_positions(&options);
+  let subdivided = mesh.subdivide_loop(&options);
+  let subdivided_limit = subdivided.loop_limit_positions(&options);
+  for v in 0..mesh.num_vertices() {
+    assert!((limit[v] - subdivided_limit[v]).len() < 1e-9);
+    // chi = 31 / 220 for vertices of valence 4.
+    assert!((limit[v].len() - 24.0 / 55.0).abs() < 1e-9);
+  }
+
+  options.set_levels(2);
+  options.set_project_to_limit(true);
+  let projected = mesh.subdivide_loop(&options);
+  options.set_project_to_limit(false);
+  let limit = mesh.subdivide_loop(&options).loop_limit_positions(&options);
+  assert_eq!(limit.len(), projected.num_vertices());
+  for (v, p) in limit.iter().enumerate() {
+    assert!((projected.vertex(v).pos() - *p).len() < 1e-9);
+  }
+}
+
+#[test]
+fn test_creases() {
+  // Without creases, the cube is rounded off.
+  let cube = TriangularMesh::new_box(Vector3::identity(0.5), Vector3::one());
+  let mut options = SubdivisionOptions::new();
+  options.set_levels(2);
+  let bounds = cube.subdivide_loop(&options).bounding_box3();
+  assert!(bounds.max_x() < 0.95 && bounds.min_x() > 0.05);
+
+  // Its edges are creases by angle, so that the corners are kept in place,
+  // and vertices on the edges stay on them.
+  options.set_crease_angle(PI / 4.0);
+  let subdivided = cube.subdivide_loop(&options);
+  assert_eq!(subdivided.bounding_box3(), cube.bounding_box3());
+  for v in 0..8 {
+    assert_eq!(subdivided.vertex(v).pos(), cube.vertex(v).pos());
+  }
+  for v in 0..subdivided.num_vertices() {
+    let p = subdivided.vertex(v).pos();
+    let on_faces = p.to_array().iter()
+        .filter(|c| c.abs() < 1e-9 || (*c - 1.0).abs() < 1e-9)
+        .count();
+    assert!(on_faces >= 1);
+  }
+  let limit = subdivided.loop_limit_positions(&options);
+  assert_eq!(limit[0], Vector3::zero());
+
+  // An explicit crease, e.g. along the edge between vertex #0 and #1, makes
+  // the edge vertex the midpoint.
+  let mut options = SubdivisionOptions::new();
+  options.add_crease_edge(1, 0);
+  let subdivided = cube.subdivide_loop(&options);
+  let e01 = subdivided.face(4 * 4).vertices()[1];
+  assert_eq!(subdivided.vertex(e01).pos(), Vector3::new(0.5, 0.0, 0.0));
+}
+
+#[test]
+fn test_boundary() {
+  // A flat grid stays flat, and vertices on its boundary (other than the
+  // corners) stay on it.
+  let n = 4;
+  let mut builder = TriangularMeshBuilder::new();
+  for y in 0..n + 1 {
+    for x in 0..n + 1 {
+      let v = builder.add_vertex(Vector3::new(x as f64, y as f64, 0.0));
+      builder.set_vertex_uv(v, Vector2::new(x as f64, y as f64) / n as f64);
+    }
+  }
+  for y in 0..n {
+    for x in 0..n {
+      let a = y * (n + 1) + x;
+      builder.add_faces(&[[a, a + 1, a + n + 2], [a, a + n + 2, a + n + 1]]);
+    }
+  }
+  let uvs = [Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0),
+      Vector2::new(1.0, 1.0)];
+  builder.set_face_uvs(0, uvs);
+  let mesh = builder.build().unwrap();
+  let subdivided = mesh.subdivide_loop(&SubdivisionOptions::new());
+  assert_eq!(subdivided.validate().num_boundary_loops(), 1);
+  let mut num_on_edge = 0;
+  for v in 0..subdivided.num_vertices() {
+    let p = subdivided.vertex(v).pos();
+    assert_eq!(p.z(), 0.0);
+    if v < mesh.num_vertices() {
+      let original = mesh.vertex(v).pos();
+      let corner = original.y() == 0.0 || original.y() == n as f64;
+      if original.x() == 0.0 && !corner {
+        assert_eq!(p.x(), 0.0);
+      }
+    }
+    if p.x() == 0.0 {
+      num_on_edge += 1;
+    }
+  }
+  assert_eq!(num_on_edge, 2 * n - 1);
+  assert!(subdivided.vertex(0).pos().x() > 0.0);
+
+  // Texture coordinates are interpolated linearly.
+  let e01 = subdivided.face(0).vertices()[1];
+  assert_eq!(subdivided.vertex(e01).uv(),
+      Some(Vector2::new(0.5, 0.0) / n as f64));
+  assert_eq!(subdivided.face(3).uvs().unwrap(), [Vector2::new(0.5, 0.0),
+      Vector2::new(1.0, 0.5), Vector2::new(0.5, 0.5)]);
+  assert!(subdivided.face(4).uvs().is_none());
+}
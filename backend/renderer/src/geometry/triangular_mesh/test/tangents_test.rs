@@ -0,0 +1,178 @@
+use super::super::{
+    TriangularMesh, TriangularMeshBuilder, MeshPoint, FaceNormalMode,
+};
+use math::{Vector2, Vector3};
+
+// Builds a 2x1 rectangle in the plane z = 0 with four faces, whose right half
+// is textured with u = x - 1 and v = y, and left half is mirrored, i.e.
+// u = 1 - x, both given as corner texture coordinates. The mirror seam runs
+// through vertices #1 and #4 at x = 1.
+fn build_mirrored() -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  for y in 0..2 {
+    for x in 0..3 {
+      builder.add_vertex(Vector3::new(x as f64, y as f64, 0.0));
+    }
+  }
+  let faces = [[0, 1, 4], [0, 4, 3], [1, 2, 5], [1, 5, 4]];
+  for (f, v) in faces.iter().enumerate() {
+    let id = builder.add_face(v[0], v[1], v[2]);
+    let uv = |i: usize| {
+      let (x, y) = ((v[i] % 3) as f64, (v[i] / 3) as f64);
+      if f < 2 { Vector2::new(1.0 - x, y) } else { Vector2::new(x - 1.0, y) }
+    };
+    builder.set_face_uvs(id, [uv(0), uv(1), uv(2)]);
+  }
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_corner_uvs() {
+  let mesh = build_mirrored();
+  assert_eq!(mesh.vertex(1).uv(), None);
+  assert_eq!(mesh.face(0).uvs(), Some([Vector2::new(1.0, 0.0),
+      Vector2::zero(), Vector2::new(0.0, 1.0)]));
+  // Vertex #1 has the same texture coordinates on both sides of the seam.
+  let left = MeshPoint::new(0, Vector3::new(0.0, 1.0, 0.0));
+  let right = MeshPoint::new(2, Vector3::new(1.0, 0.0, 0.0));
+  assert_eq!(mesh.uv(&left), Some(Vector2::zero()));
+  assert_eq!(mesh.uv(&right), Some(Vector2::zero()));
+  let p = MeshPoint::new(1, Vector3::identity(1.0 / 3.0));
+  assert_eq!(mesh.uv(&p), Some(Vector2::new(2.0 / 3.0, 2.0 / 3.0)));
+
+  // Corner texture coordinates override those of vertices.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.add_vertices(&[Vector3::zero(), Vector3::x_unit(), Vector3::y_unit()]);
+  for v in 0..3 {
+    builder.set_vertex_uv(v, Vector2::zero());
+  }
+  builder.add_face(0, 1, 2);
+  builder.set_face_uvs(0, [Vector2::zero(), Vector2::new(1.0, 0.0),
+      Vector2::new(0.0, 1.0)]);
+  let mesh = builder.build().unwrap();
+  assert_eq!(mesh.corner_uvs(0), mesh.face(0).uvs());
+  let p = MeshPoint::new(0, Vector3::identity(1.0 / 3.0));
+  assert_eq!(mesh.tangent(&p), Some(Vector3::x_unit()));
+}
+
+#[test]
+fn test_tangent_frames() {
+  let mesh = build_mirrored();
+  // Away from the seam, all corners of a vertex share a frame.
+  let right = mesh.vertex(2).tangent_frame().unwrap();
+  assert_eq!(right.tangent(), Vector3::x_unit());
+  assert_eq!(right.bitangent(), Vector3::y_unit());
+  assert_eq!(right.sign(), 1.0);
+  let left = mesh.vertex(0).tangent_frame().unwrap();
+  assert_eq!(left.tangent(), -Vector3::x_unit());
+  assert_eq!(left.bitangent(), Vector3::y_unit());
+  assert_eq!(left.sign(), -1.0);
+  assert_eq!(mesh.vertex(3).tangent_frame(), Some(left));
+
+  // Corners on the seam are split by handedness.
+  assert_eq!(mesh.vertex(1).tangent_frame(), None);
+  assert_eq!(mesh.vertex(4).tangent_frame(), None);
+  assert_eq!(mesh.face(0).tangent_frame(1), Some(left));
+  assert_eq!(mesh.face(2).tangent_frame(0), Some(right));
+
+  let p = MeshPoint::new(1, Vector3::identity(1.0 / 3.0));
+  assert_eq!(mesh.tangent(&p), Some(-Vector3::x_unit()));
+  assert_eq!(mesh.bitangent(&p), Some(Vector3::y_unit()));
+  let p = MeshPoint::new(3, Vector3::identity(1.0 / 3.0));
+  assert_eq!(mesh.tangent(&p), Some(Vector3::x_unit()));
+  assert_eq!(mesh.bitangent(&p), Some(Vector3::y_unit()));
+}
+
+#[test]
+fn test_tangent_frames_averaged() {
+  // A roof folded along the x axis, textured with u = x, so that tangents
+  // are +x on both sides and bitangents are averaged across the fold.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_face_normal_mode(FaceNormalMode::PHONG);
+  let positions = [Vector3::new(0.0, -1.0, 0.0), Vector3::new(1.0, -1.0, 0.0),
+      Vector3::new(1.0, 0.0, 1.0), Vector3::new(0.0, 0.0, 1.0),
+      Vector3::new(0.0, 1.0, 0.0), Vector3::new(1.0, 1.0, 0.0)];
+  for p in positions.iter() {
+    let v = builder.add_vertex(*p);
+    builder.set_vertex_uv(v, Vector2::new(p.x(), p.y()));
+  }
+  builder.add_faces(&[[0, 1, 2], [0, 2, 3], [3, 2, 5], [3, 5, 4]]);
+  let mut mesh = builder.build().unwrap();
+  let frame = mesh.vertex(2).tangent_frame().unwrap();
+  assert_eq!(frame.tangent(), Vector3::x_unit());
+  assert_eq!(frame.bitangent(), Vector3::y_unit());
+  assert_eq!(frame.sign(), 1.0);
+
+  // Frames are refreshed after edits.
+  mesh.flip_edge(2).unwrap();
+  assert_eq!(mesh.check_invariants(), Ok(()));
+  let frame = mesh.vertex(2).tangent_frame().unwrap();
+  assert_eq!(frame.tangent(), Vector3::x_unit());
+  assert_eq!(frame.bitangent(), Vector3::y_unit());
+}
+
+#[test]
+fn test_edits_keep_corner_uvs() {
+  let mut mesh = build_mirrored();
+  // Splits the seam edge between vertex #1 and #4, in faces #0 and #3.
+  let e = (0..mesh.num_edges())
+      .find(|e| mesh.edge(*e).start_vertex() == 4
+          && mesh.edge(*e).end_vertex() == 1)
+      .unwrap();
+  let m = mesh.split_edge(e, 0.25);
+  assert_eq!(mesh.check_invariants(), Ok(()));
+  assert_eq!(mesh.vertex(m).pos(), Vector3::new(1.0, 0.75, 0.0));
+  for f in 0..mesh.num_faces() {
+    let face = mesh.face(f);
+    let i = match face.vertices().iter().position(|v| *v == m) {
+      Some(i) => i,
+      None => continue,
+    };
+    assert_eq!(face.uvs().unwrap()[i], Vector2::new(0.0, 0.75));
+    let sign = if mesh.triangle(f).centroid().x() < 1.0 { -1.0 } else { 1.0 };
+    assert_eq!(face.tangent_frame(i).unwrap().sign(), sign);
+  }
+
+  // Splitting a face interpolates its corner texture coordinates.
+  let m = mesh.split_face(&MeshPoint::new(2, Vector3::new(0.5, 0.25, 0.25)));
+  let p = mesh.vertex(m).pos();
+  assert_eq!(p, Vector3::new(1.5, 0.25, 0.0));
+  let f = (0..mesh.num_faces())
+      .find(|f| mesh.face(*f).vertices().contains(&m))
+      .unwrap();
+  let params = mesh.mesh_point_at(f, p).unwrap();
+  assert_eq!(mesh.uv(&params), Some(Vector2::new(0.5, 0.25)));
+}
+
+#[test]
+fn test_weld_keeps_uv_seams() {
+  // A 2x1 rectangle with duplicated vertices along x = 1, as exported by many
+  // tools, whose left half is mirrored (u = 1 - x) and right half is placed
+  // elsewhere in the texture (u = x + 4).
+  let mut builder = TriangularMeshBuilder::new();
+  let mut add = |x: f64, y: f64, u: f64| {
+    let v = builder.add_vertex(Vector3::new(x, y, 0.0));
+    builder.set_vertex_uv(v, Vector2::new(u, y));
+    v
+  };
+  let (a, b, c, d) = (add(0.0, 0.0, 1.0), add(1.0, 0.0, 0.0),
+      add(1.0, 1.0, 0.0), add(0.0, 1.0, 1.0));
+  let (e, f, g, h) = (add(1.0, 0.0, 5.0), add(2.0, 0.0, 6.0),
+      add(2.0, 1.0, 6.0), add(1.0, 1.0, 5.0));
+  builder.add_faces(&[[a, b, c], [a, c, d], [e, f, g], [e, g, h]]);
+  let mut mesh = builder.build().unwrap();
+  let left = mesh.vertex(b).tangent_frame().unwrap();
+  let right = mesh.vertex(e).tangent_frame().unwrap();
+  assert_eq!(left.sign(), -1.0);
+  assert_eq!(right.sign(), 1.0);
+
+  mesh.weld_vertices(1e-6);
+  assert_eq!(mesh.num_vertices(), 6);
+  assert_eq!(mesh.face(0).uvs(), Some([Vector2::new(1.0, 0.0),
+      Vector2::zero(), Vector2::new(0.0, 1.0)]));
+  assert_eq!(mesh.face(2).uvs(), Some([Vector2::new(5.0, 0.0),
+      Vector2::new(6.0, 0.0), Vector2::new(6.0, 1.0)]));
+  assert_eq!(mesh.vertex(1).tangent_frame(), None);
+  assert_eq!(mesh.face(0).tangent_frame(1), Some(left));
+  assert_eq!(mesh.face(2).tangent_frame(0), Some(right));
+}
@@ -0,0 +1,152 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder};
+use math::Vector3;
+
+// Builds a grid of n x n unit squares in the plane z = 0, each split into two
+// triangles along its diagonal. Vertex (i, j) has index i * (n + 1) + j and
+// position (j, i, 0). Squares listed in `holes` (as (row, column)) are left
+// out.
+fn build_grid(n: usize, holes: &[(usize, usize)]) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  for i in 0..(n + 1) {
+    for j in 0..(n + 1) {
+      builder.add_vertex(Vector3::new(j as f64, i as f64, 0.0));
+    }
+  }
+  for i in 0..n {
+    for j in 0..n {
+      if holes.contains(&(i, j)) {
+        continue;
+      }
+      let v = i * (n + 1) + j;
+      builder.add_faces(&[[v, v + 1, v + n + 2], [v, v + n + 2, v + n + 1]]);
+    }
+  }
+  builder.build().unwrap()
+}
+
+fn sorted<I: Iterator<Item = usize>>(iter: I) -> Vec<usize> {
+  let mut v: Vec<usize> = iter.collect();
+  v.sort();
+  v
+}
+
+#[test]
+fn test_vertex_iterators() {
+  let mesh = build_grid(2, &[]);
+  // The diagonals go from bottom-left to top-right, so the center vertex is
+  // adjacent to 6 faces and 6 vertices.
+  assert_eq!(sorted(mesh.vertex_faces(4)), vec![0, 1, 3, 4, 6, 7]);
+  assert_eq!(sorted(mesh.vertex_neighbors(4)), vec![0, 1, 3, 5, 7, 8]);
+  assert_eq!(mesh.outgoing_edges(4).count(), 6);
+  for e in mesh.outgoing_edges(4) {
+    assert_eq!(mesh.edge(e).start_vertex(), 4);
+  }
+
+  // Boundary vertices, whose neighbors can only be reached through incoming
+  // edges.
+  assert_eq!(sorted(mesh.vertex_neighbors(0)), vec![1, 3, 4]);
+  assert_eq!(sorted(mesh.vertex_neighbors(2)), vec![1, 5]);
+  assert_eq!(sorted(mesh.vertex_neighbors(6)), vec![3, 7]);
+  assert_eq!(sorted(mesh.vertex_faces(2)), vec![2]);
+  assert_eq!(mesh.outgoing_edges(2).count(), 1);
+}
+
+#[test]
+fn test_rotational_order() {
+  let mesh = build_grid(2, &[]);
+  // Each outgoing edge follows the twin of the previous edge of the last one.
+  let edges: Vec<usize> = mesh.outgoing_edges(4).collect();
+  for i in 0..edges.len() {
+    let prev = mesh.edge(edges[i]).prev_edge();
+    assert_eq!(mesh.edge(prev).twin_edge(), Some(edges[(i + 1) % 6]));
+  }
+  // Neighbors of the center vertex turn counter-clockwise around it, i.e.
+  // their angles only decrease once, when wrapping around.
+  let center = mesh.vertex(4).pos();
+  let angles: Vec<f64> = mesh.vertex_neighbors(4)
+      .map(|u| {
+        let d = mesh.vertex(u).pos() - center;
+        d.y().atan2(d.x())
+      })
+      .collect();
+  assert_eq!((0..6).filter(|i| angles[(i + 1) % 6] < angles[*i]).count(), 1);
+  let faces: Vec<usize> = mesh.vertex_faces(4).collect();
+  for i in 0..6 {
+    assert_eq!(mesh.edge(edges[i]).face(), faces[i]);
+  }
+
+  // Walks around boundary vertices go from boundary to boundary.
+  assert_eq!(mesh.vertex_neighbors(0).collect::<Vec<_>>(), vec![1, 4, 3]);
+  assert_eq!(mesh.vertex_neighbors(1).collect::<Vec<_>>(), vec![2, 5, 4, 0]);
+  assert_eq!(mesh.vertex_neighbors(8).collect::<Vec<_>>(), vec![7, 4, 5]);
+}
+
+#[test]
+fn test_non_manifold_vertices() {
+  // Two triangles touching at vertex 0, and three triangles sharing the edge
+  // 0 -> 5.
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_allow_non_manifold_edges(true);
+  builder.add_vertices(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      -Vector3::x_unit(), -Vector3::y_unit(), Vector3::z_unit(),
+      Vector3::new(1.0, 1.0, 1.0)]);
+  builder.add_faces(&[[0, 1, 2], [0, 3, 4], [0, 5, 1], [0, 5, 3], [0, 5, 6]]);
+  let mesh = builder.build().unwrap();
+  let edges = sorted(mesh.outgoing_edges(0));
+  assert_eq!(edges.len(), 5);
+  assert!(edges.windows(2).all(|w| w[0] < w[1]));
+  assert_eq!(sorted(mesh.vertex_faces(0)), vec![0, 1, 2, 3, 4]);
+  assert_eq!(sorted(mesh.vertex_neighbors(0)), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_face_iterators() {
+  let mesh = build_grid(2, &[]);
+  let edges: Vec<usize> = mesh.face_edges(0).collect();
+  assert_eq!(edges.len(), 3);
+  for i in 0..3 {
+    assert_eq!(mesh.edge(edges[i]).face(), 0);
+    assert_eq!(mesh.edge(edges[i]).next_edge(), edges[(i + 1) % 3]);
+    assert_eq!(mesh.edge(edges[i]).start_vertex(), mesh.face(0).vertices()[i]);
+  }
+
+  // Face #0 is the lower-right triangle of the bottom-left square.
+  assert_eq!(sorted(mesh.face_neighbors(0)), vec![1, 3]);
+  // Face #3 is the upper-left triangle of the bottom-right square.
+  assert_eq!(sorted(mesh.face_neighbors(3)), vec![0, 2, 6]);
+  // Face #6 is the lower-right triangle of the top-right square.
+  assert_eq!(sorted(mesh.face_neighbors(6)), vec![3, 7]);
+}
+
+#[test]
+fn test_boundary_loops() {
+  let closed = {
+    let mut builder = TriangularMeshBuilder::new();
+    builder.add_vertices(&[
+        Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+        Vector3::z_unit()]);
+    builder.add_faces(&[[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]]);
+    builder.build().unwrap()
+  };
+  assert_eq!(closed.boundary_loops().count(), 0);
+
+  // A 3 x 3 grid with the center square removed has an outer boundary of
+  // 12 edges and an inner one of 4 edges.
+  let mesh = build_grid(3, &[(1, 1)]);
+  let mut loops: Vec<Vec<usize>> = mesh.boundary_loops().collect();
+  loops.sort_by_key(|l| l.len());
+  assert_eq!(loops.len(), 2);
+  assert_eq!(loops[0].len(), 4);
+  assert_eq!(loops[1].len(), 12);
+  for l in loops.iter() {
+    for i in 0..l.len() {
+      let e = mesh.edge(l[i]);
+      assert!(e.is_boundary());
+      assert_eq!(e.end_vertex(), mesh.edge(l[(i + 1) % l.len()]).start_vertex());
+    }
+  }
+  let inner: Vec<usize> = sorted(
+      loops[0].iter().map(|e| mesh.edge(*e).start_vertex()));
+  assert_eq!(inner, vec![5, 6, 9, 10]);
+}
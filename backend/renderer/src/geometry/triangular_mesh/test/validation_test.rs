@@ -0,0 +1,99 @@
+use super::super::{TriangularMesh, TriangularMeshBuilder};
+use math::Vector3;
+
+fn build(positions: &[Vector3], faces: &[[usize; 3]]) -> TriangularMesh {
+  let mut builder = TriangularMeshBuilder::new();
+  builder.set_allow_non_manifold_edges(true);
+  builder.add_vertices(positions);
+  builder.add_faces(faces);
+  builder.build().unwrap()
+}
+
+#[test]
+fn test_closed_meshes() {
+  let tetrahedron = build(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::z_unit()],
+      &[[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]]);
+  let report = tetrahedron.validate();
+  assert!(report.is_watertight());
+  assert_eq!(report.num_components(), 1);
+  assert_eq!(report.euler_characteristic(), 2);
+  assert_eq!(report.genus(), Some(0));
+  assert_eq!(report.num_boundary_loops(), 0);
+  assert!(report.degenerate_faces().is_empty());
+  assert!(report.duplicate_faces().is_empty());
+
+  let report = TriangularMesh::new_torus(Vector3::zero(), 2.0, 1.0, 4, 4)
+      .validate();
+  assert!(report.is_watertight());
+  assert_eq!(report.euler_characteristic(), 0);
+  assert_eq!(report.genus(), Some(1));
+}
+
+#[test]
+fn test_open_meshes() {
+  // A square with a triangular hole, and a separate triangle.
+  let report = build(&[
+      Vector3::new(0.0, 0.0, 0.0), Vector3::new(3.0, 0.0, 0.0),
+      Vector3::new(3.0, 3.0, 0.0), Vector3::new(0.0, 3.0, 0.0),
+      Vector3::new(1.0, 1.0, 0.0), Vector3::new(2.0, 1.0, 0.0),
+      Vector3::new(1.0, 2.0, 0.0),
+      Vector3::new(5.0, 0.0, 0.0), Vector3::new(6.0, 0.0, 0.0),
+      Vector3::new(5.0, 1.0, 0.0)],
+      &[[0, 1, 5], [0, 5, 4], [1, 2, 5], [5, 2, 6], [2, 3, 6], [3, 0, 4],
+        [3, 4, 6], [7, 8, 9]]).validate();
+  assert!(report.is_manifold());
+  assert!(!report.is_watertight());
+  assert_eq!(report.num_components(), 2);
+  assert_eq!(report.num_boundary_loops(), 3);
+  assert_eq!(report.num_boundary_edges(), 10);
+  // (7 - 14 + 7) + (3 - 3 + 1) = 1 = 2 * 2 - 2g - 3.
+  assert_eq!(report.euler_characteristic(), 1);
+  assert_eq!(report.genus(), Some(0));
+}
+
+#[test]
+fn test_defects() {
+  // A bowtie pinched at vertex #0, with an isolated vertex #5.
+  let bowtie = build(&[
+      Vector3::zero(), Vector3::new(1.0, -1.0, 0.0), Vector3::new(1.0, 1.0, 0.0),
+      Vector3::new(-1.0, 1.0, 0.0), Vector3::new(-1.0, -1.0, 0.0),
+      Vector3::new(9.0, 9.0, 9.0)],
+      &[[0, 1, 2], [0, 3, 4]]).validate();
+  assert_eq!(bowtie.non_manifold_vertices(), &[0]);
+  assert!(bowtie.non_manifold_edges().is_empty());
+  assert_eq!(bowtie.isolated_vertices(), &[5]);
+  assert_eq!(bowtie.num_components(), 2);
+  assert!(!bowtie.is_manifold());
+  assert_eq!(bowtie.genus(), None);
+
+  // Three fins sharing the edge between vertices #0 and #1.
+  let fins = build(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      Vector3::z_unit(), -Vector3::y_unit()],
+      &[[0, 1, 2], [1, 0, 3], [0, 1, 4]]).validate();
+  assert_eq!(fins.non_manifold_edges(), &[(0, 1)]);
+  assert!(fins.non_manifold_vertices().is_empty());
+  assert!(fins.inconsistent_edges().is_empty());
+  assert_eq!(fins.num_components(), 1);
+
+  // Two faces with inconsistent orientations, a duplicate face and a
+  // degenerate face.
+  let report = build(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      -Vector3::y_unit(), Vector3::new(2.0, 0.0, 0.0)],
+      &[[0, 1, 2], [0, 1, 3], [2, 1, 0], [0, 1, 4]]).validate();
+  assert_eq!(report.duplicate_faces(), &[(2, 0)]);
+  assert_eq!(report.degenerate_faces(), &[3]);
+  assert_eq!(report.non_manifold_edges(), &[(0, 1)]);
+
+  let report = build(&[
+      Vector3::zero(), Vector3::x_unit(), Vector3::y_unit(),
+      -Vector3::y_unit()],
+      &[[0, 1, 2], [0, 1, 3]]).validate();
+  assert_eq!(report.inconsistent_edges(), &[(0, 1)]);
+  assert!(report.non_manifold_vertices().is_empty());
+  assert_eq!(report.num_components(), 1);
+  assert!(!report.is_manifold());
+}
@@ -0,0 +1,77 @@
+use super::super::TriangularMesh;
+use geometry::{HasBoundingBox3, HasVolume};
+use math::Vector3;
+
+#[test]
+fn test_voxelize_surface() {
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1.0, 3);
+  let grid = mesh.voxelize_surface(0.1);
+  assert_eq!(grid.resolution(), [20, 20, 20]);
+  let grid_box = grid.bounding_box3();
+  let mesh_box = mesh.bounding_box3();
+  assert!((grid_box.center() - mesh_box.center()).len() < 1e-12);
+  // Every point on the surface is in a set voxel.
+  for f in 0..mesh.num_faces() {
+    let t = mesh.triangle(f);
+    for p in [t.v(0), t.centroid(), (t.v(1) + t.v(2)) / 2.0].iter() {
+      let [i, j, k] = grid.voxel_at(*p).unwrap();
+      assert!(*grid.get(i, j, k));
+    }
+  }
+  // No voxel far from the surface is set.
+  for index in 0..grid.num_voxels() {
+    let [i, j, k] = grid.coords(index);
+    let r = grid.voxel_center(i, j, k).len();
+    if *grid.get(i, j, k) {
+      assert!((r - 1.0).abs() < 0.1 * 3.0f64.sqrt());
+    }
+  }
+  assert!(!grid.get(10, 10, 10));
+}
+
+#[test]
+fn test_voxelize_solid() {
+  let mesh = TriangularMesh::new_torus(Vector3::zero(), 1.0, 0.4, 48, 24);
+  let grid = mesh.voxelize_solid(0.05);
+  for index in 0..grid.num_voxels() {
+    let [i, j, k] = grid.coords(index);
+    let c = grid.voxel_center(i, j, k);
+    let tube = ((c.x() * c.x() + c.y() * c.y()).sqrt() - 1.0).hypot(c.z());
+    if (tube - 0.4).abs() > 0.01 {
+      assert_eq!(*grid.get(i, j, k), tube < 0.4);
+    }
+  }
+  let volume = grid.num_occupied() as f64 * 0.05f64.powi(3);
+  assert!((volume / mesh.volume() - 1.0).abs() < 0.02);
+}
+
+#[test]
+fn test_signed_distance_grid() {
+  let mesh = TriangularMesh::new_box(Vector3::zero(), Vector3::new(1.0, 1.0,
+      2.0));
+  let grid = mesh.signed_distance_grid(0.1, 0.25);
+  assert_eq!(grid.resolution(), [15, 15, 25]);
+  for index in 0..grid.num_voxels() {
+    let [i, j, k] = grid.coords(index);
+    let c = grid.voxel_center(i, j, k);
+    // The exact signed distance to the box.
+    let q = Vector3::new(c.x().abs() - 0.5, c.y().abs() - 0.5,
+        c.z().abs() - 1.0);
+    let outside = Vector3::new(q.x().max(0.0), q.y().max(0.0), q.z().max(0.0));
+    let expected = outside.len() + q.x().max(q.y()).max(q.z()).min(0.0);
+    let value = *grid.get(i, j, k);
+    assert!((value - expected.clamp(-0.25, 0.25)).abs() < 1e-12);
+  }
+  assert_eq!(*grid.get(7, 7, 12), -0.25);
+}
+
+#[test]
+fn test_small_mesh() {
+  let mesh = TriangularMesh::new_icosphere(Vector3::zero(), 1e-4, 3);
+  let solid = mesh.voxelize_solid(2e-5);
+  assert_eq!(solid.resolution(), [10, 10, 10]);
+  assert!(*solid.get(5, 5, 5) && !*solid.get(0, 0, 0));
+  let grid = mesh.signed_distance_grid(2e-5, 4e-5);
+  assert_eq!(*grid.get(7, 7, 7), -4e-5);
+  assert_eq!(*grid.get(0, 0, 0), 4e-5);
+}
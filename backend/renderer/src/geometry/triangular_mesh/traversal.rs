@@ -0,0 +1,312 @@
+use geometry::triangular_mesh::TriangularMesh;
+
+impl TriangularMesh {
+  /// Returns an iterator over all outgoing half-edges of a vertex, in
+  /// counter-clockwise order around it. Each edge is reached from the
+  /// previous one through the twin of its previous edge. On the boundary, the
+  /// walk starts at the outgoing boundary edge, so that it visits all edges
+  /// at once. Around non-manifold vertices, the fans of faces are walked one
+  /// after another.
+  pub fn outgoing_edges(&self, v: usize) -> OutgoingEdges<'_> {
+    let first = self.fan_start(v, &[]);
+    OutgoingEdges {
+      mesh: self,
+      vertex: v,
+      first: first.unwrap_or(0),
+      next: first,
+      count: 0,
+      visited: Vec::new(),
+    }
+  }
+
+  /// Returns an iterator over all faces adjacent to a vertex, in
+  /// counter-clockwise order around it, see outgoing_edges().
+  pub fn vertex_faces(&self, v: usize) -> VertexFaces<'_> {
+    VertexFaces {
+      mesh: self,
+      edges: self.outgoing_edges(v),
+    }
+  }
+
+  /// Returns an iterator over the one-ring of a vertex, i.e. all vertices
+  /// connected to it by an edge, in counter-clockwise order around it, see
+  /// outgoing_edges(). Each neighbor is visited once.
+  pub fn vertex_neighbors(&self, v: usize) -> VertexNeighbors<'_> {
+    VertexNeighbors {
+      mesh: self,
+      edges: self.outgoing_edges(v),
+      pending: None,
+    }
+  }
+
+  /// Returns an iterator over the three half-edges of a face, following
+  /// next_edge (i.e. counter-clockwise) from the edge starting at vertex #0
+  /// of the face.
+  pub fn face_edges(&self, f: usize) -> FaceEdges<'_> {
+    FaceEdges {
+      mesh: self,
+      first: self.faces[f].edges[0],
+      current: Some(self.faces[f].edges[0]),
+    }
+  }
+
+  /// Returns an iterator over faces sharing an edge with a face, through the
+  /// twins of its half-edges. Boundary edges have no neighbors.
+  pub fn face_neighbors(&self, f: usize) -> FaceNeighbors<'_> {
+    FaceNeighbors {
+      mesh: self,
+      edges: self.face_edges(f),
+    }
+  }
+
+  /// Returns an iterator over all boundary loops of the mesh. Each loop is a
+  /// list of boundary half-edges (i.e. without twins), in which each edge ends
+  /// at the start vertex of the next one, and the last edge ends at the start
+  /// of the first one.
+  pub fn boundary_loops(&self) -> BoundaryLoops<'_> {
+    BoundaryLoops {
+      mesh: self,
+      visited: vec![false; self.edges.len()],
+      next_candidate: 0,
+    }
+  }
+
+  // Returns the outgoing half-edge of a vertex to start walking a fan of its
+  // faces from, skipping visited edges: a boundary edge (i.e. without twin)
+  // if any, since the walk stops at the boundary, or any edge otherwise.
+  fn fan_start(&self, v: usize, visited: &[usize]) -> Option<usize> {
+    let mut candidates = self.vertices[v].edges.iter().cloned()
+        .filter(|e| !visited.contains(e));
+    let first = candidates.next()?;
+    if self.edges[first].twin_edge.is_none() {
+      return Some(first);
+    }
+    Some(candidates.find(|e| self.edges[*e].twin_edge.is_none())
+        .unwrap_or(first))
+  }
+
+  // Returns the boundary half-edge following a given boundary half-edge,
+  // i.e. leaving its end vertex, found by rotating around the end vertex
+  // across twins. Falls back to any boundary edge leaving the end vertex if
+  // the rotation fails, which happens around non-manifold vertices.
+  fn next_boundary_edge(&self, e: usize, visited: &[bool]) -> Option<usize> {
+    let v = self.edges[e].end_vertex;
+    let mut candidate = self.edges[e].next_edge;
+    for _ in 0..self.vertices[v].edges.len() {
+      match self.edges[candidate].twin_edge {
+        None => {
+          if !visited[candidate] {
+            return Some(candidate);
+          }
+          break;
+        },
+        Some(twin) => candidate = self.edges[twin].next_edge,
+      }
+    }
+    self.vertices[v].edges.iter().cloned()
+        .find(|c| self.edges[*c].twin_edge.is_none() && !visited[*c])
+  }
+}
+
+/// Iterator over outgoing half-edges of a vertex, in counter-clockwise order.
+#[derive(Debug, Clone)]
+pub struct OutgoingEdges<'a> {
+  mesh: &'a TriangularMesh,
+  vertex: usize,
+  // The first and the next edge of the fan being walked.
+  first: usize,
+  next: Option<usize>,
+  // The number of edges visited so far.
+  count: usize,
+  // Edges visited so far, only recorded once a second fan is found around a
+  // non-manifold vertex.
+  visited: Vec<usize>,
+}
+
+impl<'a> OutgoingEdges<'a> {
+  // Returns whether the vertex has several fans, which is only known once
+  // the first one has been walked.
+  fn has_several_fans(&self) -> bool {
+    !self.visited.is_empty()
+  }
+}
+
+impl<'a> Iterator for OutgoingEdges<'a> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    let mesh = self.mesh;
+    if self.count >= mesh.vertices[self.vertex].edges.len() {
+      return None;
+    }
+    if self.next.is_none() {
+      // Edges are left after the fan, so the vertex is non-manifold. Edges of
+      // the first fan are recorded by walking it again.
+      if self.visited.is_empty() {
+        let mut e = self.first;
+        for _ in 0..self.count {
+          self.visited.push(e);
+          match mesh.edges[mesh.edges[e].prev_edge].twin_edge {
+            Some(twin) => e = twin,
+            None => break,
+          }
+        }
+      }
+      self.first = mesh.fan_start(self.vertex, &self.visited)?;
+      self.next = Some(self.first);
+    }
+    let e = self.next?;
+    let first = self.first;
+    self.next = mesh.edges[mesh.edges[e].prev_edge].twin_edge
+        .filter(|twin| *twin != first);
+    self.count += 1;
+    if self.has_several_fans() {
+      self.visited.push(e);
+    }
+    Some(e)
+  }
+}
+
+/// Iterator over faces adjacent to a vertex, in counter-clockwise order.
+#[derive(Debug, Clone)]
+pub struct VertexFaces<'a> {
+  mesh: &'a TriangularMesh,
+  edges: OutgoingEdges<'a>,
+}
+
+impl<'a> Iterator for VertexFaces<'a> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    self.edges.next().map(|e| self.mesh.edges[e].face)
+  }
+}
+
+/// Iterator over the one-ring neighbors of a vertex, in counter-clockwise
+/// order. These are the end vertices of all outgoing half-edges, and at the
+/// end of each fan on the boundary, the start vertex of the incoming boundary
+/// half-edge.
+#[derive(Debug, Clone)]
+pub struct VertexNeighbors<'a> {
+  mesh: &'a TriangularMesh,
+  edges: OutgoingEdges<'a>,
+  // The start vertex of the incoming boundary half-edge ending the fan, to be
+  // visited next.
+  pending: Option<usize>,
+}
+
+impl<'a> VertexNeighbors<'a> {
+  // Returns whether a neighbor was visited before the current edge, which
+  // only happens around non-manifold vertices.
+  fn visited_before(&self, u: usize) -> bool {
+    if !self.edges.has_several_fans() {
+      return false;
+    }
+    let mesh = self.mesh;
+    let visited = &self.edges.visited;
+    visited[..visited.len() - 1].iter().any(|e| {
+      let prev = &mesh.edges[mesh.edges[*e].prev_edge];
+      mesh.edges[*e].end_vertex == u
+          || (prev.twin_edge.is_none() && prev.start_vertex == u)
+    })
+  }
+}
+
+impl<'a> Iterator for VertexNeighbors<'a> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    let mesh = self.mesh;
+    loop {
+      let u = match self.pending.take() {
+        Some(u) => u,
+        None => {
+          let e = self.edges.next()?;
+          let prev = &mesh.edges[mesh.edges[e].prev_edge];
+          if prev.twin_edge.is_none() {
+            self.pending = Some(prev.start_vertex);
+          }
+          mesh.edges[e].end_vertex
+        },
+      };
+      if !self.visited_before(u) {
+        return Some(u);
+      }
+    }
+  }
+}
+
+/// Iterator over the three half-edges of a face.
+#[derive(Debug, Clone)]
+pub struct FaceEdges<'a> {
+  mesh: &'a TriangularMesh,
+  first: usize,
+  current: Option<usize>,
+}
+
+impl<'a> Iterator for FaceEdges<'a> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    let e = self.current?;
+    let next = self.mesh.edges[e].next_edge;
+    self.current = if next == self.first { None } else { Some(next) };
+    Some(e)
+  }
+}
+
+/// Iterator over faces sharing an edge with a face.
+#[derive(Debug, Clone)]
+pub struct FaceNeighbors<'a> {
+  mesh: &'a TriangularMesh,
+  edges: FaceEdges<'a>,
+}
+
+impl<'a> Iterator for FaceNeighbors<'a> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    let mesh = self.mesh;
+    self.edges.by_ref()
+        .filter_map(|e| mesh.edges[e].twin_edge)
+        .map(|twin| mesh.edges[twin].face)
+        .next()
+  }
+}
+
+/// Iterator over boundary loops of a mesh, each as a list of half-edges.
+#[derive(Debug, Clone)]
+pub struct BoundaryLoops<'a> {
+  mesh: &'a TriangularMesh,
+  visited: Vec<bool>,
+  // All edges before this index are either visited or not on the boundary.
+  next_candidate: usize,
+}
+
+impl<'a> Iterator for BoundaryLoops<'a> {
+  type Item = Vec<usize>;
+
+  fn next(&mut self) -> Option<Vec<usize>> {
+    let mesh = self.mesh;
+    let first = (self.next_candidate..mesh.edges.len())
+        .find(|e| mesh.edges[*e].twin_edge.is_none() && !self.visited[*e])?;
+    self.next_candidate = first + 1;
+
+    let mut boundary = vec![first];
+    self.visited[first] = true;
+    let mut current = first;
+    // The loop is closed once we are back at the start vertex of the first
+    // edge. On valid meshes the next edge is then the first edge itself.
+    while mesh.edges[current].end_vertex != mesh.edges[first].start_vertex {
+      match mesh.next_boundary_edge(current, &self.visited) {
+        Some(next) => {
+          self.visited[next] = true;
+          boundary.push(next);
+          current = next;
+        },
+        None => break,
+      }
+    }
+    Some(boundary)
+  }
+}
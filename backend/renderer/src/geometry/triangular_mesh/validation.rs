@@ -0,0 +1,214 @@
+use common::disjoint_set::DisjointSet;
+use geometry::triangular_mesh::TriangularMesh;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Report of topological and geometric defects of a triangular mesh, see
+/// TriangularMesh::validate(). Spatial edges are identified by the indices of
+/// their two vertices, the smaller one first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+  non_manifold_edges: Vec<(usize, usize)>,
+  non_manifold_vertices: Vec<usize>,
+  inconsistent_edges: Vec<(usize, usize)>,
+  num_boundary_edges: usize,
+  num_boundary_loops: usize,
+  isolated_vertices: Vec<usize>,
+  duplicate_faces: Vec<(usize, usize)>,
+  degenerate_faces: Vec<usize>,
+  num_components: usize,
+  euler_characteristic: i64,
+  genus: Option<usize>,
+}
+
+impl ValidationReport {
+  /// Returns spatial edges shared by more than two faces.
+  pub fn non_manifold_edges(&self) -> &[(usize, usize)] {
+    &self.non_manifold_edges
+  }
+
+  /// Returns vertices whose adjacent faces do not form a single fan, e.g. the
+  /// tip shared by two cones. Vertices on non-manifold edges are not included
+  /// unless they are also pinched.
+  pub fn non_manifold_vertices(&self) -> &[usize] {
+    &self.non_manifold_vertices
+  }
+
+  /// Returns spatial edges shared by two faces traversing it in the same
+  /// direction, i.e. where adjacent faces have inconsistent orientations.
+  pub fn inconsistent_edges(&self) -> &[(usize, usize)] {
+    &self.inconsistent_edges
+  }
+
+  /// Returns the number of half-edges without twins, including those of
+  /// non-manifold and inconsistent edges.
+  pub fn num_boundary_edges(&self) -> usize {
+    self.num_boundary_edges
+  }
+
+  pub fn num_boundary_loops(&self) -> usize {
+    self.num_boundary_loops
+  }
+
+  /// Returns vertices not referenced by any face.
+  pub fn isolated_vertices(&self) -> &[usize] {
+    &self.isolated_vertices
+  }
+
+  /// Returns pairs of (duplicate, original) faces formed by the same three
+  /// vertices, regardless of their order. The original is the first such face.
+  pub fn duplicate_faces(&self) -> &[(usize, usize)] {
+    &self.duplicate_faces
+  }
+
+  /// Returns faces whose triangles are degenerate, see
+  /// Triangle3::is_degenerate().
+  pub fn degenerate_faces(&self) -> &[usize] {
+    &self.degenerate_faces
+  }
+
+  /// Returns the number of connected components, where faces sharing a
+  /// spatial edge are connected. Isolated vertices are not counted.
+  pub fn num_components(&self) -> usize {
+    self.num_components
+  }
+
+  /// Returns the Euler characteristic V - E + F, where V is the number of
+  /// non-isolated vertices and E the number of spatial edges.
+  pub fn euler_characteristic(&self) -> i64 {
+    self.euler_characteristic
+  }
+
+  /// Returns the total genus (number of handles) of all components, derived
+  /// from the Euler characteristic:
+  ///
+  /// > V - E + F = 2C - 2g - b,
+  ///
+  /// where C is the number of components and b the number of boundary loops.
+  /// Returns None if the genus is not well-defined, i.e. the mesh is not an
+  /// orientable manifold with consistent orientations.
+  pub fn genus(&self) -> Option<usize> {
+    self.genus
+  }
+
+  /// Returns true if the mesh is manifold and consistently oriented.
+  pub fn is_manifold(&self) -> bool {
+    self.non_manifold_edges.is_empty() && self.non_manifold_vertices.is_empty()
+        && self.inconsistent_edges.is_empty()
+  }
+
+  /// Returns true if the mesh is a closed, consistently oriented manifold,
+  /// i.e. it encloses a volume.
+  pub fn is_watertight(&self) -> bool {
+    self.is_manifold() && self.num_boundary_edges == 0
+  }
+}
+
+impl fmt::Display for ValidationReport {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "Mesh of {} component(s), Euler characteristic {}, genus ",
+        self.num_components, self.euler_characteristic)?;
+    match self.genus {
+      Some(g) => write!(f, "{}", g)?,
+      None => write!(f, "undefined")?,
+    }
+    write!(f, ", {} boundary loop(s), {} non-manifold edge(s), {} non-manifold \
+        vertex(es), {} inconsistent edge(s), {} isolated vertex(es), {} \
+        duplicate face(s), {} degenerate face(s)", self.num_boundary_loops,
+        self.non_manifold_edges.len(), self.non_manifold_vertices.len(),
+        self.inconsistent_edges.len(), self.isolated_vertices.len(),
+        self.duplicate_faces.len(), self.degenerate_faces.len())
+  }
+}
+
+impl TriangularMesh {
+  /// Inspects the mesh for defects common in scanned or exported assets, and
+  /// computes its topological invariants. Meshes with non-manifold edges can
+  /// only be inspected if built with
+  /// TriangularMeshBuilder::set_allow_non_manifold_edges().
+  pub fn validate(&self) -> ValidationReport {
+    let spatial_edges = self.spatial_edges();
+    let mut non_manifold_edges = Vec::new();
+    let mut inconsistent_edges = Vec::new();
+    let mut face_sets = DisjointSet::new(self.faces.len());
+    for (key, shared) in spatial_edges.iter() {
+      if shared.len() > 2 {
+        non_manifold_edges.push(*key);
+      } else if shared.len() == 2 && self.edges[shared[0]].start_vertex
+          == self.edges[shared[1]].start_vertex {
+        inconsistent_edges.push(*key);
+      }
+      for e in shared.iter().skip(1) {
+        face_sets.union(self.edges[shared[0]].face, self.edges[*e].face);
+      }
+    }
+    non_manifold_edges.sort();
+    inconsistent_edges.sort();
+
+    let isolated_vertices: Vec<usize> = (0..self.vertices.len())
+        .filter(|v| self.vertices[*v].edges.is_empty())
+        .collect();
+    let non_manifold_vertices: Vec<usize> = (0..self.vertices.len())
+        .filter(|v| self.num_vertex_fans(*v) > 1)
+        .collect();
+
+    let mut first_faces: HashMap<[usize; 3], usize> = HashMap::new();
+    let mut duplicate_faces = Vec::new();
+    for f in 0..self.faces.len() {
+      let mut key = self.faces[f].vertices;
+      key.sort();
+      let original = *first_faces.entry(key).or_insert(f);
+      if original != f {
+        duplicate_faces.push((f, original));
+      }
+    }
+    let degenerate_faces = (0..self.faces.len())
+        .filter(|f| self.triangle(*f).is_degenerate())
+        .collect();
+
+    let num_boundary_edges =
+        self.edges.iter().filter(|e| e.twin_edge.is_none()).count();
+    let num_boundary_loops = self.boundary_loops().count();
+    let num_components = face_sets.labels().1;
+    let euler_characteristic =
+        (self.vertices.len() - isolated_vertices.len()) as i64
+        - spatial_edges.len() as i64 + self.faces.len() as i64;
+
+    let mut report = ValidationReport {
+      non_manifold_edges: non_manifold_edges,
+      non_manifold_vertices: non_manifold_vertices,
+      inconsistent_edges: inconsistent_edges,
+      num_boundary_edges: num_boundary_edges,
+      num_boundary_loops: num_boundary_loops,
+      isolated_vertices: isolated_vertices,
+      duplicate_faces: duplicate_faces,
+      degenerate_faces: degenerate_faces,
+      num_components: num_components,
+      euler_characteristic: euler_characteristic,
+      genus: None,
+    };
+    let twice_genus = 2 * num_components as i64 - euler_characteristic
+        - num_boundary_loops as i64;
+    if report.is_manifold() && twice_genus >= 0 && twice_genus % 2 == 0 {
+      report.genus = Some((twice_genus / 2) as usize);
+    }
+    report
+  }
+
+  // Counts the fans around a vertex, i.e. groups of its adjacent faces
+  // connected through spatial edges incident to the vertex. Orientations are
+  // ignored, so that inconsistent faces are not reported twice.
+  fn num_vertex_fans(&self, v: usize) -> usize {
+    let outgoing: Vec<usize> = self.outgoing_edges(v).collect();
+    let mut fans = DisjointSet::new(outgoing.len());
+    let mut first_by_neighbor: HashMap<usize, usize> = HashMap::new();
+    for (i, e) in outgoing.iter().enumerate() {
+      let prev = self.edges[*e].prev_edge;
+      for u in [self.edges[*e].end_vertex, self.edges[prev].start_vertex].iter() {
+        let first = *first_by_neighbor.entry(*u).or_insert(i);
+        fans.union(first, i);
+      }
+    }
+    fans.labels().1
+  }
+}
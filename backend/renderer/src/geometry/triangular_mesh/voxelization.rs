@@ -0,0 +1,105 @@
+use geometry::{BoundingBox3, HasBoundingBox3, MeshBvh, VoxelGrid};
+use geometry::triangular_mesh::TriangularMesh;
+use math::Vector3;
+
+impl TriangularMesh {
+  /// Conservatively voxelizes the surface of the mesh into a grid of cubic
+  /// voxels of a given size over its bounding box: a voxel is set if any face
+  /// overlaps or touches it (see Triangle3::overlaps_box()), so that the set
+  /// voxels cover the whole surface without gaps. The mesh must not be empty.
+  pub fn voxelize_surface(&self, voxel_size: f64) -> VoxelGrid<bool> {
+    let mut grid = VoxelGrid::new_covering(&self.bounding_box3(), voxel_size,
+        0.0, false);
+    for f in 0..self.faces.len() {
+      let t = self.triangle(f);
+      let bounds = BoundingBox3::new_from_points(&[t.v(0), t.v(1), t.v(2)]);
+      for [i, j, k] in voxels_overlapping(&grid, &bounds) {
+        if !grid.get(i, j, k) && t.overlaps_box(&grid.voxel_box(i, j, k)) {
+          grid.set(i, j, k, true);
+        }
+      }
+    }
+    grid
+  }
+
+  /// Voxelizes the solid enclosed by the mesh into a grid of cubic voxels of
+  /// a given size over its bounding box: a voxel is set if its center is
+  /// inside the mesh according to the winding number (see
+  /// MeshBvh::contains()), which is robust for imperfect meshes. Voxels
+  /// crossed by the surface whose centers are outside are not set, see
+  /// voxelize_surface() for those. The mesh must not be empty.
+  pub fn voxelize_solid(&self, voxel_size: f64) -> VoxelGrid<bool> {
+    let mut grid = VoxelGrid::new_covering(&self.bounding_box3(), voxel_size,
+        0.0, false);
+    let bvh = MeshBvh::new(self);
+    for index in 0..grid.num_voxels() {
+      let [i, j, k] = grid.coords(index);
+      if bvh.contains(grid.voxel_center(i, j, k)) {
+        grid.set(i, j, k, true);
+      }
+    }
+    grid
+  }
+
+  /// Samples the signed distance to the mesh (see MeshBvh::signed_distance())
+  /// at the centers of a grid of cubic voxels of a given size, covering the
+  /// bounding box of the mesh grown by a band on all sides. Distances are
+  /// only computed within the band around the surface, and clamped to
+  /// -band inside and band outside of it. The mesh must not be empty.
+  pub fn signed_distance_grid(&self, voxel_size: f64, band: f64)
+      -> VoxelGrid<f64> {
+    assert!(band > 0.0, "Band must be positive!");
+    let mut grid = VoxelGrid::new_covering(&self.bounding_box3(), voxel_size,
+        band, band);
+    // Voxels overlapping the bounding boxes of faces grown by the band, which
+    // include all voxels whose centers are within the band of the surface.
+    let mut near = grid.map(|_| false);
+    let pad = Vector3::identity(band);
+    for f in 0..self.faces.len() {
+      let t = self.triangle(f);
+      let bounds = BoundingBox3::new_from_points(&[t.v(0), t.v(1), t.v(2)]);
+      let bounds = BoundingBox3::new(bounds.min_corner() - pad,
+          bounds.max_corner() + pad);
+      for [i, j, k] in voxels_overlapping(&near, &bounds) {
+        near.set(i, j, k, true);
+      }
+    }
+    let bvh = MeshBvh::new(self);
+    for index in 0..grid.num_voxels() {
+      let [i, j, k] = grid.coords(index);
+      let center = grid.voxel_center(i, j, k);
+      let distance = if *near.get(i, j, k) {
+        bvh.signed_distance(center).unwrap().clamp(-band, band)
+      } else if bvh.contains(center) {
+        -band
+      } else {
+        band
+      };
+      grid.set(i, j, k, distance);
+    }
+    grid
+  }
+}
+
+// Returns the voxels of a grid overlapping a box.
+fn voxels_overlapping<T: Clone>(grid: &VoxelGrid<T>, b: &BoundingBox3)
+    -> Vec<[usize; 3]> {
+  let mut ranges = [(0, 0); 3];
+  for (axis, range) in ranges.iter_mut().enumerate() {
+    let n = grid.resolution()[axis] as f64;
+    let to_voxel = |x: f64| {
+      ((x - grid.origin()[axis]) / grid.voxel_size()).floor().clamp(0.0, n)
+    };
+    *range = (to_voxel(b.min_corner()[axis]) as usize,
+        to_voxel(b.max_corner()[axis]) as usize);
+  }
+  let mut voxels = Vec::new();
+  for k in ranges[2].0..=ranges[2].1.min(grid.resolution()[2] - 1) {
+    for j in ranges[1].0..=ranges[1].1.min(grid.resolution()[1] - 1) {
+      for i in ranges[0].0..=ranges[0].1.min(grid.resolution()[0] - 1) {
+        voxels.push([i, j, k]);
+      }
+    }
+  }
+  voxels
+}
@@ -0,0 +1,150 @@
+use geometry::{BoundingBox3, HasBoundingBox3};
+use math::Vector3;
+
+/// A dense 3-d grid of cubic voxels, storing one value per voxel. The grid
+/// spans resolution[axis] voxels along each axis from its origin, i.e. its
+/// min corner.
+///
+/// Values are stored with x varying fastest, then y, then z, which is the
+/// layout of 3-d textures and of most volume file formats, so values() can
+/// be exported as is. map() converts values, e.g. into bytes or f32s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelGrid<T> {
+  origin: Vector3,
+  voxel_size: f64,
+  resolution: [usize; 3],
+  values: Vec<T>,
+}
+
+impl<T: Clone> VoxelGrid<T> {
+  pub fn origin(&self) -> Vector3 {
+    self.origin
+  }
+
+  pub fn voxel_size(&self) -> f64 {
+    self.voxel_size
+  }
+
+  pub fn resolution(&self) -> [usize; 3] {
+    self.resolution
+  }
+
+  pub fn num_voxels(&self) -> usize {
+    self.values.len()
+  }
+
+  /// Returns the values of all voxels, with x varying fastest, then y, then
+  /// z.
+  pub fn values(&self) -> &[T] {
+    &self.values
+  }
+
+  pub fn into_values(self) -> Vec<T> {
+    self.values
+  }
+
+  /// Returns the position in values() of voxel (i, j, k).
+  pub fn index(&self, i: usize, j: usize, k: usize) -> usize {
+    assert!(i < self.resolution[0] && j < self.resolution[1]
+        && k < self.resolution[2], "Voxel out of the grid!");
+    i + self.resolution[0] * (j + self.resolution[1] * k)
+  }
+
+  /// Returns the voxel (i, j, k) at a position in values().
+  pub fn coords(&self, index: usize) -> [usize; 3] {
+    assert!(index < self.values.len(), "Voxel out of the grid!");
+    let (nx, ny) = (self.resolution[0], self.resolution[1]);
+    [index % nx, index / nx % ny, index / (nx * ny)]
+  }
+
+  pub fn get(&self, i: usize, j: usize, k: usize) -> &T {
+    &self.values[self.index(i, j, k)]
+  }
+
+  pub fn set(&mut self, i: usize, j: usize, k: usize, value: T) {
+    let index = self.index(i, j, k);
+    self.values[index] = value;
+  }
+
+  pub fn voxel_center(&self, i: usize, j: usize, k: usize) -> Vector3 {
+    self.origin + Vector3::new(i as f64 + 0.5, j as f64 + 0.5, k as f64 + 0.5)
+        * self.voxel_size
+  }
+
+  pub fn voxel_box(&self, i: usize, j: usize, k: usize) -> BoundingBox3 {
+    let min_corner = self.origin
+        + Vector3::new(i as f64, j as f64, k as f64) * self.voxel_size;
+    BoundingBox3::new(min_corner,
+        min_corner + Vector3::identity(self.voxel_size))
+  }
+
+  /// Returns the voxel containing a point, or None if the point is outside
+  /// of the grid. Points on faces between voxels belong to the upper voxel,
+  /// except on the upper faces of the grid.
+  pub fn voxel_at(&self, p: Vector3) -> Option<[usize; 3]> {
+    let mut voxel = [0; 3];
+    for axis in 0..3 {
+      let t = (p[axis] - self.origin[axis]) / self.voxel_size;
+      let n = self.resolution[axis];
+      if !(0.0..=n as f64).contains(&t) {
+        return None;
+      }
+      voxel[axis] = (t.floor() as usize).min(n - 1);
+    }
+    Some(voxel)
+  }
+
+  /// Returns a grid of the same shape with each value converted by a
+  /// function.
+  pub fn map<U, F>(&self, f: F) -> VoxelGrid<U> where U: Clone, F: Fn(&T) -> U {
+    VoxelGrid {
+      origin: self.origin,
+      voxel_size: self.voxel_size,
+      resolution: self.resolution,
+      values: self.values.iter().map(f).collect(),
+    }
+  }
+
+  /// Creates a grid with all voxels set to a value.
+  pub fn new(origin: Vector3, voxel_size: f64, resolution: [usize; 3],
+      value: T) -> Self {
+    assert!(voxel_size > 0.0, "Voxel size must be positive!");
+    assert!(resolution.iter().all(|n| *n > 0), "Empty voxel grid!");
+    Self {
+      origin: origin,
+      voxel_size: voxel_size,
+      resolution: resolution,
+      values: vec![value; resolution[0] * resolution[1] * resolution[2]],
+    }
+  }
+
+  /// Creates the smallest grid covering a box grown by a padding on all
+  /// sides, centered on the box, with all voxels set to a value.
+  pub fn new_covering(b: &BoundingBox3, voxel_size: f64, padding: f64,
+      value: T) -> Self {
+    assert!(voxel_size > 0.0, "Voxel size must be positive!");
+    let mut resolution = [0; 3];
+    let mut size = Vector3::zero();
+    for axis in 0..3 {
+      let len = b.max_corner()[axis] - b.min_corner()[axis] + 2.0 * padding;
+      resolution[axis] = ((len / voxel_size).ceil() as usize).max(1);
+      size[axis] = resolution[axis] as f64 * voxel_size;
+    }
+    Self::new(b.center() - size / 2.0, voxel_size, resolution, value)
+  }
+}
+
+impl VoxelGrid<bool> {
+  /// Returns the number of voxels which are set.
+  pub fn num_occupied(&self) -> usize {
+    self.values.iter().filter(|v| **v).count()
+  }
+}
+
+impl<T: Clone> HasBoundingBox3 for VoxelGrid<T> {
+  fn bounding_box3(&self) -> BoundingBox3 {
+    let size = Vector3::new(self.resolution[0] as f64,
+        self.resolution[1] as f64, self.resolution[2] as f64) * self.voxel_size;
+    BoundingBox3::new(self.origin, self.origin + size)
+  }
+}
@@ -45,6 +45,79 @@ impl Matrix3 {
           - self.m[0][1] * self.m[1][0] * self.m[2][2]
   }
 
+  /// Calculates the inverse by the adjugate matrix. Returns None if the matrix
+  /// is singular, i.e. its determinant is within EPSILON_TINY of 0.
+  pub fn inverse(&self) -> Option<Self> {
+    let det = self.det();
+    if det.abs() < constants::EPSILON_TINY {
+      return None;
+    }
+    // Rows of the adjugate are cross products of columns of the matrix.
+    let t = self.t();
+    Some(Self::new_from_vectors(t.m[1] ^ t.m[2], t.m[2] ^ t.m[0],
+        t.m[0] ^ t.m[1]) / det)
+  }
+
+  /// Returns the trace, i.e. the sum of diagonal elements.
+  pub fn trace(&self) -> f64 {
+    self.m[0][0] + self.m[1][1] + self.m[2][2]
+  }
+
+  /// Calculates the eigenvalues and eigenvectors of a symmetric matrix with
+  /// the cyclic Jacobi method. Returns the eigenvalues sorted in descending
+  /// order, together with a matrix whose rows are the corresponding unit
+  /// eigenvectors, forming a right-handed orthonormal basis.
+  ///
+  /// The result is undefined if the matrix is not symmetric, and contains
+  /// NaNs if the matrix does.
+  pub fn symmetric_eigen(&self) -> (Vector3, Self) {
+    const MAX_SWEEPS: usize = 50;
+    let mut a = *self;
+    // Columns of v accumulate the rotations, i.e. the eigenvectors.
+    let mut v = Self::identity();
+    let scale = (0..3).map(|i| self.m[i].len2()).sum::<f64>();
+    for _ in 0..MAX_SWEEPS {
+      let off = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+      if off <= constants::EPSILON_TINY * constants::EPSILON_TINY * scale {
+        break;
+      }
+      for &(p, q) in [(0, 1), (0, 2), (1, 2)].iter() {
+        if a[p][q] == 0.0 {
+          continue;
+        }
+        // Rotation zeroing a[p][q], see Numerical Recipes, Chapter 11.1.
+        let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+        let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+        let c = 1.0 / (t * t + 1.0).sqrt();
+        let s = t * c;
+        for k in 0..3 {
+          let (akp, akq) = (a[k][p], a[k][q]);
+          a[k][p] = c * akp - s * akq;
+          a[k][q] = s * akp + c * akq;
+        }
+        for k in 0..3 {
+          let (apk, aqk) = (a[p][k], a[q][k]);
+          a[p][k] = c * apk - s * aqk;
+          a[q][k] = s * apk + c * aqk;
+        }
+        for k in 0..3 {
+          let (vkp, vkq) = (v[k][p], v[k][q]);
+          v[k][p] = c * vkp - s * vkq;
+          v[k][q] = s * vkp + c * vkq;
+        }
+      }
+    }
+
+    let vectors = v.t();
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| a[j][j].total_cmp(&a[i][i]));
+    let e0 = vectors[order[0]];
+    let e1 = vectors[order[1]];
+    (Vector3::new(a[order[0]][order[0]], a[order[1]][order[1]],
+        a[order[2]][order[2]]),
+        Self::new_from_vectors(e0, e1, e0 ^ e1))
+  }
+
   /// Initializes from three vectors, each vector will become a row in the
   /// matrix, by the order they are specified.
   pub fn new_from_vectors(v1: Vector3, v2: Vector3, v3: Vector3) -> Self {
@@ -89,6 +162,11 @@ impl Matrix3 {
     Self::diag(Vector3::zero())
   }
 
+  /// Creates the outer product of two vectors, i.e. u * v^T.
+  pub fn outer(u: Vector3, v: Vector3) -> Self {
+    Self::new_from_vectors(v * u[0], v * u[1], v * u[2])
+  }
+
   /// Creates the cross-product-equivalent matrix from a given vector.
   /// E.g. a^b = M_a * b, calling this method with param a returns M_a.
   pub fn cross_prod_mat(v: Vector3) -> Self {
@@ -0,0 +1,279 @@
+use common::constants;
+use std::fmt;
+use std::ops::{
+  Add, AddAssign, Sub, SubAssign, Neg,
+  Div, DivAssign, Mul, MulAssign,
+  Index, IndexMut,
+};
+use std::cmp::PartialEq;
+use math::Vector3;
+
+/// Definition and operations of a 4x4 double-precision matrix, mostly used
+/// as homogeneous transforms (e.g. camera projections) applied to column
+/// vectors, i.e. p' = M * p.
+#[derive(Debug, Copy, Clone)]
+pub struct Matrix4 {
+  m: [[f64; 4]; 4],
+}
+
+impl Matrix4 {
+  /// Returns a new matrix whose value is the transpose of this.
+  pub fn t(self) -> Self {
+    let mut m = self;
+    m.transpose_self();
+    m
+  }
+
+  /// Converts this matrix to its transpose.
+  pub fn transpose_self(&mut self) {
+    for i in 0..4 {
+      for j in (i + 1)..4 {
+        let t = self.m[i][j];
+        self.m[i][j] = self.m[j][i];
+        self.m[j][i] = t;
+      }
+    }
+  }
+
+  /// Transforms a point in homogeneous coordinates (x, y, z, 1), and projects
+  /// the result back by dividing with its w component.
+  pub fn transform_point(&self, p: Vector3) -> Vector3 {
+    let h = self.mul_homogeneous(&[p.x(), p.y(), p.z(), 1.0]);
+    Vector3::new(h[0], h[1], h[2]) / h[3]
+  }
+
+  /// Transforms a direction in homogeneous coordinates (x, y, z, 0), i.e.
+  /// ignoring the translation part of the matrix.
+  pub fn transform_vector(&self, v: Vector3) -> Vector3 {
+    let h = self.mul_homogeneous(&[v.x(), v.y(), v.z(), 0.0]);
+    Vector3::new(h[0], h[1], h[2])
+  }
+
+  /// Multiplies the matrix with a vector of 4 homogeneous coordinates.
+  pub fn mul_homogeneous(&self, v: &[f64; 4]) -> [f64; 4] {
+    let mut r = [0.0; 4];
+    for (i, row) in self.m.iter().enumerate() {
+      r[i] = row[0] * v[0] + row[1] * v[1] + row[2] * v[2] + row[3] * v[3];
+    }
+    r
+  }
+
+  /// Initializes from four arrays, each array will become a row in the
+  /// matrix, by the order they are specified.
+  pub fn new_from_arrays(a1: &[f64; 4], a2: &[f64; 4], a3: &[f64; 4],
+      a4: &[f64; 4]) -> Self {
+    Self {
+      m: [*a1, *a2, *a3, *a4],
+    }
+  }
+
+  /// Initializes a diagnal matrix from four numericals.
+  pub fn diag(d1: f64, d2: f64, d3: f64, d4: f64) -> Self {
+    Self::new_from_arrays(
+        &[d1, 0.0, 0.0, 0.0],
+        &[0.0, d2, 0.0, 0.0],
+        &[0.0, 0.0, d3, 0.0],
+        &[0.0, 0.0, 0.0, d4])
+  }
+
+  /// Initializes an identity matrix.
+  pub fn identity() -> Self {
+    Self::diag(1.0, 1.0, 1.0, 1.0)
+  }
+
+  /// Initializes a zero matrix.
+  pub fn zero() -> Self {
+    Self::diag(0.0, 0.0, 0.0, 0.0)
+  }
+
+  /// Initializes a translation matrix moving points by the given vector.
+  pub fn translation(v: Vector3) -> Self {
+    Self::new_from_arrays(
+        &[1.0, 0.0, 0.0, v.x()],
+        &[0.0, 1.0, 0.0, v.y()],
+        &[0.0, 0.0, 1.0, v.z()],
+        &[0.0, 0.0, 0.0, 1.0])
+  }
+
+  /// Initializes a perspective projection matrix, with the camera at the
+  /// origin looking towards -z and y pointing up. The view frustum is mapped
+  /// to the cube [-1, 1]^3 (OpenGL convention).
+  ///
+  /// - fov_y: vertical field of view in radians;
+  /// - aspect: width divided by height of the view;
+  /// - near, far: positive distances to the near and far clipping planes.
+  pub fn perspective(fov_y: f64, aspect: f64, near: f64, far: f64) -> Self {
+    assert!(fov_y > 0.0 && aspect > 0.0, "Invalid field of view!");
+    assert!(near > 0.0 && far > near, "Invalid clipping planes!");
+    let f = 1.0 / (fov_y / 2.0).tan();
+    Self::new_from_arrays(
+        &[f / aspect, 0.0, 0.0, 0.0],
+        &[0.0, f, 0.0, 0.0],
+        &[0.0, 0.0, (far + near) / (near - far), 2.0 * far * near / (near - far)],
+        &[0.0, 0.0, -1.0, 0.0])
+  }
+
+  /// Initializes an orthographic projection matrix mapping the box
+  /// [left, right] x [bottom, top] x [-far, -near] to the cube [-1, 1]^3
+  /// (OpenGL convention).
+  pub fn orthographic(left: f64, right: f64, bottom: f64, top: f64,
+      near: f64, far: f64) -> Self {
+    assert!(right > left && top > bottom && far > near,
+        "Invalid view volume!");
+    Self::new_from_arrays(
+        &[2.0 / (right - left), 0.0, 0.0, -(right + left) / (right - left)],
+        &[0.0, 2.0 / (top - bottom), 0.0, -(top + bottom) / (top - bottom)],
+        &[0.0, 0.0, -2.0 / (far - near), -(far + near) / (far - near)],
+        &[0.0, 0.0, 0.0, 1.0])
+  }
+}
+
+/// Overriding += for matrix addition.
+impl AddAssign for Matrix4 {
+  fn add_assign(&mut self, rhs: Self) {
+    for i in 0..4 {
+      for j in 0..4 {
+        self.m[i][j] += rhs.m[i][j];
+      }
+    }
+  }
+}
+
+/// Overriding + for matrix addition.
+impl Add for Matrix4 {
+  type Output = Self;
+  fn add(self, rhs: Self) -> Self {
+    let mut m = self;
+    m += rhs;
+    m
+  }
+}
+
+/// Overriding -= for matrix subtraction.
+impl SubAssign for Matrix4 {
+  fn sub_assign(&mut self, rhs: Self) {
+    *self += -rhs;
+  }
+}
+
+/// Overriding - for matrix subtraction.
+impl Sub for Matrix4 {
+  type Output = Self;
+  fn sub(self, rhs: Self) -> Self {
+    let mut m = self;
+    m -= rhs;
+    m
+  }
+}
+
+/// Overriding - for matrix negation.
+impl Neg for Matrix4 {
+  type Output = Self;
+  fn neg(self) -> Self {
+    self * -1.0
+  }
+}
+
+/// Overriding *= for matrix multiplication with a numerical.
+impl MulAssign<f64> for Matrix4 {
+  fn mul_assign(&mut self, rhs: f64) {
+    for row in self.m.iter_mut() {
+      for x in row.iter_mut() {
+        *x *= rhs;
+      }
+    }
+  }
+}
+
+/// Overriding *= for matrix multiplication with a matrix of the same dimension.
+impl MulAssign for Matrix4 {
+  fn mul_assign(&mut self, rhs: Self) {
+    self.m = (*self * rhs).m
+  }
+}
+
+/// Overriding * for matrix multiplication with a numerical.
+impl Mul<f64> for Matrix4 {
+  type Output = Self;
+  fn mul(self, rhs: f64) -> Self {
+    let mut m = self;
+    m *= rhs;
+    m
+  }
+}
+
+/// Overriding * for matrix multiplication with a matrix of the same dimension.
+impl Mul for Matrix4 {
+  type Output = Self;
+  fn mul(self, rhs: Self) -> Self {
+    let mut m = Self::zero();
+    for i in 0..4 {
+      for j in 0..4 {
+        for k in 0..4 {
+          m.m[i][j] += self.m[i][k] * rhs.m[k][j];
+        }
+      }
+    }
+    m
+  }
+}
+
+/// Overriding /= for matrix division with a numerical.
+impl DivAssign<f64> for Matrix4 {
+  fn div_assign(&mut self, rhs: f64) {
+    assert!(rhs.abs() > constants::EPSILON_TINY, "Division by zero!");
+    *self *= 1.0 / rhs;
+  }
+}
+
+/// Overriding / for matrix division with a numerical.
+impl Div<f64> for Matrix4 {
+  type Output = Self;
+  fn div(self, rhs: f64) -> Self {
+    let mut m = self;
+    m /= rhs;
+    m
+  }
+}
+
+/// Overriding [] for immutable indexing, returning a row of the matrix.
+impl Index<usize> for Matrix4 {
+  type Output = [f64; 4];
+  fn index(&self, ind: usize) -> &[f64; 4] {
+    assert!(ind < 4, "Index out of bound!");
+    &self.m[ind]
+  }
+}
+
+/// Overriding [] for mutable indexing, returning a row of the matrix.
+impl IndexMut<usize> for Matrix4 {
+  fn index_mut(&mut self, ind: usize) -> &mut [f64; 4] {
+    assert!(ind < 4, "Index out of bound!");
+    &mut self.m[ind]
+  }
+}
+
+/// Overriding == and != to allow comparison within error bounds.
+impl PartialEq for Matrix4 {
+  fn eq(&self, rhs: &Self) -> bool {
+    self.m.iter().zip(rhs.m.iter()).all(|(r1, r2)|
+        r1.iter().zip(r2.iter()).all(|(x, y)| (x - y).abs() < constants::EPSILON))
+  }
+}
+
+/// Printing the matrix as
+/// (
+///  (m11, m12, m13, m14),
+///  ...
+///  (m41, m42, m43, m44)
+/// )
+impl fmt::Display for Matrix4 {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "(")?;
+    for (i, r) in self.m.iter().enumerate() {
+      let sep = if i < 3 { "," } else { "" };
+      write!(f, "\n ({}, {}, {}, {}){}", r[0], r[1], r[2], r[3], sep)?;
+    }
+    write!(f, "\n)")
+  }
+}
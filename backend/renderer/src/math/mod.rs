@@ -1,10 +1,14 @@
 pub use self::vector2::Vector2;
 pub use self::vector3::Vector3;
 pub use self::matrix3::Matrix3;
+pub use self::matrix4::Matrix4;
+pub use self::sparse_matrix::SparseMatrix;
 
 mod vector2;
 mod vector3;
 mod matrix3;
+mod matrix4;
+mod sparse_matrix;
 
 #[cfg(test)]
 mod test;
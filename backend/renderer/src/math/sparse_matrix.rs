@@ -0,0 +1,296 @@
+use common::constants;
+use std::ops::Mul;
+
+/// Definition and operations of a double-precision sparse matrix, stored in
+/// the compressed sparse row (CSR) format, i.e. the non-zero entries of each
+/// row are stored contiguously, sorted by their columns.
+#[derive(Debug, Clone)]
+pub struct SparseMatrix {
+  num_rows: usize,
+  num_cols: usize,
+  // Entries of row i are at row_starts[i]..row_starts[i + 1].
+  row_starts: Vec<usize>,
+  cols: Vec<usize>,
+  values: Vec<f64>,
+}
+
+impl SparseMatrix {
+  pub fn num_rows(&self) -> usize {
+    self.num_rows
+  }
+
+  pub fn num_cols(&self) -> usize {
+    self.num_cols
+  }
+
+  /// Returns the number of stored (structurally non-zero) entries.
+  pub fn num_entries(&self) -> usize {
+    self.values.len()
+  }
+
+  /// Returns the entry at row i and column j, which is 0 if not stored.
+  pub fn get(&self, i: usize, j: usize) -> f64 {
+    let row = self.row_starts[i]..self.row_starts[i + 1];
+    match self.cols[row.clone()].binary_search(&j) {
+      Ok(k) => self.values[row.start + k],
+      Err(_) => 0.0,
+    }
+  }
+
+  /// Returns the diagonal entries.
+  pub fn diagonal(&self) -> Vec<f64> {
+    (0..self.num_rows.min(self.num_cols)).map(|i| self.get(i, i)).collect()
+  }
+
+  /// Returns the product of this matrix and a vector.
+  pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+    assert!(x.len() == self.num_cols, "Dimensions must match!");
+    (0..self.num_rows)
+        .map(|i| (self.row_starts[i]..self.row_starts[i + 1])
+            .map(|k| self.values[k] * x[self.cols[k]])
+            .sum())
+        .collect()
+  }
+
+  /// Solves A * x = b for a symmetric positive (semi-)definite matrix A, with
+  /// the conjugate gradient method preconditioned by the diagonal of A
+  /// (Jacobi preconditioner). Returns None if the relative residual
+  /// |b - A * x| / |b| does not drop below the tolerance within the maximum
+  /// number of iterations. Semi-definite systems converge as long as b is in
+  /// the range of A. For details please read:
+  ///
+  /// > An Introduction to the Conjugate Gradient Method Without the Agonizing
+  /// > Pain. J. R. Shewchuk. 1994.
+  pub fn solve_conjugate_gradient(&self, b: &[f64], tolerance: f64,
+      max_iterations: usize) -> Option<Vec<f64>> {
+    assert!(self.num_rows == self.num_cols, "Matrix must be square!");
+    assert!(b.len() == self.num_rows, "Dimensions must match!");
+    let dot = |u: &[f64], v: &[f64]| -> f64 {
+      u.iter().zip(v.iter()).map(|(a, b)| a * b).sum()
+    };
+    let inv_diagonal: Vec<f64> = self.diagonal().iter()
+        .map(|d| if d.abs() > constants::EPSILON_TINY { 1.0 / d } else { 1.0 })
+        .collect();
+    let precondition = |r: &[f64]| -> Vec<f64> {
+      r.iter().zip(inv_diagonal.iter()).map(|(r, d)| r * d).collect()
+    };
+
+    let b_norm = dot(b, b).sqrt();
+    let mut x = vec![0.0; b.len()];
+    if b_norm == 0.0 {
+      return Some(x);
+    }
+    let mut r = b.to_vec();
+    let mut z = precondition(&r);
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+    for _ in 0..max_iterations {
+      if dot(&r, &r).sqrt() <= tolerance * b_norm {
+        return Some(x);
+      }
+      let ap = self.mul_vec(&p);
+      let pap = dot(&p, &ap);
+      if pap <= 0.0 {
+        break;
+      }
+      let alpha = rz / pap;
+      for i in 0..x.len() {
+        x[i] += alpha * p[i];
+        r[i] -= alpha * ap[i];
+      }
+      z = precondition(&r);
+      let rz_next = dot(&r, &z);
+      let beta = rz_next / rz;
+      rz = rz_next;
+      for i in 0..p.len() {
+        p[i] = z[i] + beta * p[i];
+      }
+    }
+    if dot(&r, &r).sqrt() <= tolerance * b_norm {
+      Some(x)
+    } else {
+      None
+    }
+  }
+
+  /// Solves A * x = b for a symmetric positive definite matrix A, directly by
+  /// a sparse LDL^T factorization (a square-root free Cholesky factorization),
+  /// or returns None if A is not positive definite. Only entries below the
+  /// diagonal are read. Unlike iterative solvers, the relative accuracy of
+  /// tiny entries of x is kept, e.g. for M-matrices (positive diagonal,
+  /// non-positive off-diagonal entries) and non-negative b. Rows are not
+  /// reordered, so the fill-in of the factor depends on their locality. For
+  /// details please read:
+  ///
+  /// > Algorithm 849: A Concise Sparse Cholesky Factorization Package.
+  /// > T. A. Davis. 2005.
+  pub fn solve_cholesky(&self, b: &[f64]) -> Option<Vec<f64>> {
+    assert!(self.num_rows == self.num_cols, "Matrix must be square!");
+    assert!(b.len() == self.num_rows, "Dimensions must match!");
+    let n = self.num_rows;
+    // The lower triangular part of row k is the upper triangular part of
+    // column k, as A is symmetric.
+    let lower_row = |k: usize| (self.row_starts[k]..self.row_starts[k + 1])
+        .filter(move |p| self.cols[*p] <= k);
+
+    // Symbolic factorization: the elimination tree and the number of non-zero
+    // entries in each column of L.
+    let mut parents: Vec<Option<usize>> = vec![None; n];
+    let mut flags = vec![0; n];
+    let mut counts = vec![0; n];
+    for k in 0..n {
+      flags[k] = k;
+      for p in lower_row(k) {
+        let mut i = self.cols[p];
+        while flags[i] != k {
+          if parents[i].is_none() {
+            parents[i] = Some(k);
+          }
+          counts[i] += 1;
+          flags[i] = k;
+          i = parents[i].unwrap();
+        }
+      }
+    }
+    let mut starts = vec![0; n + 1];
+    for k in 0..n {
+      starts[k + 1] = starts[k] + counts[k];
+    }
+
+    // Numeric factorization, computing row k of L from the rows above by a
+    // sparse triangular solve.
+    let mut l_rows = vec![0; starts[n]];
+    let mut l_values = vec![0.0; starts[n]];
+    let mut d = vec![0.0; n];
+    let mut y = vec![0.0; n];
+    let mut pattern = vec![0; n];
+    for k in 0..n {
+      let mut top = n;
+      flags[k] = k;
+      counts[k] = 0;
+      for p in lower_row(k) {
+        let mut i = self.cols[p];
+        y[i] += self.values[p];
+        let mut len = 0;
+        while flags[i] != k {
+          pattern[len] = i;
+          len += 1;
+          flags[i] = k;
+          i = parents[i].unwrap();
+        }
+        while len > 0 {
+          len -= 1;
+          top -= 1;
+          pattern[top] = pattern[len];
+        }
+      }
+      d[k] = y[k];
+      y[k] = 0.0;
+      for &i in pattern[top..n].iter() {
+        let yi = y[i];
+        y[i] = 0.0;
+        let end = starts[i] + counts[i];
+        for p in starts[i]..end {
+          y[l_rows[p]] -= l_values[p] * yi;
+        }
+        let l_ki = yi / d[i];
+        d[k] -= l_ki * yi;
+        l_rows[end] = k;
+        l_values[end] = l_ki;
+        counts[i] += 1;
+      }
+      if d[k] <= 0.0 {
+        return None;
+      }
+    }
+
+    // Solves L * D * L^T * x = b.
+    let mut x = b.to_vec();
+    for j in 0..n {
+      for p in starts[j]..starts[j + 1] {
+        x[l_rows[p]] -= l_values[p] * x[j];
+      }
+    }
+    for j in 0..n {
+      x[j] /= d[j];
+    }
+    for j in (0..n).rev() {
+      for p in starts[j]..starts[j + 1] {
+        x[j] -= l_values[p] * x[l_rows[p]];
+      }
+    }
+    Some(x)
+  }
+
+  /// Initializes from a list of (row, column, value) triplets, in any order.
+  /// Values of duplicate entries are summed up.
+  pub fn new_from_triplets(num_rows: usize, num_cols: usize,
+      triplets: &[(usize, usize, f64)]) -> Self {
+    let mut sorted = triplets.to_vec();
+    for &(i, j, _) in sorted.iter() {
+      assert!(i < num_rows && j < num_cols, "Index out of range!");
+    }
+    sorted.sort_by_key(|t| (t.0, t.1));
+    let mut row_starts = vec![0; num_rows + 1];
+    let mut cols: Vec<usize> = Vec::with_capacity(sorted.len());
+    let mut values: Vec<f64> = Vec::with_capacity(sorted.len());
+    let mut last = None;
+    for &(i, j, v) in sorted.iter() {
+      if last == Some((i, j)) {
+        *values.last_mut().unwrap() += v;
+      } else {
+        cols.push(j);
+        values.push(v);
+        row_starts[i + 1] += 1;
+        last = Some((i, j));
+      }
+    }
+    for i in 0..num_rows {
+      row_starts[i + 1] += row_starts[i];
+    }
+    Self {
+      num_rows: num_rows,
+      num_cols: num_cols,
+      row_starts: row_starts,
+      cols: cols,
+      values: values,
+    }
+  }
+
+  /// Initializes a diagonal matrix from its diagonal entries.
+  pub fn diag(diagonal: &[f64]) -> Self {
+    let triplets: Vec<(usize, usize, f64)> = diagonal.iter().enumerate()
+        .map(|(i, d)| (i, i, *d))
+        .collect();
+    Self::new_from_triplets(diagonal.len(), diagonal.len(), &triplets)
+  }
+
+  /// Returns the weighted sum a * self + b * other of two matrices of the
+  /// same dimensions.
+  pub fn weighted_sum(&self, a: f64, other: &Self, b: f64) -> Self {
+    assert!(self.num_rows == other.num_rows && self.num_cols == other.num_cols,
+        "Dimensions must match!");
+    let mut triplets =
+        Vec::with_capacity(self.values.len() + other.values.len());
+    for &(m, w) in [(self, a), (other, b)].iter() {
+      for i in 0..m.num_rows {
+        for k in m.row_starts[i]..m.row_starts[i + 1] {
+          triplets.push((i, m.cols[k], m.values[k] * w));
+        }
+      }
+    }
+    Self::new_from_triplets(self.num_rows, self.num_cols, &triplets)
+  }
+}
+
+/// Overriding * for scaling a matrix.
+impl Mul<f64> for SparseMatrix {
+  type Output = Self;
+  fn mul(self, rhs: f64) -> Self {
+    let mut m = self;
+    for v in m.values.iter_mut() {
+      *v *= rhs;
+    }
+    m
+  }
+}
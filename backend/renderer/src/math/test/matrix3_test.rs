@@ -146,3 +146,49 @@ fn test_cross_prod_mat() {
 
   dispose_test_bed(&mut tb);
 }
+
+#[test]
+fn test_outer_and_eigen() {
+  let u = Vector3::new(1.0, 2.0, 3.0);
+  let v = Vector3::new(-1.0, 0.0, 2.0);
+  assert_eq!(Matrix3::outer(u, v) * Vector3::z_unit(), u * 2.0);
+  assert_eq!(Matrix3::outer(u, v).t(), Matrix3::outer(v, u));
+  assert_eq!(Matrix3::outer(u, u).trace(), u.len2());
+
+  // Eigenvalues of a diagonal matrix are its diagonal, sorted.
+  let (values, vectors) =
+      Matrix3::diag(Vector3::new(1.0, 3.0, 2.0)).symmetric_eigen();
+  assert_eq!(values, Vector3::new(3.0, 2.0, 1.0));
+  assert_eq!(vectors[0] ^ vectors[1], vectors[2]);
+  assert!((vectors[0] * Vector3::y_unit()).abs() > 1.0 - 1e-9);
+
+  let m = Matrix3::new(
+      4.0, 1.0, -2.0,
+      1.0, 2.0, 0.0,
+      -2.0, 0.0, 3.0);
+  let (values, vectors) = m.symmetric_eigen();
+  assert!((values[0] + values[1] + values[2] - m.trace()).abs() < 1e-9);
+  assert!((values[0] * values[1] * values[2] - m.det()).abs() < 1e-9);
+  assert!(values[0] >= values[1] && values[1] >= values[2]);
+  for i in 0..3 {
+    assert_eq!(m * vectors[i], vectors[i] * values[i]);
+    assert!((vectors[i].len() - 1.0).abs() < 1e-9);
+  }
+  assert_eq!(vectors * vectors.t(), Matrix3::identity());
+  assert!((vectors.det() - 1.0).abs() < 1e-9);
+
+  let nan = Matrix3::diag(Vector3::new(1.0, ::std::f64::NAN, 2.0));
+  let (values, _) = nan.symmetric_eigen();
+  assert!(values[0].is_nan() || values[1].is_nan() || values[2].is_nan());
+}
+
+#[test]
+fn test_inverse() {
+  let tb = create_test_bed();
+  assert!(tb.m1.inverse().is_none());
+  let inv = tb.m2.inverse().unwrap();
+  assert_eq!(tb.m2 * inv, Matrix3::identity());
+  assert_eq!(inv * tb.m2, Matrix3::identity());
+  assert_eq!(Matrix3::diag(Vector3::new(2.0, 4.0, -0.5)).inverse(),
+      Some(Matrix3::diag(Vector3::new(0.5, 0.25, -2.0))));
+}
@@ -0,0 +1,58 @@
+use super::super::{Matrix4, Vector3};
+
+#[test]
+fn init_works() {
+  let m = Matrix4::new_from_arrays(
+      &[1.0, 0.0, 0.0, 0.0],
+      &[0.0, 1.0, 0.0, 0.0],
+      &[0.0, 0.0, 1.0, 0.0],
+      &[0.0, 0.0, 0.0, 1.0]);
+  assert_eq!(m, Matrix4::identity());
+  assert_eq!(Matrix4::diag(2.0, 2.0, 2.0, 2.0), Matrix4::identity() * 2.0);
+  assert_eq!(Matrix4::identity() - Matrix4::identity(), Matrix4::zero());
+  assert!(Matrix4::identity() != Matrix4::zero());
+}
+
+#[test]
+fn test_operations() {
+  let mut m1 = Matrix4::new_from_arrays(
+      &[1.0, 2.0, 0.0, 1.0],
+      &[0.0, 1.0, 3.0, 0.0],
+      &[2.0, 0.0, 1.0, 0.0],
+      &[0.0, 0.0, 0.0, 1.0]);
+  let m2 = Matrix4::translation(Vector3::new(1.0, 2.0, 3.0));
+  // m3 == m1 * m2.
+  let m3 = Matrix4::new_from_arrays(
+      &[1.0, 2.0, 0.0, 6.0],
+      &[0.0, 1.0, 3.0, 11.0],
+      &[2.0, 0.0, 1.0, 5.0],
+      &[0.0, 0.0, 0.0, 1.0]);
+  assert_eq!(m1 * m2, m3);
+  assert_eq!(m1 * Matrix4::identity(), m1);
+  assert_eq!((m1 * m2).t(), m2.t() * m1.t());
+  assert_eq!(m3 / 2.0, m3 * 0.5);
+  assert_eq!(m1[1][2], 3.0);
+  m1[1][2] = -3.0;
+  assert_eq!(m1.t()[2][1], -3.0);
+
+  let p = Vector3::new(1.0, 1.0, 1.0);
+  assert_eq!(m2.transform_point(p), Vector3::new(2.0, 3.0, 4.0));
+  assert_eq!(m2.transform_vector(p), p);
+}
+
+#[test]
+fn test_projections() {
+  let pi = ::std::f64::consts::PI;
+  let m = Matrix4::perspective(pi / 2.0, 2.0, 1.0, 10.0);
+  // Corners of the near and far planes are mapped to the canonical cube.
+  assert_eq!(m.transform_point(Vector3::new(2.0, 1.0, -1.0)),
+      Vector3::new(1.0, 1.0, -1.0));
+  assert_eq!(m.transform_point(Vector3::new(-20.0, -10.0, -10.0)),
+      Vector3::new(-1.0, -1.0, 1.0));
+
+  let o = Matrix4::orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 10.0);
+  assert_eq!(o.transform_point(Vector3::new(2.0, -1.0, -1.0)),
+      Vector3::new(1.0, -1.0, -1.0));
+  assert_eq!(o.transform_point(Vector3::new(0.0, 0.0, -10.0)),
+      Vector3::new(0.0, 0.0, 1.0));
+}
@@ -1,3 +1,5 @@
 mod vector2_test;
 mod vector3_test;
 mod matrix3_test;
+mod matrix4_test;
+mod sparse_matrix_test;
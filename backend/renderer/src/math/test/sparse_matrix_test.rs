@@ -0,0 +1,102 @@
+use super::super::SparseMatrix;
+
+#[test]
+fn test_triplets() {
+  let m = SparseMatrix::new_from_triplets(2, 3,
+      &[(1, 2, 3.0), (0, 1, 1.0), (1, 0, -1.0), (0, 1, 2.0)]);
+  assert_eq!(m.num_rows(), 2);
+  assert_eq!(m.num_cols(), 3);
+  assert_eq!(m.num_entries(), 3);
+  assert_eq!(m.get(0, 1), 3.0);
+  assert_eq!(m.get(1, 0), -1.0);
+  assert_eq!(m.get(1, 2), 3.0);
+  assert_eq!(m.get(0, 0), 0.0);
+  assert_eq!(m.mul_vec(&[1.0, 2.0, 3.0]), vec![6.0, 8.0]);
+  assert_eq!(m.diagonal(), vec![0.0, 0.0]);
+
+  let sum = m.weighted_sum(2.0, &SparseMatrix::new_from_triplets(2, 3,
+      &[(0, 0, 1.0), (1, 2, 1.0)]), -1.0);
+  assert_eq!(sum.get(0, 0), -1.0);
+  assert_eq!(sum.get(0, 1), 6.0);
+  assert_eq!(sum.get(1, 2), 5.0);
+  assert_eq!((sum * 0.5).get(0, 1), 3.0);
+  assert_eq!(SparseMatrix::diag(&[1.0, 2.0]).mul_vec(&[3.0, 3.0]),
+      vec![3.0, 6.0]);
+}
+
+#[test]
+fn test_conjugate_gradient() {
+  // The 1-d Poisson matrix tridiag(-1, 2, -1), which is positive definite.
+  let n = 50;
+  let mut triplets = Vec::new();
+  for i in 0..n {
+    triplets.push((i, i, 2.0));
+    if i > 0 {
+      triplets.push((i, i - 1, -1.0));
+      triplets.push((i - 1, i, -1.0));
+    }
+  }
+  let a = SparseMatrix::new_from_triplets(n, n, &triplets);
+  let expected: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin()).collect();
+  let b = a.mul_vec(&expected);
+  let x = a.solve_conjugate_gradient(&b, 1e-12, 1000).unwrap();
+  for i in 0..n {
+    assert!((x[i] - expected[i]).abs() < 1e-8);
+  }
+  // Too few iterations.
+  assert!(a.solve_conjugate_gradient(&b, 1e-12, 3).is_none());
+  assert_eq!(a.solve_conjugate_gradient(&vec![0.0; n], 1e-12, 1),
+      Some(vec![0.0; n]));
+
+  // The singular Laplacian of a path, with b in its range (summing to 0).
+  let mut triplets = vec![(0, 0, 1.0), (n - 1, n - 1, 1.0)];
+  for i in 1..n - 1 {
+    triplets.push((i, i, 2.0));
+  }
+  for i in 1..n {
+    triplets.push((i, i - 1, -1.0));
+    triplets.push((i - 1, i, -1.0));
+  }
+  let l = SparseMatrix::new_from_triplets(n, n, &triplets);
+  let mut b = vec![0.0; n];
+  b[0] = 1.0;
+  b[n - 1] = -1.0;
+  let x = l.solve_conjugate_gradient(&b, 1e-10, 1000).unwrap();
+  let r = l.mul_vec(&x);
+  for i in 0..n {
+    assert!((r[i] - b[i]).abs() < 1e-8);
+  }
+}
+
+#[test]
+fn test_solve_cholesky() {
+  // A tridiagonal M-matrix, whose solution decays exponentially.
+  let n = 200;
+  let mut triplets = Vec::new();
+  for i in 0..n {
+    triplets.push((i, i, 2.5));
+    if i > 0 {
+      triplets.push((i, i - 1, -1.0));
+      triplets.push((i - 1, i, -1.0));
+    }
+  }
+  let a = SparseMatrix::new_from_triplets(n, n, &triplets);
+  let mut b = vec![0.0; n];
+  b[0] = 1.0;
+  let x = a.solve_cholesky(&b).unwrap();
+  // x_i = 2^-i / 2 solves all rows but the last one, whose correction
+  // vanishes towards the first rows.
+  for (i, xi) in x.iter().enumerate().take(n / 2) {
+    let expected = 0.5f64.powi(i as i32 + 1);
+    assert!(((xi - expected) / expected).abs() < 1e-9);
+  }
+  let r = a.mul_vec(&x);
+  for i in 0..n {
+    assert!((r[i] - b[i]).abs() < 1e-12);
+  }
+
+  // Not positive definite.
+  let singular = SparseMatrix::new_from_triplets(2, 2,
+      &[(0, 0, 1.0), (0, 1, -1.0), (1, 0, -1.0), (1, 1, 1.0)]);
+  assert!(singular.solve_cholesky(&[1.0, -1.0]).is_none());
+}